@@ -0,0 +1,2 @@
+DROP TABLE caldav_object;
+DROP TABLE app_password;
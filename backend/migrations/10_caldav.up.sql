@@ -0,0 +1,19 @@
+CREATE TABLE app_password (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    hashed_password TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_used_at TIMESTAMP WITH TIME ZONE NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id)
+);
+
+CREATE TABLE caldav_object (
+    todo_id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    uid TEXT NOT NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id),
+    CONSTRAINT fk_todo FOREIGN KEY (todo_id) REFERENCES todo (id) ON DELETE CASCADE,
+    CONSTRAINT caldav_object_name UNIQUE (user_id, name)
+);
@@ -0,0 +1,3 @@
+ALTER TABLE todo DROP COLUMN status_id;
+
+DROP TABLE status;
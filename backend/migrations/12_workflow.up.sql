@@ -0,0 +1,25 @@
+CREATE TABLE status (
+    id UUID PRIMARY KEY,
+    project_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_project FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    CONSTRAINT status_category CHECK (category IN ('todo', 'in_progress', 'done'))
+);
+
+ALTER TABLE todo
+    ADD COLUMN status_id UUID,
+    ADD CONSTRAINT fk_status FOREIGN KEY (status_id) REFERENCES status (id) ON DELETE SET NULL;
+
+INSERT INTO status (id, project_id, name, category, position, created_at)
+SELECT gen_random_uuid(), project.id, workflow.name, workflow.category, workflow.position, NOW()
+FROM project
+CROSS JOIN (
+    VALUES
+        ('Backlog', 'todo', 0),
+        ('In progress', 'in_progress', 1),
+        ('Review', 'in_progress', 2),
+        ('Done', 'done', 3)
+) AS workflow (name, category, position);
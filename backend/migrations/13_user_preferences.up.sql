@@ -0,0 +1,9 @@
+CREATE TABLE user_preferences (
+    user_id UUID PRIMARY KEY,
+    time_zone TEXT,
+    page_size INTEGER NOT NULL DEFAULT 10,
+    theme TEXT NOT NULL DEFAULT 'system',
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id) ON DELETE CASCADE,
+    CONSTRAINT user_preferences_theme CHECK (theme IN ('system', 'light', 'dark'))
+);
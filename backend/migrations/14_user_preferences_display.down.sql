@@ -0,0 +1,5 @@
+ALTER TABLE user_preferences
+    DROP COLUMN date_format,
+    DROP COLUMN default_view,
+    DROP COLUMN week_start,
+    DROP COLUMN locale;
@@ -0,0 +1,10 @@
+ALTER TABLE user_preferences
+    ADD COLUMN date_format TEXT NOT NULL DEFAULT 'iso',
+    ADD COLUMN default_view TEXT NOT NULL DEFAULT 'active',
+    ADD COLUMN week_start TEXT NOT NULL DEFAULT 'monday',
+    ADD COLUMN locale TEXT NOT NULL DEFAULT 'en',
+    ADD CONSTRAINT user_preferences_date_format CHECK (date_format IN ('iso', 'european', 'us')),
+    ADD CONSTRAINT user_preferences_default_view CHECK (
+        default_view IN ('active', 'all', 'completed', 'board', 'calendar')
+    ),
+    ADD CONSTRAINT user_preferences_week_start CHECK (week_start IN ('monday', 'sunday'));
@@ -0,0 +1,10 @@
+DROP TRIGGER todo_sync ON todo;
+
+DROP FUNCTION todo_sync;
+
+DROP TABLE todo_tombstone;
+
+DROP INDEX todo_sync;
+
+ALTER TABLE todo
+    DROP COLUMN sync_xid;
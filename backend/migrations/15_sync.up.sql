@@ -0,0 +1,42 @@
+-- Every todo write stamps the writing transaction, deletes leave a tombstone.
+-- Sync tokens are snapshot xmins: all transactions below one are committed,
+-- so changes at or above it are the ones a client may not have seen yet.
+ALTER TABLE todo
+    ADD COLUMN sync_xid XID8 NOT NULL DEFAULT pg_current_xact_id();
+
+CREATE INDEX todo_sync ON todo (user_id, sync_xid);
+
+CREATE TABLE todo_tombstone (
+    todo_id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    deleted_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    sync_xid XID8 NOT NULL DEFAULT pg_current_xact_id(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id) ON DELETE CASCADE
+);
+
+CREATE INDEX todo_tombstone_sync ON todo_tombstone (user_id, sync_xid);
+
+-- A trigger so bulk deletes, cascades and every other write path are covered.
+-- Todos deleted along with their user leave no tombstone.
+CREATE FUNCTION todo_sync() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO todo_tombstone (todo_id, user_id, deleted_at)
+        SELECT OLD.id, OLD.user_id, NOW()
+        WHERE EXISTS (SELECT 1 FROM "user" WHERE id = OLD.user_id);
+
+        RETURN OLD;
+    END IF;
+
+    IF TG_OP = 'INSERT' THEN
+        DELETE FROM todo_tombstone WHERE todo_id = NEW.id;
+    END IF;
+
+    NEW.sync_xid := pg_current_xact_id();
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER todo_sync BEFORE INSERT OR UPDATE OR DELETE ON todo
+    FOR EACH ROW EXECUTE FUNCTION todo_sync();
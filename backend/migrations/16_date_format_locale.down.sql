@@ -0,0 +1,6 @@
+UPDATE user_preferences SET date_format = 'iso' WHERE date_format = 'locale';
+
+ALTER TABLE user_preferences
+    DROP CONSTRAINT user_preferences_date_format,
+    ADD CONSTRAINT user_preferences_date_format CHECK (date_format IN ('iso', 'european', 'us')),
+    ALTER COLUMN date_format SET DEFAULT 'iso';
@@ -0,0 +1,6 @@
+ALTER TABLE user_preferences
+    DROP CONSTRAINT user_preferences_date_format,
+    ADD CONSTRAINT user_preferences_date_format CHECK (
+        date_format IN ('locale', 'iso', 'european', 'us')
+    ),
+    ALTER COLUMN date_format SET DEFAULT 'locale';
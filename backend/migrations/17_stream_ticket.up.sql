@@ -0,0 +1,8 @@
+-- Single-use tickets opening an event stream, so access tokens stay out of
+-- URLs and logs.
+CREATE TABLE stream_ticket (
+    ticket TEXT PRIMARY KEY,
+    user_id UUID NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+CREATE TABLE project (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id)
+);
+
+ALTER TABLE todo
+    ADD COLUMN project_id UUID NULL,
+    ADD CONSTRAINT fk_project FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE SET NULL;
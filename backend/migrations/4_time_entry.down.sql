@@ -0,0 +1,2 @@
+DROP TABLE time_entry;
+ALTER TABLE todo DROP COLUMN estimate_minutes;
@@ -0,0 +1,15 @@
+ALTER TABLE todo ADD COLUMN estimate_minutes INTEGER NULL;
+
+CREATE TABLE time_entry (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    todo_id UUID NOT NULL,
+    note TEXT NOT NULL,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    stopped_at TIMESTAMP WITH TIME ZONE NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id),
+    CONSTRAINT fk_todo FOREIGN KEY (todo_id) REFERENCES todo (id) ON DELETE CASCADE,
+    CONSTRAINT stopped_after_started CHECK (stopped_at IS NULL OR stopped_at >= started_at)
+);
+
+CREATE UNIQUE INDEX time_entry_running_idx ON time_entry (user_id) WHERE stopped_at IS NULL;
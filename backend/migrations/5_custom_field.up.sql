@@ -0,0 +1,22 @@
+CREATE TABLE custom_field (
+    id UUID PRIMARY KEY,
+    project_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    options JSONB NOT NULL,
+    position INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_project FOREIGN KEY (project_id) REFERENCES project (id) ON DELETE CASCADE,
+    CONSTRAINT custom_field_kind CHECK (kind IN ('text', 'number', 'date', 'select', 'checkbox'))
+);
+
+CREATE TABLE custom_field_value (
+    todo_id UUID NOT NULL,
+    field_id UUID NOT NULL,
+    value JSONB NOT NULL,
+    PRIMARY KEY (todo_id, field_id),
+    CONSTRAINT fk_todo FOREIGN KEY (todo_id) REFERENCES todo (id) ON DELETE CASCADE,
+    CONSTRAINT fk_field FOREIGN KEY (field_id) REFERENCES custom_field (id) ON DELETE CASCADE
+);
+
+CREATE INDEX custom_field_value_field_idx ON custom_field_value (field_id, value);
@@ -0,0 +1,4 @@
+DROP TABLE template_item;
+DROP TABLE template;
+ALTER TABLE todo DROP COLUMN parent_id;
+ALTER TABLE todo DROP COLUMN due_at;
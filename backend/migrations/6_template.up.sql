@@ -0,0 +1,22 @@
+ALTER TABLE todo ADD COLUMN due_at TIMESTAMP WITH TIME ZONE NULL;
+ALTER TABLE todo ADD COLUMN parent_id UUID NULL;
+ALTER TABLE todo ADD CONSTRAINT fk_parent FOREIGN KEY (parent_id) REFERENCES todo (id) ON DELETE CASCADE;
+
+CREATE TABLE template (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    name TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id)
+);
+
+CREATE TABLE template_item (
+    id UUID PRIMARY KEY,
+    template_id UUID NOT NULL,
+    parent_id UUID NULL,
+    name TEXT NOT NULL,
+    due_offset_days INTEGER NULL,
+    position INTEGER NOT NULL,
+    CONSTRAINT fk_template FOREIGN KEY (template_id) REFERENCES template (id) ON DELETE CASCADE,
+    CONSTRAINT fk_parent FOREIGN KEY (parent_id) REFERENCES template_item (id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+DROP TABLE notification;
+DROP TABLE reminder;
+DROP TABLE job;
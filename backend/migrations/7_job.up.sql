@@ -0,0 +1,42 @@
+CREATE TABLE job (
+    id UUID PRIMARY KEY,
+    kind TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    status TEXT NOT NULL,
+    run_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    attempts INTEGER NOT NULL,
+    max_attempts INTEGER NOT NULL,
+    last_error TEXT NULL,
+    locked_at TIMESTAMP WITH TIME ZONE NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT job_status CHECK (status IN ('pending', 'running', 'done', 'failed'))
+);
+
+CREATE INDEX job_due_idx ON job (status, run_at);
+
+CREATE TABLE reminder (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    todo_id UUID NOT NULL,
+    remind_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    fired_at TIMESTAMP WITH TIME ZONE NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id),
+    CONSTRAINT fk_todo FOREIGN KEY (todo_id) REFERENCES todo (id) ON DELETE CASCADE
+);
+
+CREATE TABLE notification (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    todo_id UUID NULL,
+    kind TEXT NOT NULL,
+    message TEXT NOT NULL,
+    is_read BOOLEAN NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id),
+    CONSTRAINT fk_todo FOREIGN KEY (todo_id) REFERENCES todo (id) ON DELETE CASCADE,
+    CONSTRAINT notification_kind CHECK (kind IN ('reminder'))
+);
+
+CREATE INDEX notification_user_idx ON notification (user_id, is_read, created_at);
@@ -0,0 +1,6 @@
+CREATE TABLE calendar_token (
+    user_id UUID PRIMARY KEY,
+    token TEXT UNIQUE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES "user" (id)
+);
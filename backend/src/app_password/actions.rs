@@ -0,0 +1,119 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
+    ModelTrait, Order, QueryFilter, QueryOrder, Set,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    auth::security::{self, SecurityError},
+    entities::{app_password, user},
+};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Bcrypt {0}")]
+    Security(#[from] SecurityError),
+    #[error("InvalidCredentials")]
+    InvalidCredentials,
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+}
+
+fn new_password() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+pub async fn get_app_passwords(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<Vec<app_password::Model>, ActionError> {
+    Ok(app_password::Entity::find()
+        .filter(app_password::Column::UserId.eq(*user_id))
+        .order_by(app_password::Column::CreatedAt, Order::Asc)
+        .all(db)
+        .await?)
+}
+
+/// Creates an app password, the plain password is only returned here.
+pub async fn create_app_password(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    name: &str,
+) -> Result<(app_password::Model, String), ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let password = new_password();
+
+    let app_password = app_password::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(*user_id),
+        name: Set(name.to_owned()),
+        hashed_password: Set(security::hash_password(password.clone()).await?),
+        created_at: Set(Utc::now()),
+        last_used_at: Set(None),
+    };
+
+    Ok((app_password.insert(db).await?, password))
+}
+
+pub async fn delete_app_password(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<(), ActionError> {
+    let app_password = app_password::Entity::find_by_id(*id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if app_password.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    app_password.delete(db).await?;
+
+    Ok(())
+}
+
+/// Signs in with an app password, used by clients that only speak Basic auth.
+pub async fn verify_app_password(
+    db: &DatabaseConnection,
+    username: &str,
+    password: &str,
+) -> Result<user::Model, ActionError> {
+    let user = user::Entity::find()
+        .filter(user::Column::Username.eq(username))
+        .one(db)
+        .await?
+        .ok_or(ActionError::InvalidCredentials)?;
+
+    let app_passwords = app_password::Entity::find()
+        .filter(app_password::Column::UserId.eq(user.id))
+        .all(db)
+        .await?;
+
+    for app_password in app_passwords {
+        if security::verify_password(password.to_owned(), app_password.hashed_password.clone())
+            .await?
+        {
+            let mut app_password = app_password.into_active_model();
+
+            app_password.last_used_at = Set(Some(Utc::now()));
+
+            app_password.update(db).await?;
+
+            return Ok(user);
+        }
+    }
+
+    Err(ActionError::InvalidCredentials)
+}
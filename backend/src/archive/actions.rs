@@ -0,0 +1,284 @@
+use std::io::{self, Cursor, Write};
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Serialize;
+use thiserror::Error;
+use uuid::Uuid;
+use zip::{result::ZipError, write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::entities::{
+    app_password, caldav_object, custom_field, custom_field_value, notification, project, reminder,
+    status, template, template_item, time_entry, todo, user, user_preferences, webhook,
+    webhook_delivery,
+};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Io: {0}")]
+    Io(#[from] io::Error),
+    #[error("Json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Zip: {0}")]
+    Zip(#[from] ZipError),
+    #[error("Not Found")]
+    NotFound,
+}
+
+/// Account details without the password hash.
+#[derive(Debug, Serialize)]
+struct Profile {
+    id: Uuid,
+    username: String,
+    joined_at: DateTime<Utc>,
+}
+
+struct Archive {
+    writer: ZipWriter<Cursor<Vec<u8>>>,
+}
+
+impl Archive {
+    fn file<T: Serialize>(&mut self, name: &str, value: &T) -> Result<(), ActionError> {
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        self.writer.start_file(name, options)?;
+        self.writer.write_all(&serde_json::to_vec_pretty(value)?)?;
+
+        Ok(())
+    }
+}
+
+/// Everything stored for the user as a ZIP of JSON files, one per table.
+/// Secrets such as password hashes, webhook secrets and calendar tokens are
+/// left out.
+pub async fn archive(db: &DatabaseConnection, user_id: &Uuid) -> Result<Vec<u8>, ActionError> {
+    let user = user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let preferences = user_preferences::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?;
+
+    let todos = todo::Entity::find()
+        .filter(todo::Column::UserId.eq(*user_id))
+        .order_by_asc(todo::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let custom_field_values = custom_field_value::Entity::find()
+        .inner_join(todo::Entity)
+        .filter(todo::Column::UserId.eq(*user_id))
+        .all(db)
+        .await?;
+
+    let projects = project::Entity::find()
+        .filter(project::Column::UserId.eq(*user_id))
+        .order_by_asc(project::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let custom_fields = custom_field::Entity::find()
+        .inner_join(project::Entity)
+        .filter(project::Column::UserId.eq(*user_id))
+        .order_by_asc(custom_field::Column::Position)
+        .all(db)
+        .await?;
+
+    let statuses = status::Entity::find()
+        .inner_join(project::Entity)
+        .filter(project::Column::UserId.eq(*user_id))
+        .order_by_asc(status::Column::Position)
+        .all(db)
+        .await?;
+
+    let time_entries = time_entry::Entity::find()
+        .filter(time_entry::Column::UserId.eq(*user_id))
+        .order_by_asc(time_entry::Column::StartedAt)
+        .all(db)
+        .await?;
+
+    let templates = template::Entity::find()
+        .filter(template::Column::UserId.eq(*user_id))
+        .order_by_asc(template::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let template_items = template_item::Entity::find()
+        .inner_join(template::Entity)
+        .filter(template::Column::UserId.eq(*user_id))
+        .order_by_asc(template_item::Column::Position)
+        .all(db)
+        .await?;
+
+    let reminders = reminder::Entity::find()
+        .filter(reminder::Column::UserId.eq(*user_id))
+        .order_by_asc(reminder::Column::RemindAt)
+        .all(db)
+        .await?;
+
+    let notifications = notification::Entity::find()
+        .filter(notification::Column::UserId.eq(*user_id))
+        .order_by_asc(notification::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let webhooks = webhook::Entity::find()
+        .filter(webhook::Column::UserId.eq(*user_id))
+        .order_by_asc(webhook::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let webhook_deliveries = webhook_delivery::Entity::find()
+        .inner_join(webhook::Entity)
+        .filter(webhook::Column::UserId.eq(*user_id))
+        .order_by_asc(webhook_delivery::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let app_passwords = app_password::Entity::find()
+        .filter(app_password::Column::UserId.eq(*user_id))
+        .order_by_asc(app_password::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let caldav_objects = caldav_object::Entity::find()
+        .filter(caldav_object::Column::UserId.eq(*user_id))
+        .all(db)
+        .await?;
+
+    let mut archive = Archive {
+        writer: ZipWriter::new(Cursor::new(Vec::new())),
+    };
+
+    archive.file(
+        "profile.json",
+        &Profile {
+            id: user.id,
+            username: user.username,
+            joined_at: user.joined_at,
+        },
+    )?;
+    archive.file("preferences.json", &preferences)?;
+    archive.file("todos.json", &todos)?;
+    archive.file("custom_field_values.json", &custom_field_values)?;
+    archive.file("projects.json", &projects)?;
+    archive.file("custom_fields.json", &custom_fields)?;
+    archive.file("statuses.json", &statuses)?;
+    archive.file("time_entries.json", &time_entries)?;
+    archive.file("templates.json", &templates)?;
+    archive.file("template_items.json", &template_items)?;
+    archive.file("reminders.json", &reminders)?;
+    archive.file("notifications.json", &notifications)?;
+    archive.file("webhooks.json", &webhooks)?;
+    archive.file("webhook_deliveries.json", &webhook_deliveries)?;
+    archive.file("app_passwords.json", &app_passwords)?;
+    archive.file("caldav_objects.json", &caldav_objects)?;
+
+    Ok(archive.writer.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+    use zip::ZipArchive;
+
+    use super::*;
+    use crate::{
+        app_password, calendar,
+        database::testing,
+        webhook::{self, actions::NewWebhook},
+    };
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn archive_has_a_file_per_table_without_secrets() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let mut user = user.into_active_model();
+
+        user.hashed_password = Set("$2b$12$password-hash".to_owned());
+
+        let user = user.update(&db).await.unwrap();
+
+        testing::todo(&db, &user.id, "Water the plants").await;
+
+        let (_, password) = app_password::actions::create_app_password(&db, &user.id, "Phone")
+            .await
+            .unwrap();
+
+        let calendar_token = calendar::actions::get_calendar_token(&db, &user.id)
+            .await
+            .unwrap();
+
+        webhook::actions::create_webhook(
+            &db,
+            &user.id,
+            &NewWebhook {
+                url: "https://93.184.216.34/hook".to_owned(),
+                events: vec![],
+                secret: "webhook-secret".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut zip = ZipArchive::new(Cursor::new(archive(&db, &user.id).await.unwrap())).unwrap();
+
+        let mut names = zip.file_names().map(str::to_owned).collect::<Vec<_>>();
+
+        names.sort();
+
+        assert_eq!(
+            names,
+            [
+                "app_passwords.json",
+                "caldav_objects.json",
+                "custom_field_values.json",
+                "custom_fields.json",
+                "notifications.json",
+                "preferences.json",
+                "profile.json",
+                "projects.json",
+                "reminders.json",
+                "statuses.json",
+                "template_items.json",
+                "templates.json",
+                "time_entries.json",
+                "todos.json",
+                "webhook_deliveries.json",
+                "webhooks.json",
+            ]
+        );
+
+        let mut content = String::new();
+
+        for name in names {
+            zip.by_name(&name)
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+        }
+
+        assert!(content.contains("Water the plants"));
+        assert!(content.contains("Phone"));
+        assert!(content.contains("https://93.184.216.34/hook"));
+
+        for secret in [
+            "hashed_password",
+            "password-hash",
+            "webhook-secret",
+            &password,
+            &calendar_token.token,
+        ] {
+            assert!(!content.contains(secret), "{secret}");
+        }
+    }
+}
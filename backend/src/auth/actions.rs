@@ -1,7 +1,7 @@
 use chrono::Utc;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
-    QueryFilter, Set,
+    ModelTrait, QueryFilter, Set, TransactionTrait,
 };
 use thiserror::Error;
 use uuid::Uuid;
@@ -96,11 +96,39 @@ pub async fn change_password(
 pub async fn sign_refresh(db: &DatabaseConnection, token: String) -> Result<Token, ActionError> {
     let user_id = security::verify_refresh_token(token).await?;
 
-    let user = get_user_by_id(db, &user_id).await?;
+    let user = get_user_by_id(db, &user_id)
+        .await
+        .map_err(|err| match err {
+            ActionError::NotFound => ActionError::InvalidCredentials,
+            err => err,
+        })?;
 
     Ok(security::create_token(user.id).await?)
 }
 
+/// Deletes the user with everything they own in one transaction, the
+/// foreign keys cascade. Issued tokens stop working since their user is
+/// gone.
+pub async fn delete_account(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    password: &str,
+) -> Result<(), ActionError> {
+    let user = get_user_by_id(db, user_id).await?;
+
+    if !security::verify_password(password.to_owned(), user.hashed_password.clone()).await? {
+        return Err(ActionError::InvalidCredentials);
+    }
+
+    let txn = db.begin().await?;
+
+    user.delete(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
 pub async fn get_user_by_id(
     db: &DatabaseConnection,
     id: &Uuid,
@@ -110,3 +138,176 @@ pub async fn get_user_by_id(
         .await?
         .ok_or(ActionError::NotFound)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use chrono::Duration;
+    use sea_orm::{ConnectionTrait, DbBackend, Statement};
+
+    use super::*;
+    use crate::{
+        app_password, calendar,
+        database::testing,
+        event::{memory::MemoryEventBus, ticket},
+        http::extractors::AuthUser,
+        project, reminder,
+        template::{self, actions::NewTemplateItem},
+        time_tracking::{self, actions::NewTimeEntry},
+        todo,
+        webhook::{self, actions::NewWebhook},
+    };
+
+    /// Signs up a user owning a row in every table that belongs to users.
+    async fn seeded_user(db: &DatabaseConnection) -> (Uuid, Token) {
+        env::set_var("SECRET", "secret");
+
+        let token = sign_up(db, "alice", "password").await.unwrap();
+
+        let user_id = security::verify_access_token(token.access.clone())
+            .await
+            .unwrap();
+
+        let events = MemoryEventBus::new();
+
+        project::actions::create_project(db, &user_id, "Home")
+            .await
+            .unwrap();
+
+        let todo = testing::todo(db, &user_id, "Water the plants").await;
+        let deleted = testing::todo(db, &user_id, "Old").await;
+
+        todo::actions::delete_todo(db, &events, &user_id, &deleted.id)
+            .await
+            .unwrap();
+
+        let now = Utc::now();
+
+        time_tracking::actions::create_time_entry(
+            db,
+            &user_id,
+            &todo.id,
+            &NewTimeEntry {
+                note: String::new(),
+                started_at: now - Duration::hours(1),
+                stopped_at: now,
+            },
+        )
+        .await
+        .unwrap();
+
+        template::actions::create_template(
+            db,
+            &user_id,
+            "Weekly",
+            &[NewTemplateItem {
+                name: "Review".to_owned(),
+                due_offset_days: None,
+                subtasks: vec![],
+            }],
+        )
+        .await
+        .unwrap();
+
+        reminder::actions::create_reminder(db, &user_id, &todo.id, &(now + Duration::days(1)))
+            .await
+            .unwrap();
+
+        app_password::actions::create_app_password(db, &user_id, "Phone")
+            .await
+            .unwrap();
+
+        calendar::actions::get_calendar_token(db, &user_id)
+            .await
+            .unwrap();
+
+        ticket::create_ticket(db, &user_id).await.unwrap();
+
+        webhook::actions::create_webhook(
+            db,
+            &user_id,
+            &NewWebhook {
+                url: "https://93.184.216.34/hook".to_owned(),
+                events: vec![],
+                secret: "webhook-secret".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+        (user_id, token)
+    }
+
+    async fn row_counts(db: &DatabaseConnection) -> Vec<(String, i64)> {
+        let tables = db
+            .query_all(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT table_name::TEXT FROM information_schema.tables \
+                 WHERE table_schema = 'public' AND table_type = 'BASE TABLE'"
+                    .to_owned(),
+            ))
+            .await
+            .unwrap();
+
+        let mut counts = Vec::new();
+
+        for table in tables {
+            let table = table.try_get::<String>("", "table_name").unwrap();
+
+            let count = db
+                .query_one(Statement::from_string(
+                    DbBackend::Postgres,
+                    format!("SELECT COUNT(*) AS count FROM \"{table}\""),
+                ))
+                .await
+                .unwrap()
+                .unwrap()
+                .try_get::<i64>("", "count")
+                .unwrap();
+
+            counts.push((table, count));
+        }
+
+        counts
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn wrong_password_keeps_the_account() {
+        let (db, _guard) = testing::db().await;
+
+        let (user_id, _) = seeded_user(&db).await;
+
+        let result = delete_account(&db, &user_id, "wrong").await;
+
+        assert!(matches!(result, Err(ActionError::InvalidCredentials)));
+        assert!(get_user_by_id(&db, &user_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn deleting_the_account_leaves_nothing_behind() {
+        let (db, _guard) = testing::db().await;
+
+        let (user_id, token) = seeded_user(&db).await;
+
+        assert!(AuthUser::from_token(&db, &token.access).await.is_ok());
+        assert!(row_counts(&db)
+            .await
+            .contains(&("todo_tombstone".to_owned(), 1)));
+
+        delete_account(&db, &user_id, "password").await.unwrap();
+
+        // Jobs belong to no user, the reminder job finds its reminder gone.
+        let left = row_counts(&db)
+            .await
+            .into_iter()
+            .filter(|(table, count)| *count > 0 && table != "job")
+            .collect::<Vec<_>>();
+
+        assert!(left.is_empty(), "{left:?}");
+
+        assert!(AuthUser::from_token(&db, &token.access).await.is_err());
+    }
+}
@@ -0,0 +1,590 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+    IntoActiveModel, ModelTrait, Order, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    calendar::ics::{self, ParsedTodo},
+    entities::{caldav_object, project, todo, user},
+    event::{EventBus, TodoEvent, TodoEventKind},
+};
+
+/// Shortest summary accepted, the same as the name of a new todo.
+const MIN_SUMMARY_LENGTH: u64 = 5;
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Invalid calendar data")]
+    InvalidCalendarData,
+    #[error("Precondition Failed")]
+    PreconditionFailed,
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+}
+
+/// Calendar collection, todos without a project live in the inbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum List {
+    Inbox,
+    Project(Uuid),
+}
+
+impl List {
+    pub fn parse(segment: &str) -> Option<Self> {
+        match segment {
+            "inbox" => Some(List::Inbox),
+            segment => segment.parse().ok().map(List::Project),
+        }
+    }
+
+    pub fn segment(&self) -> String {
+        match self {
+            List::Inbox => "inbox".to_owned(),
+            List::Project(id) => id.to_string(),
+        }
+    }
+
+    fn project_id(&self) -> Option<Uuid> {
+        match self {
+            List::Inbox => None,
+            List::Project(id) => Some(*id),
+        }
+    }
+
+    fn apply<Q>(&self, stmt: Q) -> Q
+    where
+        Q: QueryFilter,
+    {
+        match self {
+            List::Inbox => stmt.filter(todo::Column::ProjectId.is_null()),
+            List::Project(id) => stmt.filter(todo::Column::ProjectId.eq(*id)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Collection {
+    pub list: List,
+    pub name: String,
+    /// Changes whenever a todo of the collection is added, changed or removed.
+    pub ctag: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Object {
+    /// Last segment of the object href, e.g. `<todo id>.ics`.
+    pub name: String,
+    pub uid: String,
+    pub todo: todo::Model,
+}
+
+impl Object {
+    fn new(todo: todo::Model, caldav_object: Option<caldav_object::Model>) -> Self {
+        match caldav_object {
+            Some(caldav_object) => Self {
+                name: caldav_object.name,
+                uid: caldav_object.uid,
+                todo,
+            },
+            None => Self {
+                name: format!("{}.ics", todo.id),
+                uid: ics::uid(&todo),
+                todo,
+            },
+        }
+    }
+
+    pub fn etag(&self) -> String {
+        format!("\"{:x}\"", self.todo.updated_at.timestamp_micros())
+    }
+
+    pub fn render(&self) -> String {
+        ics::render_todo(&self.todo, &self.uid)
+    }
+}
+
+/// Preconditions of a write, taken from `If-Match` and `If-None-Match`.
+#[derive(Debug, Default, Clone)]
+pub struct Preconditions {
+    pub if_match: Option<String>,
+    pub if_none_match: Option<String>,
+}
+
+impl Preconditions {
+    fn check(&self, object: Option<&Object>) -> Result<(), ActionError> {
+        let matches = |value: &str| match object {
+            Some(object) => value
+                .split(',')
+                .any(|etag| etag.trim() == "*" || etag.trim() == object.etag()),
+            None => false,
+        };
+
+        if let Some(if_match) = &self.if_match {
+            if !matches(if_match) {
+                return Err(ActionError::PreconditionFailed);
+            }
+        }
+
+        if let Some(if_none_match) = &self.if_none_match {
+            if matches(if_none_match) {
+                return Err(ActionError::PreconditionFailed);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn ctag(updated_at: Option<DateTime<Utc>>, count: i64) -> String {
+    format!(
+        "{:x}-{count}",
+        updated_at
+            .map(|value| value.timestamp_micros())
+            .unwrap_or(0)
+    )
+}
+
+async fn check_user(db: &DatabaseConnection, user_id: &Uuid) -> Result<(), ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    Ok(())
+}
+
+pub async fn get_collections(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<Vec<Collection>, ActionError> {
+    check_user(db, user_id).await?;
+
+    let projects = project::Entity::find()
+        .filter(project::Column::UserId.eq(*user_id))
+        .order_by(project::Column::Name, Order::Asc)
+        .all(db)
+        .await?;
+
+    let stats: Vec<(Option<Uuid>, Option<DateTime<Utc>>, i64)> = todo::Entity::find()
+        .select_only()
+        .column(todo::Column::ProjectId)
+        .column_as(Expr::col(todo::Column::UpdatedAt).max(), "updated_at")
+        .column_as(Expr::col(todo::Column::Id).count(), "count")
+        .filter(todo::Column::UserId.eq(*user_id))
+        .group_by(todo::Column::ProjectId)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let stats: HashMap<Option<Uuid>, (Option<DateTime<Utc>>, i64)> = stats
+        .into_iter()
+        .map(|(project_id, updated_at, count)| (project_id, (updated_at, count)))
+        .collect();
+
+    let collection = |list: List, name: String| {
+        let (updated_at, count) = stats.get(&list.project_id()).copied().unwrap_or((None, 0));
+
+        Collection {
+            list,
+            name,
+            ctag: ctag(updated_at, count),
+        }
+    };
+
+    let mut collections = vec![collection(List::Inbox, "Inbox".to_owned())];
+
+    for project in projects {
+        collections.push(collection(List::Project(project.id), project.name));
+    }
+
+    Ok(collections)
+}
+
+pub async fn get_collection(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    list: &List,
+) -> Result<Collection, ActionError> {
+    check_user(db, user_id).await?;
+
+    let name = match list {
+        List::Inbox => "Inbox".to_owned(),
+        List::Project(project_id) => {
+            let project = project::Entity::find_by_id(*project_id)
+                .one(db)
+                .await?
+                .ok_or(ActionError::NotFound)?;
+
+            if project.user_id != *user_id {
+                return Err(ActionError::Forbidden);
+            }
+
+            project.name
+        }
+    };
+
+    let stmt = todo::Entity::find()
+        .select_only()
+        .column_as(Expr::col(todo::Column::UpdatedAt).max(), "updated_at")
+        .column_as(Expr::col(todo::Column::Id).count(), "count")
+        .filter(todo::Column::UserId.eq(*user_id));
+
+    let (updated_at, count): (Option<DateTime<Utc>>, i64) = list
+        .apply(stmt)
+        .into_tuple()
+        .one(db)
+        .await?
+        .unwrap_or((None, 0));
+
+    Ok(Collection {
+        list: *list,
+        name,
+        ctag: ctag(updated_at, count),
+    })
+}
+
+pub async fn get_objects(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    list: &List,
+) -> Result<Vec<Object>, ActionError> {
+    get_collection(db, user_id, list).await?;
+
+    let stmt = todo::Entity::find()
+        .find_also_related(caldav_object::Entity)
+        .filter(todo::Column::UserId.eq(*user_id));
+
+    let todos = list
+        .apply(stmt)
+        .order_by(todo::Column::CreatedAt, Order::Asc)
+        .all(db)
+        .await?;
+
+    Ok(todos
+        .into_iter()
+        .map(|(todo, caldav_object)| Object::new(todo, caldav_object))
+        .collect())
+}
+
+async fn find_object(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    list: &List,
+    name: &str,
+) -> Result<Option<Object>, ActionError> {
+    let caldav_object = caldav_object::Entity::find()
+        .filter(caldav_object::Column::UserId.eq(*user_id))
+        .filter(caldav_object::Column::Name.eq(name))
+        .one(db)
+        .await?;
+
+    let todo_id = match &caldav_object {
+        Some(caldav_object) => caldav_object.todo_id,
+        None => match name.trim_end_matches(".ics").parse::<Uuid>() {
+            Ok(todo_id) => todo_id,
+            Err(_) => return Ok(None),
+        },
+    };
+
+    let stmt = todo::Entity::find_by_id(todo_id).filter(todo::Column::UserId.eq(*user_id));
+
+    let Some(todo) = list.apply(stmt).one(db).await? else {
+        return Ok(None);
+    };
+
+    let caldav_object = match caldav_object {
+        Some(caldav_object) => Some(caldav_object),
+        None => todo.find_related(caldav_object::Entity).one(db).await?,
+    };
+
+    let object = Object::new(todo, caldav_object);
+
+    Ok((object.name == name).then_some(object))
+}
+
+pub async fn get_object(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    list: &List,
+    name: &str,
+) -> Result<Object, ActionError> {
+    get_collection(db, user_id, list).await?;
+
+    find_object(db, user_id, list, name)
+        .await?
+        .ok_or(ActionError::NotFound)
+}
+
+/// Creates or replaces the todo behind an object, returns whether it was created.
+pub async fn put_object(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    user_id: &Uuid,
+    list: &List,
+    name: &str,
+    content: &str,
+    preconditions: &Preconditions,
+) -> Result<(Object, bool), ActionError> {
+    let ParsedTodo {
+        uid,
+        summary,
+        is_completed,
+        completed_at,
+        due_at,
+    } = ics::parse_todo(content).ok_or(ActionError::InvalidCalendarData)?;
+
+    if !validator::validate_length(&summary, Some(MIN_SUMMARY_LENGTH), None, None) {
+        return Err(ActionError::InvalidCalendarData);
+    }
+
+    get_collection(db, user_id, list).await?;
+
+    let object = find_object(db, user_id, list, name).await?;
+
+    preconditions.check(object.as_ref())?;
+
+    let now = Utc::now();
+
+    let completed_at = match is_completed {
+        true => Some(completed_at.unwrap_or(now)),
+        false => None,
+    };
+
+    match object {
+        Some(object) => {
+            let kind = match (object.todo.is_completed, is_completed) {
+                (false, true) => TodoEventKind::Completed,
+                (true, false) => TodoEventKind::Reverted,
+                _ => TodoEventKind::Updated,
+            };
+
+            let changes_completion = object.todo.is_completed != is_completed;
+
+            let updated_at = object.todo.updated_at;
+
+            let mut todo = object.todo.into_active_model();
+
+            if changes_completion {
+                todo.status_id = Set(None);
+            }
+
+            todo.name = Set(summary);
+            todo.is_completed = Set(is_completed);
+            todo.completed_at = Set(completed_at);
+            todo.due_at = Set(due_at);
+            todo.updated_at = Set(now);
+
+            let mut update = todo::Entity::update(todo);
+
+            // The etag is the update time, a todo changed since it matched is left alone.
+            if preconditions.if_match.is_some() {
+                update = update.filter(todo::Column::UpdatedAt.eq(updated_at));
+            }
+
+            let todo = match update.exec(db).await {
+                Ok(todo) => todo,
+                Err(DbErr::RecordNotUpdated) => return Err(ActionError::PreconditionFailed),
+                Err(err) => return Err(err.into()),
+            };
+
+            events.publish(TodoEvent::new(kind, &todo)).await;
+
+            Ok((
+                Object {
+                    name: object.name,
+                    uid: object.uid,
+                    todo,
+                },
+                false,
+            ))
+        }
+        None => {
+            let uid = uid.ok_or(ActionError::InvalidCalendarData)?;
+
+            let todo = todo::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                user_id: Set(*user_id),
+                project_id: Set(list.project_id()),
+                name: Set(summary),
+                is_completed: Set(is_completed),
+                estimate_minutes: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+                completed_at: Set(completed_at),
+                due_at: Set(due_at),
+                parent_id: Set(None),
+                status_id: Set(None),
+            };
+
+            let txn = db.begin().await?;
+
+            let todo = todo.insert(&txn).await?;
+
+            let caldav_object = caldav_object::ActiveModel {
+                todo_id: Set(todo.id),
+                user_id: Set(*user_id),
+                name: Set(name.to_owned()),
+                uid: Set(uid),
+            }
+            .insert(&txn)
+            .await?;
+
+            txn.commit().await?;
+
+            events
+                .publish(TodoEvent::new(TodoEventKind::Created, &todo))
+                .await;
+
+            Ok((Object::new(todo, Some(caldav_object)), true))
+        }
+    }
+}
+
+pub async fn delete_object(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    user_id: &Uuid,
+    list: &List,
+    name: &str,
+    preconditions: &Preconditions,
+) -> Result<(), ActionError> {
+    get_collection(db, user_id, list).await?;
+
+    let object = find_object(db, user_id, list, name)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    preconditions.check(Some(&object))?;
+
+    let id = object.todo.id;
+
+    let mut delete = todo::Entity::delete_many().filter(todo::Column::Id.eq(id));
+
+    if preconditions.if_match.is_some() {
+        delete = delete.filter(todo::Column::UpdatedAt.eq(object.todo.updated_at));
+    }
+
+    if delete.exec(db).await?.rows_affected == 0 {
+        return Err(match preconditions.if_match {
+            Some(_) => ActionError::PreconditionFailed,
+            None => ActionError::NotFound,
+        });
+    }
+
+    events.publish(TodoEvent::deleted(*user_id, id)).await;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{database::testing, event::memory::MemoryEventBus};
+
+    fn calendar(summary: &str) -> String {
+        format!("BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:uid-1\r\nSUMMARY:{summary}\r\nEND:VTODO\r\nEND:VCALENDAR\r\n")
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn refuses_summaries_shorter_than_todo_names() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let result = put_object(
+            &db,
+            &MemoryEventBus::new(),
+            &user.id,
+            &List::Inbox,
+            "a.ics",
+            &calendar("Milk"),
+            &Preconditions::default(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ActionError::InvalidCalendarData)));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn only_one_of_concurrent_writes_with_the_same_etag_wins() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let events = MemoryEventBus::new();
+
+        let (object, created) = put_object(
+            &db,
+            &events,
+            &user.id,
+            &List::Inbox,
+            "a.ics",
+            &calendar("Buy milk"),
+            &Preconditions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(created);
+
+        let preconditions = Preconditions {
+            if_match: Some(object.etag()),
+            if_none_match: None,
+        };
+
+        let first = calendar("Buy oat milk");
+        let second = calendar("Buy soy milk");
+
+        let results = tokio::join!(
+            put_object(
+                &db,
+                &events,
+                &user.id,
+                &List::Inbox,
+                "a.ics",
+                &first,
+                &preconditions
+            ),
+            put_object(
+                &db,
+                &events,
+                &user.id,
+                &List::Inbox,
+                "a.ics",
+                &second,
+                &preconditions
+            ),
+        );
+
+        let results = [results.0, results.1];
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(results
+            .iter()
+            .any(|result| matches!(result, Err(ActionError::PreconditionFailed))));
+
+        let stale = delete_object(
+            &db,
+            &events,
+            &user.id,
+            &List::Inbox,
+            "a.ics",
+            &preconditions,
+        )
+        .await;
+
+        assert!(matches!(stale, Err(ActionError::PreconditionFailed)));
+    }
+}
@@ -0,0 +1,479 @@
+//! Just enough XML for WebDAV request bodies and multistatus responses.
+
+use std::collections::HashMap;
+
+pub const DAV: &str = "DAV:";
+pub const CALDAV: &str = "urn:ietf:params:xml:ns:caldav";
+pub const CALENDAR_SERVER: &str = "http://calendarserver.org/ns/";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Name {
+    pub namespace: String,
+    pub local: String,
+}
+
+impl Name {
+    pub fn new(namespace: &str, local: &str) -> Self {
+        Self {
+            namespace: namespace.to_owned(),
+            local: local.to_owned(),
+        }
+    }
+
+    pub fn is(&self, namespace: &str, local: &str) -> bool {
+        self.namespace == namespace && self.local == local
+    }
+
+    fn prefix(&self) -> Option<&'static str> {
+        match self.namespace.as_str() {
+            DAV => Some("d"),
+            CALDAV => Some("c"),
+            CALENDAR_SERVER => Some("cs"),
+            _ => None,
+        }
+    }
+
+    fn open(&self, empty: bool) -> String {
+        let end = if empty { "/>" } else { ">" };
+
+        match self.prefix() {
+            Some(prefix) => format!("<{prefix}:{}{end}", self.local),
+            None => format!(
+                "<x:{} xmlns:x=\"{}\"{end}",
+                self.local,
+                escape(&self.namespace)
+            ),
+        }
+    }
+
+    fn close(&self) -> String {
+        format!("</{}:{}>", self.prefix().unwrap_or("x"), self.local)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Element {
+    pub name: Name,
+    pub attributes: HashMap<String, String>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    /// Parses a document into its root element, `None` if it isn't well formed enough.
+    pub fn parse(body: &str) -> Option<Element> {
+        let mut scopes: Vec<HashMap<String, String>> = Vec::new();
+        let mut stack: Vec<Element> = Vec::new();
+
+        let mut rest = body;
+
+        while let Some(start) = rest.find('<') {
+            if let Some(element) = stack.last_mut() {
+                element.text.push_str(&unescape(&rest[..start]));
+            }
+
+            rest = &rest[start..];
+
+            if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let end = cdata.find("]]>")?;
+
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&cdata[..end]);
+                }
+
+                rest = &cdata[end + 3..];
+
+                continue;
+            }
+
+            let skip_to = if rest.starts_with("<?") {
+                Some("?>")
+            } else if rest.starts_with("<!--") {
+                Some("-->")
+            } else if rest.starts_with("<!") {
+                Some(">")
+            } else {
+                None
+            };
+
+            if let Some(skip_to) = skip_to {
+                let end = rest.find(skip_to)?;
+
+                rest = &rest[end + skip_to.len()..];
+
+                continue;
+            }
+
+            let end = rest.find('>')?;
+            let tag = &rest[1..end];
+
+            rest = &rest[end + 1..];
+
+            if tag.starts_with('/') {
+                scopes.pop();
+
+                let element = stack.pop()?;
+
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Some(element),
+                }
+
+                continue;
+            }
+
+            let (tag, empty) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+
+            let (qualified, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+
+            let attributes = parse_attributes(attributes);
+
+            let mut scope = HashMap::new();
+
+            for (name, value) in &attributes {
+                if name == "xmlns" {
+                    scope.insert(String::new(), value.clone());
+                } else if let Some(prefix) = name.strip_prefix("xmlns:") {
+                    scope.insert(prefix.to_owned(), value.clone());
+                }
+            }
+
+            scopes.push(scope);
+
+            let (prefix, local) = qualified.split_once(':').unwrap_or(("", qualified));
+
+            let namespace = scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.get(prefix))
+                .cloned()
+                .unwrap_or_default();
+
+            let element = Element {
+                name: Name::new(&namespace, local),
+                attributes,
+                children: Vec::new(),
+                text: String::new(),
+            };
+
+            if empty {
+                scopes.pop();
+
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Some(element),
+                }
+            } else {
+                stack.push(element);
+            }
+        }
+
+        None
+    }
+
+    pub fn child(&self, namespace: &str, local: &str) -> Option<&Element> {
+        self.children
+            .iter()
+            .find(|child| child.name.is(namespace, local))
+    }
+
+    pub fn children_named<'a>(
+        &'a self,
+        namespace: &'a str,
+        local: &'a str,
+    ) -> impl Iterator<Item = &'a Element> {
+        self.children
+            .iter()
+            .filter(move |child| child.name.is(namespace, local))
+    }
+}
+
+fn parse_attributes(mut rest: &str) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+
+    while let Some((name, value)) = rest.split_once('=') {
+        let value = value.trim_start();
+
+        let Some(quote) = value
+            .chars()
+            .next()
+            .filter(|char| *char == '"' || *char == '\'')
+        else {
+            break;
+        };
+
+        let Some(end) = value[1..].find(quote) else {
+            break;
+        };
+
+        attributes.insert(name.trim().to_owned(), unescape(&value[1..end + 1]));
+
+        rest = &value[end + 2..];
+    }
+
+    attributes
+}
+
+pub fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Properties of a single resource, `None` values are reported as not found.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub href: String,
+    pub props: Vec<(Name, Option<String>)>,
+    pub status: Option<&'static str>,
+}
+
+impl Response {
+    pub fn new(href: String) -> Self {
+        Self {
+            href,
+            props: Vec::new(),
+            status: None,
+        }
+    }
+
+    pub fn not_found(href: String) -> Self {
+        Self {
+            href,
+            props: Vec::new(),
+            status: Some("HTTP/1.1 404 Not Found"),
+        }
+    }
+
+    pub fn prop(&mut self, name: Name, value: Option<String>) {
+        self.props.push((name, value));
+    }
+
+    fn render(&self, content: &mut String) {
+        content.push_str("<d:response><d:href>");
+        content.push_str(&escape(&self.href));
+        content.push_str("</d:href>");
+
+        if let Some(status) = self.status {
+            content.push_str(&format!("<d:status>{status}</d:status></d:response>"));
+
+            return;
+        }
+
+        let found: Vec<_> = self
+            .props
+            .iter()
+            .filter_map(|(name, value)| value.as_ref().map(|value| (name, value)))
+            .collect();
+
+        let missing: Vec<_> = self
+            .props
+            .iter()
+            .filter(|(_, value)| value.is_none())
+            .map(|(name, _)| name)
+            .collect();
+
+        if !found.is_empty() {
+            content.push_str("<d:propstat><d:prop>");
+
+            for (name, value) in found {
+                if value.is_empty() {
+                    content.push_str(&name.open(true));
+                } else {
+                    content.push_str(&name.open(false));
+                    content.push_str(value);
+                    content.push_str(&name.close());
+                }
+            }
+
+            content.push_str("</d:prop><d:status>HTTP/1.1 200 OK</d:status></d:propstat>");
+        }
+
+        if !missing.is_empty() {
+            content.push_str("<d:propstat><d:prop>");
+
+            for name in missing {
+                content.push_str(&name.open(true));
+            }
+
+            content.push_str("</d:prop><d:status>HTTP/1.1 404 Not Found</d:status></d:propstat>");
+        }
+
+        content.push_str("</d:response>");
+    }
+}
+
+/// Body of a response refused because of a failed precondition, e.g. `CALDAV:valid-calendar-data`.
+pub fn error(condition: &Name) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<d:error xmlns:d=\"{DAV}\" xmlns:c=\"{CALDAV}\" xmlns:cs=\"{CALENDAR_SERVER}\">{}</d:error>\n",
+        condition.open(true)
+    )
+}
+
+pub fn multistatus(responses: &[Response]) -> String {
+    let mut content = format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<d:multistatus xmlns:d=\"{DAV}\" xmlns:c=\"{CALDAV}\" xmlns:cs=\"{CALENDAR_SERVER}\">"
+    );
+
+    for response in responses {
+        response.render(&mut content);
+    }
+
+    content.push_str("</d:multistatus>\n");
+
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_namespaced_props() {
+        let root = Element::parse(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+            <d:propfind xmlns:d="DAV:" xmlns:cs="http://calendarserver.org/ns/">
+                <d:prop>
+                    <d:displayname/>
+                    <cs:getctag />
+                    <c:supported-calendar-component-set xmlns:c="urn:ietf:params:xml:ns:caldav"/>
+                    <x:color xmlns:x="http://apple.com/ns/ical/"/>
+                </d:prop>
+            </d:propfind>"#,
+        )
+        .unwrap();
+
+        assert!(root.name.is(DAV, "propfind"));
+
+        let names: Vec<_> = root
+            .child(DAV, "prop")
+            .unwrap()
+            .children
+            .iter()
+            .map(|child| child.name.clone())
+            .collect();
+
+        assert_eq!(
+            names,
+            [
+                Name::new(DAV, "displayname"),
+                Name::new(CALENDAR_SERVER, "getctag"),
+                Name::new(CALDAV, "supported-calendar-component-set"),
+                Name::new("http://apple.com/ns/ical/", "color"),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_namespace_applies_to_unprefixed_elements() {
+        let root = Element::parse(r#"<propfind xmlns="DAV:"><allprop/></propfind>"#).unwrap();
+
+        assert!(root.name.is(DAV, "propfind"));
+        assert!(root.child(DAV, "allprop").is_some());
+    }
+
+    #[test]
+    fn reads_calendar_multiget_hrefs() {
+        let root = Element::parse(
+            r#"<C:calendar-multiget xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+                <D:prop><D:getetag/><C:calendar-data/></D:prop>
+                <D:href>/caldav/inbox/a.ics</D:href>
+                <D:href>/caldav/inbox/b%20c.ics?x=1&amp;y=2</D:href>
+            </C:calendar-multiget>"#,
+        )
+        .unwrap();
+
+        assert!(root.name.is(CALDAV, "calendar-multiget"));
+
+        let hrefs: Vec<_> = root
+            .children_named(DAV, "href")
+            .map(|href| href.text.as_str())
+            .collect();
+
+        assert_eq!(
+            hrefs,
+            ["/caldav/inbox/a.ics", "/caldav/inbox/b%20c.ics?x=1&y=2"]
+        );
+    }
+
+    #[test]
+    fn keeps_cdata_as_is() {
+        let root =
+            Element::parse("<d:href xmlns:d=\"DAV:\"><![CDATA[/a&b<c>.ics]]></d:href>").unwrap();
+
+        assert_eq!(root.text, "/a&b<c>.ics");
+    }
+
+    #[test]
+    fn skips_comments_and_doctype() {
+        let root =
+            Element::parse("<!DOCTYPE x><!-- <d:nope/> --><d:href xmlns:d=\"DAV:\">a</d:href>")
+                .unwrap();
+
+        assert_eq!(root.text, "a");
+    }
+
+    #[test]
+    fn rejects_malformed_or_truncated_input() {
+        for body in [
+            "",
+            "not xml",
+            "<d:propfind xmlns:d=\"DAV:\">",
+            "<d:propfind xmlns:d=\"DAV:\"><d:prop>",
+            "<d:propfind xmlns:d=\"DAV:\"",
+            "<d:href xmlns:d=\"DAV:\"><![CDATA[/a.ics</d:href>",
+            "<!-- unterminated",
+            "</d:propfind>",
+        ] {
+            assert!(Element::parse(body).is_none(), "{body:?}");
+        }
+    }
+
+    #[test]
+    fn reads_attributes_with_either_quote() {
+        let root = Element::parse(r#"<x a="1" b='two &amp; three'/>"#).unwrap();
+
+        assert_eq!(root.attributes["a"], "1");
+        assert_eq!(root.attributes["b"], "two & three");
+    }
+
+    #[test]
+    fn renders_found_and_missing_props() {
+        let mut response = Response::new("/caldav/a&b/".to_owned());
+
+        response.prop(Name::new(DAV, "displayname"), Some("Inbox".to_owned()));
+        response.prop(Name::new(CALENDAR_SERVER, "getctag"), None);
+
+        let body = multistatus(&[response]);
+
+        assert!(body.contains("<d:href>/caldav/a&amp;b/</d:href>"));
+        assert!(body.contains("<d:prop><d:displayname>Inbox</d:displayname></d:prop><d:status>HTTP/1.1 200 OK</d:status>"));
+        assert!(body
+            .contains("<d:prop><cs:getctag/></d:prop><d:status>HTTP/1.1 404 Not Found</d:status>"));
+    }
+
+    #[test]
+    fn renders_precondition_errors() {
+        let body = error(&Name::new(CALDAV, "valid-calendar-data"));
+
+        let root = Element::parse(&body).unwrap();
+
+        assert!(root.name.is(DAV, "error"));
+        assert!(root.child(CALDAV, "valid-calendar-data").is_some());
+    }
+}
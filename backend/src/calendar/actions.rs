@@ -0,0 +1,121 @@
+use chrono::Utc;
+use sea_orm::{
+    sea_query::OnConflict, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, Order, QueryFilter,
+    QueryOrder, Set,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::ics;
+use crate::entities::{calendar_token, project, todo, user};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+}
+
+fn new_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+async fn set_token(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    update: bool,
+) -> Result<calendar_token::Model, ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let token = calendar_token::ActiveModel {
+        user_id: Set(*user_id),
+        token: Set(new_token()),
+        created_at: Set(Utc::now()),
+    };
+
+    let mut on_conflict = OnConflict::column(calendar_token::Column::UserId);
+
+    if update {
+        on_conflict.update_columns([
+            calendar_token::Column::Token,
+            calendar_token::Column::CreatedAt,
+        ]);
+    } else {
+        on_conflict.do_nothing();
+    }
+
+    match calendar_token::Entity::insert(token)
+        .on_conflict(on_conflict)
+        .exec(db)
+        .await
+    {
+        Ok(_) | Err(DbErr::RecordNotInserted) => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    calendar_token::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)
+}
+
+/// Returns the feed token of the user, creating one on first use.
+pub async fn get_calendar_token(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<calendar_token::Model, ActionError> {
+    set_token(db, user_id, false).await
+}
+
+/// Replaces the feed token, so previously shared feed URLs stop working.
+pub async fn regenerate_calendar_token(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<calendar_token::Model, ActionError> {
+    set_token(db, user_id, true).await
+}
+
+pub async fn get_feed(
+    db: &DatabaseConnection,
+    token: &str,
+    project_id: &Option<Uuid>,
+) -> Result<String, ActionError> {
+    let calendar_token = calendar_token::Entity::find()
+        .filter(calendar_token::Column::Token.eq(token))
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let mut stmt = todo::Entity::find().filter(todo::Column::UserId.eq(calendar_token.user_id));
+
+    let name = match project_id {
+        Some(project_id) => {
+            let project = project::Entity::find_by_id(*project_id)
+                .one(db)
+                .await?
+                .ok_or(ActionError::NotFound)?;
+
+            if project.user_id != calendar_token.user_id {
+                return Err(ActionError::Forbidden);
+            }
+
+            stmt = stmt.filter(todo::Column::ProjectId.eq(*project_id));
+
+            format!("Todos - {}", project.name)
+        }
+        None => "Todos".to_owned(),
+    };
+
+    let todos = stmt
+        .order_by(todo::Column::CreatedAt, Order::Asc)
+        .all(db)
+        .await?;
+
+    Ok(ics::render(&name, &todos))
+}
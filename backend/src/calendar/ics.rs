@@ -0,0 +1,447 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::entities::todo;
+
+/// Longest content line in octets, longer ones are folded (RFC 5545 3.1).
+const LINE_LENGTH: usize = 75;
+
+fn format_date_time(value: &DateTime<Utc>) -> String {
+    value.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+struct Calendar {
+    content: String,
+}
+
+impl Calendar {
+    fn line(&mut self, name: &str, value: &str) {
+        let line = format!("{name}:{value}");
+
+        let mut length = 0;
+
+        for char in line.chars() {
+            if length + char.len_utf8() > LINE_LENGTH {
+                self.content.push_str("\r\n ");
+
+                length = 1;
+            }
+
+            self.content.push(char);
+
+            length += char.len_utf8();
+        }
+
+        self.content.push_str("\r\n");
+    }
+
+    fn header(&mut self) {
+        self.line("BEGIN", "VCALENDAR");
+        self.line("VERSION", "2.0");
+        self.line("PRODID", "-//todos-rs//Todos//EN");
+        self.line("CALSCALE", "GREGORIAN");
+    }
+
+    fn todo(&mut self, todo: &todo::Model, uid: &str, now: &DateTime<Utc>) {
+        self.line("BEGIN", "VTODO");
+        self.line("UID", &escape(uid));
+        self.line("DTSTAMP", &format_date_time(now));
+        self.line("CREATED", &format_date_time(&todo.created_at));
+        self.line("LAST-MODIFIED", &format_date_time(&todo.updated_at));
+        self.line("SUMMARY", &escape(&todo.name));
+
+        if let Some(due_at) = &todo.due_at {
+            self.line("DUE", &format_date_time(due_at));
+        }
+
+        if todo.is_completed {
+            self.line("STATUS", "COMPLETED");
+            self.line("PERCENT-COMPLETE", "100");
+        } else {
+            self.line("STATUS", "NEEDS-ACTION");
+        }
+
+        if let Some(completed_at) = &todo.completed_at {
+            self.line("COMPLETED", &format_date_time(completed_at));
+        }
+
+        if let Some(parent_id) = &todo.parent_id {
+            self.line("RELATED-TO", &format!("{parent_id}@todos"));
+        }
+
+        self.line("END", "VTODO");
+    }
+
+    /// Calendars that ignore `VTODO` still show due todos as events.
+    fn event(&mut self, todo: &todo::Model, due_at: &DateTime<Utc>, now: &DateTime<Utc>) {
+        self.line("BEGIN", "VEVENT");
+        self.line("UID", &format!("{}-due@todos", todo.id));
+        self.line("DTSTAMP", &format_date_time(now));
+        self.line("CREATED", &format_date_time(&todo.created_at));
+        self.line("LAST-MODIFIED", &format_date_time(&todo.updated_at));
+        self.line("SUMMARY", &escape(&todo.name));
+        self.line("DTSTART", &format_date_time(due_at));
+        self.line("TRANSP", "TRANSPARENT");
+
+        if todo.is_completed {
+            self.line("STATUS", "CANCELLED");
+        } else {
+            self.line("STATUS", "CONFIRMED");
+        }
+
+        self.line("END", "VEVENT");
+    }
+}
+
+/// `UID` of todos that weren't created by a CalDAV client.
+pub fn uid(todo: &todo::Model) -> String {
+    format!("{}@todos", todo.id)
+}
+
+pub fn render(name: &str, todos: &[todo::Model]) -> String {
+    let now = Utc::now();
+
+    let mut calendar = Calendar {
+        content: String::new(),
+    };
+
+    calendar.header();
+    calendar.line("X-WR-CALNAME", &escape(name));
+
+    for todo in todos {
+        calendar.todo(todo, &uid(todo), &now);
+
+        if let Some(due_at) = &todo.due_at {
+            calendar.event(todo, due_at, &now);
+        }
+    }
+
+    calendar.line("END", "VCALENDAR");
+
+    calendar.content
+}
+
+/// Calendar object with a single `VTODO`, as served over CalDAV.
+pub fn render_todo(todo: &todo::Model, uid: &str) -> String {
+    let mut calendar = Calendar {
+        content: String::new(),
+    };
+
+    calendar.header();
+    calendar.todo(todo, uid, &Utc::now());
+    calendar.line("END", "VCALENDAR");
+
+    calendar.content
+}
+
+/// Fields of a `VTODO` sent by a client, anything else is dropped.
+#[derive(Debug, Default, Clone)]
+pub struct ParsedTodo {
+    pub uid: Option<String>,
+    pub summary: String,
+    pub is_completed: bool,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    let mut chars = value.chars();
+
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            result.push(char);
+
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') | Some('N') => result.push('\n'),
+            Some(char) => result.push(char),
+            None => {}
+        }
+    }
+
+    result
+}
+
+/// Reads a date-time in its `TZID` time zone, dates as their midnight in UTC.
+///
+/// Floating date-times (neither `Z` nor `TZID`) and `TZID`s that aren't IANA
+/// names, like the ones of custom `VTIMEZONE`s, are read as UTC. Times in a
+/// daylight saving gap are moved past it.
+fn parse_date_time(value: &str, tzid: Option<&str>) -> Option<DateTime<Utc>> {
+    if let Some(utc) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(|value| Utc.from_utc_datetime(&value));
+    }
+
+    if let Ok(value) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        let Some(tz) = tzid.and_then(|tzid| tzid.parse::<Tz>().ok()) else {
+            return Some(Utc.from_utc_datetime(&value));
+        };
+
+        return tz
+            .from_local_datetime(&value)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(value + Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|value| value.with_timezone(&Utc));
+    }
+
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()
+        .and_then(|value| value.and_hms_opt(0, 0, 0))
+        .map(|value| Utc.from_utc_datetime(&value))
+}
+
+/// Reads the first `VTODO` of a calendar object, `None` if there is none.
+pub fn parse_todo(content: &str) -> Option<ParsedTodo> {
+    let content = content
+        .replace("\r\n ", "")
+        .replace("\r\n\t", "")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut todo: Option<ParsedTodo> = None;
+
+    let mut depth = 0;
+
+    for line in content.lines() {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        let mut params = name.split(';');
+
+        let name = params.next().unwrap_or_default().to_uppercase();
+
+        let tzid = params.find_map(|param| {
+            param
+                .split_once('=')
+                .filter(|(key, _)| key.eq_ignore_ascii_case("TZID"))
+                .map(|(_, value)| value.trim_matches('"'))
+        });
+
+        match (name.as_str(), value) {
+            ("BEGIN", "VTODO") if todo.is_none() => {
+                todo = Some(ParsedTodo::default());
+
+                continue;
+            }
+            ("END", "VTODO") if depth == 0 => break,
+            ("BEGIN", _) if todo.is_some() => depth += 1,
+            ("END", _) if todo.is_some() => depth -= 1,
+            _ => {}
+        }
+
+        let Some(todo) = todo.as_mut().filter(|_| depth == 0) else {
+            continue;
+        };
+
+        match name.as_str() {
+            "UID" => todo.uid = Some(unescape(value)),
+            "SUMMARY" => todo.summary = unescape(value),
+            "STATUS" => todo.is_completed = value.eq_ignore_ascii_case("COMPLETED"),
+            "COMPLETED" => todo.completed_at = parse_date_time(value, tzid),
+            "DUE" => todo.due_at = parse_date_time(value, tzid),
+            _ => {}
+        }
+    }
+
+    todo
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn todo(name: &str) -> todo::Model {
+        todo::Model {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            project_id: None,
+            name: name.to_owned(),
+            is_completed: false,
+            estimate_minutes: None,
+            created_at: at("2023-04-01T08:00:00Z"),
+            updated_at: at("2023-04-02T09:30:00Z"),
+            completed_at: None,
+            due_at: None,
+            parent_id: None,
+            status_id: None,
+        }
+    }
+
+    fn lines(content: &str) -> Vec<&str> {
+        content
+            .split("\r\n")
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        let value = "Milk; eggs, bread\\butter\nand jam";
+
+        assert_eq!(escape(value), r"Milk\; eggs\, bread\\butter\nand jam");
+        assert_eq!(unescape(&escape(value)), value);
+    }
+
+    #[test]
+    fn folds_long_lines_without_splitting_characters() {
+        let name = "Überprüfen ".repeat(20);
+
+        let content = render_todo(&todo(&name), "uid@todos");
+
+        for line in lines(&content) {
+            assert!(line.len() <= LINE_LENGTH, "{line:?}");
+        }
+
+        assert_eq!(parse_todo(&content).unwrap().summary, name);
+    }
+
+    #[test]
+    fn renders_due_todos_as_events_too() {
+        let mut due = todo("Pay rent");
+
+        due.due_at = Some(at("2023-04-05T17:00:00Z"));
+
+        let mut done = todo("Buy milk");
+
+        done.is_completed = true;
+        done.completed_at = Some(at("2023-04-03T10:00:00Z"));
+
+        let content = render("Inbox", &[due.clone(), done.clone()]);
+
+        let lines = lines(&content);
+
+        assert_eq!(lines.first(), Some(&"BEGIN:VCALENDAR"));
+        assert_eq!(lines.last(), Some(&"END:VCALENDAR"));
+        assert!(lines.contains(&"X-WR-CALNAME:Inbox"));
+        assert_eq!(
+            lines.iter().filter(|line| **line == "BEGIN:VTODO").count(),
+            2
+        );
+        assert_eq!(
+            lines.iter().filter(|line| **line == "BEGIN:VEVENT").count(),
+            1
+        );
+        assert!(lines.contains(&format!("UID:{}", uid(&due)).as_str()));
+        assert!(lines.contains(&format!("UID:{}-due@todos", due.id).as_str()));
+        assert!(lines.contains(&"DUE:20230405T170000Z"));
+        assert!(lines.contains(&"DTSTART:20230405T170000Z"));
+        assert!(lines.contains(&"STATUS:COMPLETED"));
+        assert!(lines.contains(&"COMPLETED:20230403T100000Z"));
+    }
+
+    #[test]
+    fn reads_back_rendered_todos() {
+        let mut todo = todo("Call mom, then dad");
+
+        todo.is_completed = true;
+        todo.completed_at = Some(at("2023-04-03T10:00:00Z"));
+        todo.due_at = Some(at("2023-04-05T17:00:00Z"));
+
+        let parsed = parse_todo(&render_todo(&todo, "abc@example.com")).unwrap();
+
+        assert_eq!(parsed.uid.as_deref(), Some("abc@example.com"));
+        assert_eq!(parsed.summary, "Call mom, then dad");
+        assert!(parsed.is_completed);
+        assert_eq!(parsed.completed_at, todo.completed_at);
+        assert_eq!(parsed.due_at, todo.due_at);
+    }
+
+    #[test]
+    fn reads_the_first_todo_of_client_calendars() {
+        let parsed = parse_todo(
+            "BEGIN:VCALENDAR\n\
+             BEGIN:VTIMEZONE\n\
+             TZID:Europe/Berlin\n\
+             END:VTIMEZONE\n\
+             BEGIN:VTODO\n\
+             UID:1234\n\
+             SUMMARY;LANGUAGE=en:Water the\n \x20plants\n\
+             DUE;TZID=Europe/Berlin:20230405T170000\n\
+             BEGIN:VALARM\n\
+             SUMMARY:Reminder\n\
+             END:VALARM\n\
+             status:needs-action\n\
+             END:VTODO\n\
+             BEGIN:VTODO\n\
+             UID:5678\n\
+             SUMMARY:Second\n\
+             END:VTODO\n\
+             END:VCALENDAR\n",
+        )
+        .unwrap();
+
+        assert_eq!(parsed.uid.as_deref(), Some("1234"));
+        assert_eq!(parsed.summary, "Water the plants");
+        assert!(!parsed.is_completed);
+        assert_eq!(parsed.due_at, Some(at("2023-04-05T15:00:00Z")));
+    }
+
+    #[test]
+    fn reads_dates_as_midnight() {
+        assert_eq!(
+            parse_date_time("20230405", None),
+            Some(at("2023-04-05T00:00:00Z"))
+        );
+        assert_eq!(
+            parse_date_time("20230405T170000Z", None),
+            Some(at("2023-04-05T17:00:00Z"))
+        );
+        assert_eq!(parse_date_time("tomorrow", None), None);
+    }
+
+    #[test]
+    fn converts_date_times_from_their_time_zone() {
+        assert_eq!(
+            parse_date_time("20230105T170000", Some("Europe/Berlin")),
+            Some(at("2023-01-05T16:00:00Z"))
+        );
+        assert_eq!(
+            parse_date_time("20230326T023000", Some("Europe/Berlin")),
+            Some(at("2023-03-26T01:30:00Z"))
+        );
+        assert_eq!(
+            parse_date_time("20230405T170000", None),
+            Some(at("2023-04-05T17:00:00Z"))
+        );
+        assert_eq!(
+            parse_date_time("20230405T170000", Some("W. Europe Standard Time")),
+            Some(at("2023-04-05T17:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn needs_a_todo() {
+        assert!(parse_todo(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:Party\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n"
+        )
+        .is_none());
+        assert!(parse_todo("").is_none());
+    }
+}
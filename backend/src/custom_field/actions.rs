@@ -0,0 +1,142 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, ModelTrait, Order,
+    PaginatorTrait, QueryOrder, Set,
+};
+use serde_json::json;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entities::{
+    custom_field::{self, FieldKind},
+    project,
+};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewField {
+    pub name: String,
+    pub kind: FieldKind,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FieldChanges {
+    pub name: Option<String>,
+    pub options: Option<Vec<String>>,
+    pub position: Option<i32>,
+}
+
+async fn get_owned_project(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: &Uuid,
+) -> Result<project::Model, ActionError> {
+    let project = project::Entity::find_by_id(*project_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if project.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    Ok(project)
+}
+
+pub async fn get_fields(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: &Uuid,
+) -> Result<Vec<custom_field::Model>, ActionError> {
+    let project = get_owned_project(db, user_id, project_id).await?;
+
+    Ok(project
+        .find_related(custom_field::Entity)
+        .order_by(custom_field::Column::Position, Order::Asc)
+        .order_by(custom_field::Column::CreatedAt, Order::Asc)
+        .all(db)
+        .await?)
+}
+
+pub async fn create_field(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: &Uuid,
+    new_field: &NewField,
+) -> Result<custom_field::Model, ActionError> {
+    let project = get_owned_project(db, user_id, project_id).await?;
+
+    let position = project.find_related(custom_field::Entity).count(db).await?;
+
+    let new_field = custom_field::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        project_id: Set(project.id),
+        name: Set(new_field.name.clone()),
+        kind: Set(new_field.kind),
+        options: Set(json!(new_field.options)),
+        position: Set(position as i32),
+        created_at: Set(Utc::now()),
+    };
+
+    Ok(new_field.insert(db).await?)
+}
+
+pub async fn get_field(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<custom_field::Model, ActionError> {
+    let (field, project) = custom_field::Entity::find_by_id(*id)
+        .find_also_related(project::Entity)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    match project {
+        Some(project) if project.user_id == *user_id => Ok(field),
+        _ => Err(ActionError::Forbidden),
+    }
+}
+
+pub async fn update_field(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+    changes: &FieldChanges,
+) -> Result<custom_field::Model, ActionError> {
+    let mut field = get_field(db, user_id, id).await?.into_active_model();
+
+    if let Some(name) = &changes.name {
+        field.name = Set(name.to_owned());
+    }
+
+    if let Some(options) = &changes.options {
+        field.options = Set(json!(options));
+    }
+
+    if let Some(position) = changes.position {
+        field.position = Set(position);
+    }
+
+    Ok(field.update(db).await?)
+}
+
+pub async fn delete_field(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<(), ActionError> {
+    get_field(db, user_id, id).await?.delete(db).await?;
+
+    Ok(())
+}
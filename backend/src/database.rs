@@ -1,7 +1,9 @@
 use std::env;
 
 use once_cell::sync;
-use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, DbErr, Statement,
+};
 
 pub static DATABASE_URL: sync::Lazy<String> = sync::Lazy::new(|| {
     env::var("DATABASE_URL").expect("Environment variable \"DATABASE_URL\" not found")
@@ -12,3 +14,151 @@ pub async fn get_db() -> anyhow::Result<DatabaseConnection> {
 
     Ok(Database::connect(opt).await?)
 }
+
+pub async fn is_valid_time_zone<C>(db: &C, time_zone: &str) -> Result<bool, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT 1 AS exists FROM pg_timezone_names WHERE name = $1",
+        [time_zone.into()],
+    );
+
+    Ok(db.query_one(stmt).await?.is_some())
+}
+
+/// Helpers for tests that need PostgreSQL. They're ignored unless run with
+/// `--include-ignored` and `TEST_DATABASE_URL` set to a server where the test
+/// user can create databases.
+#[cfg(test)]
+pub mod testing {
+    use std::{env, fs, thread};
+
+    use chrono::Utc;
+    use sea_orm::{ConnectionTrait, Database, DatabaseConnection, EntityTrait, Set};
+    use uuid::Uuid;
+
+    use crate::entities::{todo, user};
+
+    /// Drops the database of a test when the test ends, passed or not.
+    pub struct Guard {
+        url: String,
+        name: String,
+    }
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let url = self.url.clone();
+            let name = self.name.clone();
+
+            // Drop can't await, and the test's runtime may be shutting down.
+            let dropped = thread::spawn(move || {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .unwrap()
+                    .block_on(async {
+                        let admin = Database::connect(&url).await?;
+
+                        admin
+                            .execute_unprepared(&format!("DROP DATABASE {name} WITH (FORCE)"))
+                            .await
+                    })
+            })
+            .join();
+
+            if let Ok(Err(err)) = dropped {
+                eprintln!("Test database {} wasn't dropped: {err}", self.name);
+            }
+        }
+    }
+
+    /// A new database with all migrations applied, dropped with the guard.
+    pub async fn db() -> (DatabaseConnection, Guard) {
+        let url = env::var("TEST_DATABASE_URL")
+            .expect("Environment variable \"TEST_DATABASE_URL\" not found");
+
+        let admin = Database::connect(&url).await.unwrap();
+
+        let name = format!("todos_test_{}", Uuid::new_v4().simple());
+
+        admin
+            .execute_unprepared(&format!("CREATE DATABASE {name}"))
+            .await
+            .unwrap();
+
+        let (server, _) = url.rsplit_once('/').unwrap();
+
+        let guard = Guard {
+            url: url.clone(),
+            name: name.clone(),
+        };
+
+        let db = Database::connect(format!("{server}/{name}")).await.unwrap();
+
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/migrations");
+
+        let mut migrations = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.to_string_lossy().ends_with(".up.sql"))
+            .collect::<Vec<_>>();
+
+        migrations.sort_by_key(|path| {
+            let file_name = path.file_name().unwrap().to_string_lossy().into_owned();
+
+            file_name
+                .split('_')
+                .next()
+                .and_then(|number| number.parse::<u32>().ok())
+                .unwrap()
+        });
+
+        for path in migrations {
+            db.execute_unprepared(&fs::read_to_string(path).unwrap())
+                .await
+                .unwrap();
+        }
+
+        (db, guard)
+    }
+
+    pub async fn user(db: &DatabaseConnection) -> user::Model {
+        let id = Uuid::new_v4();
+
+        user::Entity::insert(user::ActiveModel {
+            id: Set(id),
+            username: Set(format!("user_{}", id.simple())),
+            hashed_password: Set(String::new()),
+            joined_at: Set(Utc::now()),
+        })
+        .exec(db)
+        .await
+        .unwrap();
+
+        user::Entity::find_by_id(id).one(db).await.unwrap().unwrap()
+    }
+
+    pub async fn todo(db: &DatabaseConnection, user_id: &Uuid, name: &str) -> todo::Model {
+        let now = Utc::now();
+
+        todo::Entity::insert(todo::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(*user_id),
+            project_id: Set(None),
+            name: Set(name.to_owned()),
+            is_completed: Set(false),
+            estimate_minutes: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+            completed_at: Set(None),
+            due_at: Set(None),
+            parent_id: Set(None),
+            status_id: Set(None),
+        })
+        .exec_with_returning(db)
+        .await
+        .unwrap()
+    }
+}
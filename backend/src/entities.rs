@@ -1,2 +1,20 @@
+pub mod app_password;
+pub mod caldav_object;
+pub mod calendar_token;
+pub mod custom_field;
+pub mod custom_field_value;
+pub mod job;
+pub mod notification;
+pub mod project;
+pub mod reminder;
+pub mod status;
+pub mod stream_ticket;
+pub mod template;
+pub mod template_item;
+pub mod time_entry;
 pub mod todo;
+pub mod todo_tombstone;
 pub mod user;
+pub mod user_preferences;
+pub mod webhook;
+pub mod webhook_delivery;
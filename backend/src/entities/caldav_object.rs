@@ -0,0 +1,44 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Resource name and `UID` a CalDAV client chose for a todo it created.
+#[derive(Debug, Clone, Serialize, DeriveEntityModel)]
+#[sea_orm(table_name = "caldav_object")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub todo_id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub uid: String,
+}
+
+#[derive(Debug, Clone, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+    #[sea_orm(
+        belongs_to = "super::todo::Entity",
+        from = "Column::TodoId",
+        to = "super::todo::Column::Id"
+    )]
+    Todo,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl Related<super::todo::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Todo.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    #[sea_orm(string_value = "text")]
+    Text,
+    #[sea_orm(string_value = "number")]
+    Number,
+    #[sea_orm(string_value = "date")]
+    Date,
+    #[sea_orm(string_value = "select")]
+    Select,
+    #[sea_orm(string_value = "checkbox")]
+    Checkbox,
+}
+
+#[derive(Debug, Clone, Serialize, DeriveEntityModel)]
+#[sea_orm(table_name = "custom_field")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub kind: FieldKind,
+    pub options: Json,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::ProjectId",
+        to = "super::project::Column::Id"
+    )]
+    Project,
+    #[sea_orm(has_many = "super::custom_field_value::Entity")]
+    CustomFieldValue,
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl Related<super::custom_field_value::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CustomFieldValue.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
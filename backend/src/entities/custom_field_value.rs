@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, DeriveEntityModel)]
+#[sea_orm(table_name = "custom_field_value")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub todo_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub field_id: Uuid,
+    pub value: Json,
+}
+
+#[derive(Debug, Clone, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::todo::Entity",
+        from = "Column::TodoId",
+        to = "super::todo::Column::Id"
+    )]
+    Todo,
+    #[sea_orm(
+        belongs_to = "super::custom_field::Entity",
+        from = "Column::FieldId",
+        to = "super::custom_field::Column::Id"
+    )]
+    CustomField,
+}
+
+impl Related<super::todo::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Todo.def()
+    }
+}
+
+impl Related<super::custom_field::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CustomField.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, DeriveEntityModel)]
+#[sea_orm(table_name = "template_item")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub template_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub due_offset_days: Option<i32>,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::template::Entity",
+        from = "Column::TemplateId",
+        to = "super::template::Column::Id"
+    )]
+    Template,
+}
+
+impl Related<super::template::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Template.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,19 +1,24 @@
 use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, DeriveEntityModel)]
+#[derive(Debug, Clone, Serialize, Deserialize, DeriveEntityModel)]
 #[sea_orm(table_name = "todo")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: Uuid,
     pub user_id: Uuid,
+    pub project_id: Option<Uuid>,
     pub name: String,
     pub is_completed: bool,
+    pub estimate_minutes: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub parent_id: Option<Uuid>,
+    pub status_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, EnumIter, DeriveRelation)]
@@ -24,6 +29,28 @@ pub enum Relation {
         to = "super::user::Column::Id"
     )]
     User,
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::ProjectId",
+        to = "super::project::Column::Id"
+    )]
+    Project,
+    #[sea_orm(
+        belongs_to = "super::status::Entity",
+        from = "Column::StatusId",
+        to = "super::status::Column::Id"
+    )]
+    Status,
+    #[sea_orm(has_many = "super::time_entry::Entity")]
+    TimeEntry,
+    #[sea_orm(has_many = "super::custom_field_value::Entity")]
+    CustomFieldValue,
+    #[sea_orm(has_many = "super::reminder::Entity")]
+    Reminder,
+    #[sea_orm(has_many = "super::notification::Entity")]
+    Notification,
+    #[sea_orm(has_one = "super::caldav_object::Entity")]
+    CaldavObject,
 }
 
 impl Related<super::user::Entity> for Entity {
@@ -32,4 +59,46 @@ impl Related<super::user::Entity> for Entity {
     }
 }
 
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl Related<super::status::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Status.def()
+    }
+}
+
+impl Related<super::time_entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimeEntry.def()
+    }
+}
+
+impl Related<super::custom_field_value::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CustomFieldValue.def()
+    }
+}
+
+impl Related<super::reminder::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reminder.def()
+    }
+}
+
+impl Related<super::notification::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notification.def()
+    }
+}
+
+impl Related<super::caldav_object::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CaldavObject.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
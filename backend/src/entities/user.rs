@@ -9,6 +9,7 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: Uuid,
     pub username: String,
+    #[serde(skip_serializing)]
     pub hashed_password: String,
     pub joined_at: DateTime<Utc>,
 }
@@ -17,6 +18,28 @@ pub struct Model {
 pub enum Relation {
     #[sea_orm(has_many = "super::todo::Entity")]
     Todo,
+    #[sea_orm(has_many = "super::project::Entity")]
+    Project,
+    #[sea_orm(has_many = "super::time_entry::Entity")]
+    TimeEntry,
+    #[sea_orm(has_many = "super::template::Entity")]
+    Template,
+    #[sea_orm(has_many = "super::reminder::Entity")]
+    Reminder,
+    #[sea_orm(has_many = "super::notification::Entity")]
+    Notification,
+    #[sea_orm(has_many = "super::webhook::Entity")]
+    Webhook,
+    #[sea_orm(has_one = "super::calendar_token::Entity")]
+    CalendarToken,
+    #[sea_orm(has_many = "super::app_password::Entity")]
+    AppPassword,
+    #[sea_orm(has_many = "super::caldav_object::Entity")]
+    CaldavObject,
+    #[sea_orm(has_one = "super::user_preferences::Entity")]
+    UserPreferences,
+    #[sea_orm(has_many = "super::todo_tombstone::Entity")]
+    TodoTombstone,
 }
 
 impl Related<super::todo::Entity> for Entity {
@@ -25,4 +48,70 @@ impl Related<super::todo::Entity> for Entity {
     }
 }
 
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl Related<super::time_entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TimeEntry.def()
+    }
+}
+
+impl Related<super::template::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Template.def()
+    }
+}
+
+impl Related<super::reminder::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reminder.def()
+    }
+}
+
+impl Related<super::notification::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Notification.def()
+    }
+}
+
+impl Related<super::webhook::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Webhook.def()
+    }
+}
+
+impl Related<super::calendar_token::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CalendarToken.def()
+    }
+}
+
+impl Related<super::app_password::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AppPassword.def()
+    }
+}
+
+impl Related<super::caldav_object::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CaldavObject.def()
+    }
+}
+
+impl Related<super::user_preferences::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::UserPreferences.def()
+    }
+}
+
+impl Related<super::todo_tombstone::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TodoTombstone.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Color scheme of the frontend, `System` follows the browser setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[sea_orm(string_value = "system")]
+    System,
+    #[sea_orm(string_value = "light")]
+    Light,
+    #[sea_orm(string_value = "dark")]
+    Dark,
+}
+
+/// How dates are written, times are 24-hour unless the language has them
+/// otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum DateFormat {
+    /// The way of the preferred language.
+    #[sea_orm(string_value = "locale")]
+    Locale,
+    /// `2023-03-31`
+    #[sea_orm(string_value = "iso")]
+    Iso,
+    /// `31.03.2023`
+    #[sea_orm(string_value = "european")]
+    European,
+    /// `03/31/2023`
+    #[sea_orm(string_value = "us")]
+    Us,
+}
+
+/// Page the frontend opens on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultView {
+    #[sea_orm(string_value = "active")]
+    Active,
+    #[sea_orm(string_value = "all")]
+    All,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+    #[sea_orm(string_value = "board")]
+    Board,
+    #[sea_orm(string_value = "calendar")]
+    Calendar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "Text")]
+#[serde(rename_all = "snake_case")]
+pub enum WeekStart {
+    #[sea_orm(string_value = "monday")]
+    Monday,
+    #[sea_orm(string_value = "sunday")]
+    Sunday,
+}
+
+#[derive(Debug, Clone, Serialize, DeriveEntityModel)]
+#[sea_orm(table_name = "user_preferences")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    #[serde(skip_serializing)]
+    pub user_id: Uuid,
+    /// IANA name, the browser one is used when unset.
+    pub time_zone: Option<String>,
+    pub page_size: i32,
+    pub theme: Theme,
+    pub date_format: DateFormat,
+    pub default_view: DefaultView,
+    pub week_start: WeekStart,
+    /// BCP 47 tag such as `en` or `de-AT`.
+    pub locale: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
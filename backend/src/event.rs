@@ -0,0 +1,80 @@
+use std::{env, sync::Arc};
+
+use async_trait::async_trait;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::entities::todo;
+
+pub mod memory;
+pub mod postgres;
+pub mod ticket;
+
+/// Events buffered per subscriber before slow ones start skipping.
+const CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoEventKind {
+    Created,
+    Updated,
+    Completed,
+    Reverted,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoEvent {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: TodoEventKind,
+    pub todo_id: Uuid,
+    pub todo: Option<todo::Model>,
+}
+
+impl TodoEvent {
+    pub fn new(kind: TodoEventKind, todo: &todo::Model) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id: todo.user_id,
+            kind,
+            todo_id: todo.id,
+            todo: Some(todo.clone()),
+        }
+    }
+
+    pub fn deleted(user_id: Uuid, todo_id: Uuid) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            kind: TodoEventKind::Deleted,
+            todo_id,
+            todo: None,
+        }
+    }
+}
+
+#[async_trait]
+pub trait EventBus: Send + Sync {
+    /// Delivers the event to subscribers. Failures are logged rather than
+    /// returned, a lost event must not fail the change that caused it.
+    async fn publish(&self, event: TodoEvent);
+
+    fn subscribe(&self) -> broadcast::Receiver<TodoEvent>;
+}
+
+pub type Events = Arc<dyn EventBus>;
+
+/// Creates the bus selected by `EVENT_BUS`: `memory` (default) for a single
+/// instance, `postgres` to fan out across instances with `LISTEN/NOTIFY`.
+pub async fn create_event_bus(db: &DatabaseConnection) -> anyhow::Result<Events> {
+    match env::var("EVENT_BUS").as_deref() {
+        Ok("postgres") => Ok(Arc::new(postgres::PostgresEventBus::connect(db).await?)),
+        Ok("memory") | Err(env::VarError::NotPresent) => {
+            Ok(Arc::new(memory::MemoryEventBus::new()))
+        }
+        _ => anyhow::bail!("Environment variable \"EVENT_BUS\" is invalid"),
+    }
+}
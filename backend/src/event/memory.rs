@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use tokio::sync::broadcast;
+
+use super::{EventBus, TodoEvent, CAPACITY};
+
+/// Delivers events to subscribers of the same process only.
+#[derive(Debug, Clone)]
+pub struct MemoryEventBus {
+    sender: broadcast::Sender<TodoEvent>,
+}
+
+impl Default for MemoryEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryEventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CAPACITY);
+
+        Self { sender }
+    }
+}
+
+#[async_trait]
+impl EventBus for MemoryEventBus {
+    async fn publish(&self, event: TodoEvent) {
+        // Sending only fails when nobody is subscribed, which is fine.
+        self.sender.send(event).ok();
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<TodoEvent> {
+        self.sender.subscribe()
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait, Statement};
+use sqlx::postgres::PgListener;
+use tokio::sync::broadcast;
+
+use super::{EventBus, TodoEvent, TodoEventKind, CAPACITY};
+use crate::entities::todo;
+
+const CHANNEL: &str = "todo_events";
+
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Fans events out to every instance through `LISTEN/NOTIFY`.
+///
+/// Published events are only sent to PostgreSQL; local subscribers receive
+/// them back through the listener like any other instance does. Payloads
+/// are limited to 8000 bytes, so notifications carry ids only and the todo is
+/// read again on arrival.
+#[derive(Debug, Clone)]
+pub struct PostgresEventBus {
+    db: DatabaseConnection,
+    sender: broadcast::Sender<TodoEvent>,
+}
+
+impl PostgresEventBus {
+    pub async fn connect(db: &DatabaseConnection) -> anyhow::Result<Self> {
+        let (sender, _) = broadcast::channel(CAPACITY);
+
+        let mut listener = PgListener::connect_with(db.get_postgres_connection_pool()).await?;
+
+        listener.listen(CHANNEL).await?;
+
+        tokio::spawn(listen(db.clone(), listener, sender.clone()));
+
+        Ok(Self {
+            db: db.clone(),
+            sender,
+        })
+    }
+}
+
+async fn listen(
+    db: DatabaseConnection,
+    mut listener: PgListener,
+    sender: broadcast::Sender<TodoEvent>,
+) {
+    loop {
+        // The listener reconnects by itself, notifications sent while it was
+        // away are lost.
+        let notification = match listener.recv().await {
+            Ok(notification) => notification,
+            Err(err) => {
+                tracing::error!("Event listener: {}", err);
+
+                tokio::time::sleep(RETRY_INTERVAL).await;
+
+                continue;
+            }
+        };
+
+        let mut event = match serde_json::from_str::<TodoEvent>(notification.payload()) {
+            Ok(event) => event,
+            Err(err) => {
+                tracing::error!("Event listener: {}", err);
+
+                continue;
+            }
+        };
+
+        if event.kind != TodoEventKind::Deleted {
+            // Gone by now means a delete event follows, this one is stale.
+            match todo::Entity::find_by_id(event.todo_id).one(&db).await {
+                Ok(Some(todo)) => event.todo = Some(todo),
+                Ok(None) => continue,
+                Err(err) => {
+                    tracing::error!("Event listener: {}", err);
+
+                    continue;
+                }
+            }
+        }
+
+        sender.send(event).ok();
+    }
+}
+
+#[async_trait]
+impl EventBus for PostgresEventBus {
+    async fn publish(&self, event: TodoEvent) {
+        let event = TodoEvent {
+            todo: None,
+            ..event
+        };
+
+        let payload = match serde_json::to_string(&event) {
+            Ok(payload) => payload,
+            Err(err) => {
+                tracing::error!("Event bus: {}", err);
+
+                return;
+            }
+        };
+
+        let stmt = Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "SELECT pg_notify($1, $2)",
+            [CHANNEL.into(), payload.into()],
+        );
+
+        if let Err(err) = self.db.execute(stmt).await {
+            tracing::error!("Event bus: {}", err);
+        }
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<TodoEvent> {
+        self.sender.subscribe()
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::{Duration, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entities::{stream_ticket, user};
+
+/// Time a client has to open the stream after asking for a ticket.
+const TICKET_SECONDS: i64 = 60;
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Not Found")]
+    NotFound,
+}
+
+/// Issues a ticket for one event stream of the user.
+pub async fn create_ticket(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<stream_ticket::Model, ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let now = Utc::now();
+
+    // Tickets that were never used pile up otherwise.
+    stream_ticket::Entity::delete_many()
+        .filter(stream_ticket::Column::ExpiresAt.lt(now))
+        .exec(db)
+        .await?;
+
+    let ticket = stream_ticket::Model {
+        ticket: format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()),
+        user_id: *user_id,
+        expires_at: now + Duration::seconds(TICKET_SECONDS),
+    };
+
+    stream_ticket::Entity::insert(stream_ticket::ActiveModel {
+        ticket: Set(ticket.ticket.clone()),
+        user_id: Set(ticket.user_id),
+        expires_at: Set(ticket.expires_at),
+    })
+    .exec(db)
+    .await?;
+
+    Ok(ticket)
+}
+
+/// Uses up a ticket, returning the user it was issued to. Deleting it is
+/// what claims it, so concurrent attempts get it at most once.
+pub async fn redeem_ticket(db: &DatabaseConnection, ticket: &str) -> Result<Uuid, ActionError> {
+    let found = stream_ticket::Entity::find_by_id(ticket.to_owned())
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let deleted = stream_ticket::Entity::delete_many()
+        .filter(stream_ticket::Column::Ticket.eq(ticket))
+        .filter(stream_ticket::Column::ExpiresAt.gt(Utc::now()))
+        .exec(db)
+        .await?;
+
+    match deleted.rows_affected {
+        1 => Ok(found.user_id),
+        _ => Err(ActionError::NotFound),
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use async_stream::try_stream;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use thiserror::Error;
+use tokio_stream::Stream;
+use uuid::Uuid;
+
+use super::formats::{self, ExportFormat, ExportedTodo};
+use crate::{
+    entities::{project, status, user},
+    todo::actions::{self as todo_actions, TodosFilter},
+};
+
+/// Todos loaded and written per chunk of the response.
+const PAGE_SIZE: u64 = 500;
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Todo: {0}")]
+    Todo(#[from] todo_actions::ActionError),
+    #[error("Csv: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("Json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Not Found")]
+    NotFound,
+}
+
+/// Streams the todos matching the filter in chunks, so exports of any size
+/// are never held in memory at once.
+pub async fn export(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    filter: &TodosFilter,
+    format: ExportFormat,
+) -> Result<impl Stream<Item = Result<String, ActionError>>, ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let projects: HashMap<Uuid, String> = project::Entity::find()
+        .filter(project::Column::UserId.eq(*user_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|project| (project.id, project.name))
+        .collect();
+
+    let statuses: HashMap<Uuid, String> = status::Entity::find()
+        .inner_join(project::Entity)
+        .filter(project::Column::UserId.eq(*user_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|status| (status.id, status.name))
+        .collect();
+
+    let db = db.clone();
+    let user_id = *user_id;
+    let filter = filter.clone();
+
+    Ok(try_stream! {
+        yield formats::header(format)?;
+
+        let mut after = None;
+        let mut is_first = true;
+
+        loop {
+            let todos =
+                todo_actions::get_todos_after(&db, &user_id, &filter, &after, PAGE_SIZE).await?;
+
+            let Some(last) = todos.last() else {
+                break;
+            };
+
+            after = Some((last.created_at, last.id));
+
+            let ids: Vec<Uuid> = todos.iter().map(|todo| todo.id).collect();
+
+            let mut values = todo_actions::get_field_values(&db, &ids).await?;
+
+            let mut chunk = String::new();
+
+            for todo in todos {
+                let exported = ExportedTodo {
+                    project: todo.project_id.and_then(|id| projects.get(&id).cloned()),
+                    status: todo.status_id.and_then(|id| statuses.get(&id).cloned()),
+                    fields: values.remove(&todo.id).unwrap_or_default(),
+                    todo,
+                };
+
+                chunk.push_str(&formats::render(format, &exported, is_first)?);
+
+                is_first = false;
+            }
+
+            yield chunk;
+        }
+
+        yield formats::footer(format);
+    })
+}
@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::actions::ActionError;
+use crate::entities::todo;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+    Markdown,
+    Todotxt,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+            ExportFormat::Todotxt => "text/plain; charset=utf-8",
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "todos.json",
+            ExportFormat::Csv => "todos.csv",
+            ExportFormat::Markdown => "todos.md",
+            ExportFormat::Todotxt => "todo.txt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedTodo {
+    #[serde(flatten)]
+    pub todo: todo::Model,
+    pub project: Option<String>,
+    pub status: Option<String>,
+    pub fields: HashMap<Uuid, Value>,
+}
+
+const CSV_HEADERS: [&str; 14] = [
+    "id",
+    "name",
+    "project_id",
+    "project",
+    "status_id",
+    "status",
+    "is_completed",
+    "estimate_minutes",
+    "created_at",
+    "updated_at",
+    "completed_at",
+    "due_at",
+    "parent_id",
+    "fields",
+];
+
+fn csv_line(record: &[String]) -> Result<String, ActionError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    writer.write_record(record)?;
+
+    let line = writer
+        .into_inner()
+        .map_err(|err| csv::Error::from(err.into_error()))?;
+
+    Ok(String::from_utf8_lossy(&line).into_owned())
+}
+
+fn optional<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+fn date(value: &DateTime<Utc>) -> String {
+    value.format("%Y-%m-%d").to_string()
+}
+
+/// Written before the first todo.
+pub fn header(format: ExportFormat) -> Result<String, ActionError> {
+    Ok(match format {
+        ExportFormat::Json => "[".to_owned(),
+        ExportFormat::Csv => csv_line(&CSV_HEADERS.map(|header| header.to_owned()))?,
+        ExportFormat::Markdown => "# Todos\n\n".to_owned(),
+        ExportFormat::Todotxt => String::new(),
+    })
+}
+
+/// Written after the last todo.
+pub fn footer(format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => "\n]\n".to_owned(),
+        _ => String::new(),
+    }
+}
+
+pub fn render(
+    format: ExportFormat,
+    exported: &ExportedTodo,
+    is_first: bool,
+) -> Result<String, ActionError> {
+    let ExportedTodo {
+        todo,
+        project,
+        status,
+        fields,
+    } = exported;
+
+    Ok(match format {
+        ExportFormat::Json => {
+            let separator = if is_first { "\n" } else { ",\n" };
+
+            format!("{separator}  {}", serde_json::to_string(exported)?)
+        }
+        ExportFormat::Csv => csv_line(&[
+            todo.id.to_string(),
+            todo.name.clone(),
+            optional(&todo.project_id),
+            project.clone().unwrap_or_default(),
+            optional(&todo.status_id),
+            status.clone().unwrap_or_default(),
+            todo.is_completed.to_string(),
+            optional(&todo.estimate_minutes),
+            todo.created_at.to_rfc3339(),
+            todo.updated_at.to_rfc3339(),
+            optional(&todo.completed_at.map(|value| value.to_rfc3339())),
+            optional(&todo.due_at.map(|value| value.to_rfc3339())),
+            optional(&todo.parent_id),
+            if fields.is_empty() {
+                String::new()
+            } else {
+                Value::from(
+                    fields
+                        .iter()
+                        .map(|(id, value)| (id.to_string(), value.clone()))
+                        .collect::<serde_json::Map<_, _>>(),
+                )
+                .to_string()
+            },
+        ])?,
+        ExportFormat::Markdown => {
+            let mut details = Vec::new();
+
+            if let Some(project) = project {
+                details.push(format!("project: {project}"));
+            }
+
+            if let Some(status) = status {
+                details.push(format!("status: {status}"));
+            }
+
+            if let Some(due_at) = &todo.due_at {
+                details.push(format!("due: {}", due_at.format("%Y-%m-%d %H:%M")));
+            }
+
+            if let Some(estimate_minutes) = &todo.estimate_minutes {
+                details.push(format!("estimate: {estimate_minutes} min"));
+            }
+
+            if let Some(completed_at) = &todo.completed_at {
+                details.push(format!(
+                    "completed: {}",
+                    completed_at.format("%Y-%m-%d %H:%M")
+                ));
+            }
+
+            let check = if todo.is_completed { "x" } else { " " };
+
+            let indent = if todo.parent_id.is_some() { "  " } else { "" };
+
+            let name = todo.name.replace('\n', " ");
+
+            match details.is_empty() {
+                true => format!("{indent}- [{check}] {name}\n"),
+                false => format!("{indent}- [{check}] {name} _({})_\n", details.join(", ")),
+            }
+        }
+        ExportFormat::Todotxt => {
+            let mut parts = Vec::new();
+
+            if todo.is_completed {
+                parts.push("x".to_owned());
+                parts.push(date(&todo.completed_at.unwrap_or(todo.updated_at)));
+            }
+
+            parts.push(date(&todo.created_at));
+            parts.push(todo.name.replace('\n', " "));
+
+            if let Some(project) = project {
+                parts.push(format!(
+                    "+{}",
+                    project.split_whitespace().collect::<Vec<_>>().join("-")
+                ));
+            }
+
+            if let Some(due_at) = &todo.due_at {
+                parts.push(format!("due:{}", date(due_at)));
+            }
+
+            if let Some(estimate_minutes) = &todo.estimate_minutes {
+                parts.push(format!("estimate:{estimate_minutes}"));
+            }
+
+            format!("{}\n", parts.join(" "))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::formats::{parse_csv, parse_todo_txt, CsvMapping};
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn exported() -> Vec<ExportedTodo> {
+        let parent = todo::Model {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            project_id: Some(Uuid::new_v4()),
+            name: "Plan the \"big\" trip, soon".to_owned(),
+            is_completed: false,
+            estimate_minutes: Some(90),
+            created_at: at("2023-04-01T08:00:00Z"),
+            updated_at: at("2023-04-02T09:30:00Z"),
+            completed_at: None,
+            due_at: Some(at("2023-04-05T17:00:00Z")),
+            parent_id: None,
+            status_id: Some(Uuid::new_v4()),
+        };
+
+        let child = todo::Model {
+            id: Uuid::new_v4(),
+            name: "Book\nflights".to_owned(),
+            is_completed: true,
+            estimate_minutes: None,
+            completed_at: Some(at("2023-04-03T10:00:00Z")),
+            due_at: None,
+            parent_id: Some(parent.id),
+            status_id: None,
+            ..parent.clone()
+        };
+
+        vec![
+            ExportedTodo {
+                todo: parent,
+                project: Some("Summer holiday".to_owned()),
+                status: Some("In progress".to_owned()),
+                fields: HashMap::from([(Uuid::nil(), Value::from("high"))]),
+            },
+            ExportedTodo {
+                todo: child,
+                project: Some("Summer holiday".to_owned()),
+                status: None,
+                fields: HashMap::new(),
+            },
+        ]
+    }
+
+    fn write(format: ExportFormat, todos: &[ExportedTodo]) -> String {
+        let mut content = header(format).unwrap();
+
+        for (index, todo) in todos.iter().enumerate() {
+            content.push_str(&render(format, todo, index == 0).unwrap());
+        }
+
+        content.push_str(&footer(format));
+
+        content
+    }
+
+    #[test]
+    fn writes_a_json_array() {
+        let todos = exported();
+
+        let value: Value = serde_json::from_str(&write(ExportFormat::Json, &todos)).unwrap();
+
+        let array = value.as_array().unwrap();
+
+        assert_eq!(array.len(), 2);
+        assert_eq!(array[0]["id"], todos[0].todo.id.to_string());
+        assert_eq!(array[0]["project"], "Summer holiday");
+        assert_eq!(
+            array[0]["status_id"],
+            todos[0].todo.status_id.unwrap().to_string()
+        );
+        assert_eq!(array[0]["status"], "In progress");
+        assert_eq!(array[1]["status"], Value::Null);
+        assert_eq!(array[0]["fields"][Uuid::nil().to_string()], "high");
+        assert_eq!(array[1]["parent_id"], todos[0].todo.id.to_string());
+
+        let empty: Value = serde_json::from_str(&write(ExportFormat::Json, &[])).unwrap();
+
+        assert_eq!(empty, Value::Array(Vec::new()));
+    }
+
+    #[test]
+    fn writes_csv_the_import_reads() {
+        let todos = exported();
+
+        let content = write(ExportFormat::Csv, &todos);
+
+        assert!(content.starts_with("id,name,project_id,project,status_id,status,is_completed,"));
+
+        let mut reader = csv::Reader::from_reader(content.as_bytes());
+
+        let statuses = reader
+            .records()
+            .map(|record| {
+                let record = record.unwrap();
+
+                (record[4].to_owned(), record[5].to_owned())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            statuses,
+            [
+                (
+                    todos[0].todo.status_id.unwrap().to_string(),
+                    "In progress".to_owned()
+                ),
+                (String::new(), String::new()),
+            ]
+        );
+
+        let parsed = parse_csv(&content, &CsvMapping::default());
+
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(parsed.todos.len(), 2);
+
+        for (parsed, exported) in parsed.todos.iter().zip(&todos) {
+            assert_eq!(parsed.name, exported.todo.name);
+            assert_eq!(parsed.project, exported.project);
+            assert_eq!(parsed.is_completed, exported.todo.is_completed);
+            assert_eq!(parsed.created_at, Some(exported.todo.created_at));
+            assert_eq!(parsed.completed_at, exported.todo.completed_at);
+            assert_eq!(parsed.due_at, exported.todo.due_at);
+        }
+    }
+
+    #[test]
+    fn writes_markdown_checklists() {
+        assert_eq!(
+            write(ExportFormat::Markdown, &exported()),
+            "# Todos\n\n\
+             - [ ] Plan the \"big\" trip, soon _(project: Summer holiday, status: In progress, due: 2023-04-05 17:00, estimate: 90 min)_\n  \
+             - [x] Book flights _(project: Summer holiday, completed: 2023-04-03 10:00)_\n"
+        );
+    }
+
+    #[test]
+    fn writes_todo_txt_the_import_reads() {
+        let content = write(ExportFormat::Todotxt, &exported());
+
+        assert_eq!(
+            content,
+            "2023-04-01 Plan the \"big\" trip, soon +Summer-holiday due:2023-04-05 estimate:90\n\
+             x 2023-04-03 2023-04-01 Book flights +Summer-holiday\n"
+        );
+
+        let parsed = parse_todo_txt(&content);
+
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(
+            parsed.todos[0].name,
+            "Plan the \"big\" trip, soon estimate:90"
+        );
+        assert_eq!(parsed.todos[0].project.as_deref(), Some("Summer-holiday"));
+        assert_eq!(parsed.todos[0].due_at, Some(at("2023-04-05T00:00:00Z")));
+        assert!(parsed.todos[1].is_completed);
+        assert_eq!(
+            parsed.todos[1].completed_at,
+            Some(at("2023-04-03T00:00:00Z"))
+        );
+    }
+}
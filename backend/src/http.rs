@@ -1,5 +1,8 @@
 pub mod api;
 pub mod app;
+pub mod caldav;
+pub mod deserializers;
+pub mod error;
 pub mod extractors;
 pub mod server;
 pub mod swagger;
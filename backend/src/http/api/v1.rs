@@ -1,10 +1,44 @@
 use axum::Router;
 
+mod app_password;
+mod archive;
 mod auth;
+mod calendar;
+mod custom_field;
+mod event;
+mod export;
+mod import;
+mod notification;
+mod preference;
+mod project;
+mod reminder;
+mod stats;
+mod sync;
+mod template;
+mod time_tracking;
 mod todo;
+mod webhook;
+mod workflow;
 
 pub async fn create_router() -> anyhow::Result<Router> {
     Ok(Router::new()
+        .merge(app_password::create_router().await?)
+        .merge(archive::create_router().await?)
         .merge(auth::create_router().await?)
-        .merge(todo::create_router().await?))
+        .merge(calendar::create_router().await?)
+        .merge(custom_field::create_router().await?)
+        .merge(event::create_router().await?)
+        .merge(export::create_router().await?)
+        .merge(import::create_router().await?)
+        .merge(notification::create_router().await?)
+        .merge(preference::create_router().await?)
+        .merge(project::create_router().await?)
+        .merge(reminder::create_router().await?)
+        .merge(stats::create_router().await?)
+        .merge(sync::create_router().await?)
+        .merge(template::create_router().await?)
+        .merge(time_tracking::create_router().await?)
+        .merge(todo::create_router().await?)
+        .merge(webhook::create_router().await?)
+        .merge(workflow::create_router().await?))
 }
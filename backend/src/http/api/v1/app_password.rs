@@ -0,0 +1,128 @@
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get},
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    app_password::actions::{self, ActionError},
+    auth::security::SecurityError,
+    entities::app_password,
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Security(inner) => security_into_response(inner),
+        ActionError::InvalidCredentials => {
+            ErrorResponse::new(StatusCode::UNAUTHORIZED, "invalid_credentials", error)
+                .into_response()
+        }
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn security_into_response(error: SecurityError) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NewAppPassword {
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct CreatedAppPassword {
+    #[serde(flatten)]
+    pub app_password: app_password::Model,
+    pub password: String,
+}
+
+pub async fn get_app_passwords(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_app_passwords(&db, &user.id).await?))
+}
+
+pub async fn create_app_password(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Json(payload): Json<NewAppPassword>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let (app_password, password) =
+        actions::create_app_password(&db, &user.id, &payload.name).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedAppPassword {
+            app_password,
+            password,
+        }),
+    ))
+}
+
+pub async fn delete_app_password(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::delete_app_password(&db, &user.id, &id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route(
+            "/profile/app-passwords",
+            get(get_app_passwords).post(create_app_password),
+        )
+        .route("/profile/app-passwords/:id", delete(delete_app_password)))
+}
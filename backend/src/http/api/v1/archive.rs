@@ -0,0 +1,102 @@
+use axum::{
+    extract::Query,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    archive::actions::{self, ActionError},
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Auth: {}", .0.message)]
+    Auth(ErrorResponse),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Auth(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<ErrorResponse> for HandlerError {
+    fn from(error: ErrorResponse) -> Self {
+        HandlerError::Auth(error)
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Io(_) | ActionError::Json(_) | ActionError::Zip(_) => {
+            tracing::error!("{}", error);
+
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+/// Like exports, the archive may be downloaded from a link with a
+/// single-use ticket in the query.
+#[derive(Deserialize)]
+pub struct ArchiveQuery {
+    pub ticket: Option<String>,
+}
+
+pub async fn archive(
+    Extension(db): Extension<DatabaseConnection>,
+    user: Option<AuthUser>,
+    Query(query): Query<ArchiveQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let user = match (user, &query.ticket) {
+        (Some(user), _) => user,
+        (None, Some(ticket)) => AuthUser::from_ticket(&db, ticket).await?,
+        (None, None) => {
+            return Err(ErrorResponse::new(
+                StatusCode::UNAUTHORIZED,
+                "invalid_token",
+                "Invalid token",
+            )
+            .into())
+        }
+    };
+
+    let archive = actions::archive(&db, &user.id).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_owned()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"todos-{}.zip\"", user.username),
+            ),
+        ],
+        archive,
+    ))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new().route("/profile/archive", get(archive)))
+}
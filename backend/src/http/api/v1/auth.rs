@@ -15,6 +15,7 @@ use crate::{
         actions::{self, ActionError},
         security::SecurityError,
     },
+    http::error::ErrorResponse,
     http::extractors::AuthUser,
 };
 
@@ -40,19 +41,23 @@ fn action_into_response(error: ActionError) -> Response {
         ActionError::Db(inner) => db_into_response(inner),
         ActionError::Security(inner) => security_into_response(inner),
         ActionError::UserAlredyExists => {
-            (StatusCode::BAD_REQUEST, error.to_string()).into_response()
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "user_already_exists", error)
+                .into_response()
         }
         ActionError::InvalidCredentials => {
-            (StatusCode::UNAUTHORIZED, error.to_string()).into_response()
+            ErrorResponse::new(StatusCode::UNAUTHORIZED, "invalid_credentials", error)
+                .into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
         }
-        ActionError::NotFound => (StatusCode::NOT_FOUND, error.to_string()).into_response(),
     }
 }
 
 fn db_into_response(error: DbErr) -> Response {
     tracing::error!("{}", error);
 
-    (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
 }
 
 fn validation_into_response(error: validator::ValidationErrors) -> Response {
@@ -62,20 +67,20 @@ fn validation_into_response(error: validator::ValidationErrors) -> Response {
 fn security_into_response(error: SecurityError) -> Response {
     match error {
         SecurityError::JwtInvalidAudience => {
-            (StatusCode::UNAUTHORIZED, error.to_string()).into_response()
+            ErrorResponse::new(StatusCode::UNAUTHORIZED, "invalid_token", error).into_response()
         }
         _ => {
             tracing::error!("{}", error);
-            (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
         }
     }
 }
 
 #[derive(Deserialize, Validate)]
 pub struct Credentials {
-    #[validate(length(min = 5, message = "Too short"))]
+    #[validate(length(min = 5, code = "too_short", message = "Too short"))]
     pub username: String,
-    #[validate(length(min = 10, message = "Too short"))]
+    #[validate(length(min = 10, code = "too_short", message = "Too short"))]
     pub password: String,
 }
 
@@ -87,12 +92,17 @@ pub struct Token {
 
 #[derive(Deserialize, Validate)]
 pub struct PasswordChange {
-    #[validate(length(min = 10, message = "Too short"))]
+    #[validate(length(min = 10, code = "too_short", message = "Too short"))]
     pub password: String,
-    #[validate(length(min = 10, message = "Too short"))]
+    #[validate(length(min = 10, code = "too_short", message = "Too short"))]
     pub new_password: String,
 }
 
+#[derive(Deserialize)]
+pub struct AccountDeletion {
+    pub password: String,
+}
+
 pub async fn sign_up(
     Extension(db): Extension<DatabaseConnection>,
     Json(credentials): Json<Credentials>,
@@ -140,11 +150,21 @@ pub async fn change_password(
     Ok(StatusCode::NO_CONTENT)
 }
 
+pub async fn delete_account(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Json(payload): Json<AccountDeletion>,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::delete_account(&db, &user.id, &payload.password).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 pub async fn create_router() -> anyhow::Result<Router> {
     Ok(Router::new()
         .route("/sign-in", post(sign_in))
         .route("/sign-up", post(sign_up))
         .route("/sign-refresh", post(sign_refresh))
-        .route("/profile", get(profile))
+        .route("/profile", get(profile).delete(delete_account))
         .route("/change-password", post(change_password)))
 }
@@ -0,0 +1,94 @@
+use axum::{
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    calendar::actions::{self, ActionError},
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+#[derive(Deserialize)]
+pub struct FeedQuery {
+    pub project_id: Option<Uuid>,
+}
+
+pub async fn get_calendar_token(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_calendar_token(&db, &user.id).await?))
+}
+
+pub async fn regenerate_calendar_token(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(
+        actions::regenerate_calendar_token(&db, &user.id).await?,
+    ))
+}
+
+pub async fn get_feed(
+    Extension(db): Extension<DatabaseConnection>,
+    Path(token): Path<String>,
+    Query(query): Query<FeedQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let token = token.trim_end_matches(".ics");
+
+    let feed = actions::get_feed(&db, token, &query.project_id).await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        feed,
+    ))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route(
+            "/profile/calendar-token",
+            get(get_calendar_token).post(regenerate_calendar_token),
+        )
+        .route("/calendar/:token", get(get_feed)))
+}
@@ -0,0 +1,138 @@
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, patch},
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    custom_field::actions::{self, ActionError, FieldChanges},
+    entities::custom_field::FieldKind,
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NewField {
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub name: String,
+    pub kind: FieldKind,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateField {
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub name: Option<String>,
+    pub options: Option<Vec<String>>,
+    #[validate(range(min = 0, code = "negative", message = "Must not be negative"))]
+    pub position: Option<i32>,
+}
+
+pub async fn get_fields(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_fields(&db, &user.id, &project_id).await?))
+}
+
+pub async fn create_field(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<NewField>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let new_field = actions::NewField {
+        name: payload.name,
+        kind: payload.kind,
+        options: payload.options,
+    };
+
+    let field = actions::create_field(&db, &user.id, &project_id, &new_field).await?;
+
+    Ok((StatusCode::CREATED, Json(field)))
+}
+
+pub async fn update_field(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateField>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let changes = FieldChanges {
+        name: payload.name,
+        options: payload.options,
+        position: payload.position,
+    };
+
+    Ok(Json(
+        actions::update_field(&db, &user.id, &id, &changes).await?,
+    ))
+}
+
+pub async fn delete_field(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::delete_field(&db, &user.id, &id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route("/projects/:id/fields", get(get_fields).post(create_field))
+        .route("/fields/:id", patch(update_field).delete(delete_field)))
+}
@@ -0,0 +1,94 @@
+use axum::{
+    extract::Query,
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+
+use crate::{
+    event::{
+        ticket::{self, ActionError},
+        Events,
+    },
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::NotFound => ErrorResponse::new(
+            StatusCode::UNAUTHORIZED,
+            "invalid_ticket",
+            "Invalid or expired ticket",
+        )
+        .into_response(),
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+#[derive(Deserialize)]
+pub struct EventsQuery {
+    pub ticket: String,
+}
+
+pub async fn create_ticket(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok((
+        StatusCode::CREATED,
+        Json(ticket::create_ticket(&db, &user.id).await?),
+    ))
+}
+
+pub async fn get_events(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
+    Query(query): Query<EventsQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let user_id = ticket::redeem_ticket(&db, &query.ticket).await?;
+
+    // Lagged receivers skip the missed events instead of closing the stream.
+    let stream = BroadcastStream::new(events.subscribe()).filter_map(move |event| match event {
+        Ok(event) if event.user_id == user_id => {
+            Some(Event::default().event("todo").json_data(event))
+        }
+        _ => None,
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route("/events", get(get_events))
+        .route("/events/tickets", post(create_ticket)))
+}
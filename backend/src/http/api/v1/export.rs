@@ -0,0 +1,138 @@
+use axum::{
+    body::StreamBody,
+    extract::Query,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    export::{
+        actions::{self, ActionError},
+        formats::ExportFormat,
+    },
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    todo::actions::TodosFilter,
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Auth: {}", .0.message)]
+    Auth(ErrorResponse),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Auth(inner) => inner.into_response(),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+impl From<ErrorResponse> for HandlerError {
+    fn from(error: ErrorResponse) -> Self {
+        HandlerError::Auth(error)
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Todo(_) | ActionError::Csv(_) | ActionError::Json(_) => {
+            tracing::error!("{}", error);
+
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+/// Same filters as `TodosQuery`. Downloads started from a link can't send
+/// an `Authorization` header, so they pass a single-use ticket instead.
+#[derive(Deserialize, Validate)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub is_completed: Option<bool>,
+    pub project_id: Option<Uuid>,
+    pub field_id: Option<Uuid>,
+    pub field_value: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        code = "length_out_of_range",
+        message = "Must be 1 to 100 characters"
+    ))]
+    pub search: Option<String>,
+    pub ticket: Option<String>,
+}
+
+pub async fn export(
+    Extension(db): Extension<DatabaseConnection>,
+    user: Option<AuthUser>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    query.validate()?;
+
+    let user = match (user, &query.ticket) {
+        (Some(user), _) => user,
+        (None, Some(ticket)) => AuthUser::from_ticket(&db, ticket).await?,
+        (None, None) => {
+            return Err(ErrorResponse::new(
+                StatusCode::UNAUTHORIZED,
+                "invalid_token",
+                "Invalid token",
+            )
+            .into())
+        }
+    };
+
+    let filter = TodosFilter {
+        is_completed: query.is_completed,
+        project_id: query.project_id,
+        field: query.field_id.zip(query.field_value),
+        search: query.search,
+    };
+
+    let stream = actions::export(&db, &user.id, &filter, query.format).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, query.format.content_type().to_owned()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", query.format.file_name()),
+            ),
+        ],
+        StreamBody::new(stream),
+    ))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new().route("/export", get(export)))
+}
@@ -0,0 +1,122 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use validator::Validate;
+
+use crate::{
+    event::Events,
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    import::{
+        actions::{self, ActionError, ImportFormat},
+        formats::CsvMapping,
+    },
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Invalid(errors) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, Json(errors)).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize, Default)]
+pub struct ImportMapping {
+    pub name: Option<String>,
+    pub project: Option<String>,
+    pub is_completed: Option<String>,
+    pub created_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub due_at: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NewImport {
+    pub format: ImportFormat,
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub content: String,
+    #[serde(default)]
+    pub mapping: ImportMapping,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+pub async fn import(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
+    user: AuthUser,
+    Json(payload): Json<NewImport>,
+) -> Result<Response, HandlerError> {
+    payload.validate()?;
+
+    let mapping = CsvMapping {
+        name: payload.mapping.name,
+        project: payload.mapping.project,
+        is_completed: payload.mapping.is_completed,
+        created_at: payload.mapping.created_at,
+        completed_at: payload.mapping.completed_at,
+        due_at: payload.mapping.due_at,
+    };
+
+    if payload.dry_run {
+        let preview =
+            actions::preview_import(&db, &user.id, payload.format, &payload.content, &mapping)
+                .await?;
+
+        return Ok(Json(preview).into_response());
+    }
+
+    let imported = actions::import(
+        &db,
+        events.as_ref(),
+        &user.id,
+        payload.format,
+        &payload.content,
+        &mapping,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(imported)).into_response())
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new().route("/import", post(import)))
+}
@@ -0,0 +1,132 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::notification,
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    notification::actions::{self, ActionError},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Serialize)]
+pub struct Notifications {
+    pub data: Vec<notification::Model>,
+    pub count: u64,
+    pub unread: u64,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NotificationsQuery {
+    pub is_read: Option<bool>,
+    #[validate(range(min = 1, max = 50, code = "out_of_range", message = "Out of range"))]
+    #[serde(default = "NotificationsQuery::default_limit")]
+    pub limit: u64,
+    #[validate(range(min = 0, code = "negative", message = "Must not be negative"))]
+    #[serde(default = "NotificationsQuery::default_offset")]
+    pub offset: u64,
+}
+
+impl NotificationsQuery {
+    fn default_limit() -> u64 {
+        10
+    }
+
+    fn default_offset() -> u64 {
+        0
+    }
+}
+
+pub async fn get_notifications(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Query(query): Query<NotificationsQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    query.validate()?;
+
+    let count = actions::get_notifications_count(&db, &user.id, &query.is_read).await?;
+
+    let unread = actions::get_notifications_count(&db, &user.id, &Some(false)).await?;
+
+    let data =
+        actions::get_notifications(&db, &user.id, &query.is_read, &query.limit, &query.offset)
+            .await?;
+
+    Ok(Json(Notifications {
+        data,
+        count,
+        unread,
+    }))
+}
+
+pub async fn mark_read(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::mark_read(&db, &user.id, &id).await?))
+}
+
+pub async fn mark_all_read(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::mark_all_read(&db, &user.id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route("/notifications", get(get_notifications))
+        .route("/notifications/read", post(mark_all_read))
+        .route("/notifications/:id/read", post(mark_read)))
+}
@@ -0,0 +1,131 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use validator::{Validate, ValidationError};
+
+use crate::{
+    entities::user_preferences::{DateFormat, DefaultView, Theme, WeekStart},
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    preference::actions::{self, ActionError, PreferencesChanges},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::InvalidTimeZone => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid_time_zone", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize, Validate)]
+pub struct UpdatePreferences {
+    #[validate(length(
+        min = 1,
+        max = 64,
+        code = "length_out_of_range",
+        message = "Must be 1 to 64 characters"
+    ))]
+    pub time_zone: Option<String>,
+    #[validate(range(
+        min = 1,
+        max = 25,
+        code = "out_of_range",
+        message = "Must be between 1 and 25"
+    ))]
+    pub page_size: i32,
+    pub theme: Theme,
+    pub date_format: DateFormat,
+    pub default_view: DefaultView,
+    pub week_start: WeekStart,
+    #[validate(custom = "validate_locale")]
+    pub locale: String,
+}
+
+/// Loose BCP 47 check, letters and digits in `-` separated subtags.
+fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    let valid = locale.len() <= 35
+        && locale.split('-').all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+
+    if !valid {
+        let mut error = ValidationError::new("invalid_locale");
+
+        error.message = Some("Must be a language tag such as en or de-AT".into());
+
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+pub async fn get_preferences(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_preferences(&db, &user.id).await?))
+}
+
+pub async fn update_preferences(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Json(payload): Json<UpdatePreferences>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let changes = PreferencesChanges {
+        time_zone: payload.time_zone,
+        page_size: payload.page_size,
+        theme: payload.theme,
+        date_format: payload.date_format,
+        default_view: payload.default_view,
+        week_start: payload.week_start,
+        locale: payload.locale,
+    };
+
+    Ok(Json(
+        actions::update_preferences(&db, &user.id, changes).await?,
+    ))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new().route(
+        "/profile/preferences",
+        get(get_preferences).put(update_preferences),
+    ))
+}
@@ -0,0 +1,124 @@
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    project::actions::{self, ActionError},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NewProject {
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub name: String,
+}
+
+pub async fn get_projects(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_projects(&db, &user.id).await?))
+}
+
+pub async fn create_project(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Json(payload): Json<NewProject>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let project = actions::create_project(&db, &user.id, &payload.name).await?;
+
+    Ok((StatusCode::CREATED, Json(project)))
+}
+
+pub async fn get_project(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_project(&db, &user.id, &id).await?))
+}
+
+pub async fn update_project(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<NewProject>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    Ok(Json(
+        actions::update_project(&db, &user.id, &id, &payload.name).await?,
+    ))
+}
+
+pub async fn delete_project(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::delete_project(&db, &user.id, &id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route("/projects", get(get_projects).post(create_project))
+        .route(
+            "/projects/:id",
+            get(get_project)
+                .patch(update_project)
+                .delete(delete_project),
+        ))
+}
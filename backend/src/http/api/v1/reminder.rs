@@ -0,0 +1,98 @@
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    reminder::actions::{self, ActionError},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Job(_) | ActionError::Notification(_) => {
+            tracing::error!("{}", error);
+
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+        }
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+#[derive(Deserialize)]
+pub struct NewReminder {
+    pub remind_at: DateTime<Utc>,
+}
+
+pub async fn get_reminders(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(todo_id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_reminders(&db, &user.id, &todo_id).await?))
+}
+
+pub async fn create_reminder(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(todo_id): Path<Uuid>,
+    Json(payload): Json<NewReminder>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let reminder = actions::create_reminder(&db, &user.id, &todo_id, &payload.remind_at).await?;
+
+    Ok((StatusCode::CREATED, Json(reminder)))
+}
+
+pub async fn delete_reminder(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::delete_reminder(&db, &user.id, &id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route(
+            "/todos/:id/reminders",
+            get(get_reminders).post(create_reminder),
+        )
+        .route("/reminders/:id", delete(delete_reminder)))
+}
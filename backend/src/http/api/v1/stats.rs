@@ -0,0 +1,92 @@
+use axum::{
+    extract::Query,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Json, Router,
+};
+use chrono::{Duration, Months, NaiveDate, Utc};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    todo::stats::{self, ActionError, Period},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::InvalidTimeZone => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid_time_zone", error).into_response()
+        }
+        ActionError::InvalidRange => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid_range", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+#[derive(Deserialize)]
+pub struct StatsQuery {
+    #[serde(default)]
+    pub period: Period,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    #[serde(default = "StatsQuery::default_tz")]
+    pub tz: String,
+}
+
+impl StatsQuery {
+    fn default_tz() -> String {
+        "UTC".into()
+    }
+
+    /// Last 30 days, 12 weeks or 12 months up to `to`.
+    fn default_from(&self, to: &NaiveDate) -> NaiveDate {
+        match self.period {
+            Period::Day => *to - Duration::days(29),
+            Period::Week => *to - Duration::weeks(11),
+            Period::Month => to.checked_sub_months(Months::new(11)).unwrap_or(*to),
+        }
+    }
+}
+
+pub async fn get_stats(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Query(query): Query<StatsQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+
+    let from = query.from.unwrap_or_else(|| query.default_from(&to));
+
+    Ok(Json(
+        stats::get_stats(&db, &user.id, query.period, &from, &to, &query.tz).await?,
+    ))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new().route("/stats", get(get_stats)))
+}
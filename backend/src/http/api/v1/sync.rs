@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::Query,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::{
+    entities::{todo, todo_tombstone},
+    event::Events,
+    http::error::ErrorResponse,
+    http::{deserializers::double_option, extractors::AuthUser},
+    sync::actions::{self, ActionError, Outcome},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize)]
+pub struct SyncQuery {
+    pub since: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct Todo {
+    #[serde(flatten)]
+    pub todo: todo::Model,
+    pub fields: HashMap<Uuid, Value>,
+}
+
+/// The token is a string, clients should treat it as opaque.
+#[derive(Serialize)]
+pub struct Delta {
+    pub todos: Vec<Todo>,
+    pub deleted: Vec<todo_tombstone::Model>,
+    pub token: String,
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Strategy {
+    #[default]
+    LastWriterWins,
+    Merge,
+}
+
+impl From<Strategy> for actions::Strategy {
+    fn from(value: Strategy) -> Self {
+        match value {
+            Strategy::LastWriterWins => actions::Strategy::LastWriterWins,
+            Strategy::Merge => actions::Strategy::Merge,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Validate, Default)]
+pub struct TodoFields {
+    #[validate(length(min = 5, code = "too_short", message = "Too short"))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub project_id: Option<Option<Uuid>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(range(min = 1, code = "must_be_positive", message = "Must be positive"))]
+    pub estimate_minutes: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    pub is_completed: Option<bool>,
+}
+
+impl From<TodoFields> for actions::TodoFields {
+    fn from(value: TodoFields) -> Self {
+        Self {
+            name: value.name,
+            project_id: value.project_id,
+            estimate_minutes: value.estimate_minutes,
+            due_at: value.due_at,
+            is_completed: value.is_completed,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Change {
+    Create {
+        id: Uuid,
+        fields: TodoFields,
+    },
+    Update {
+        id: Uuid,
+        changed_at: DateTime<Utc>,
+        #[serde(default)]
+        base: TodoFields,
+        fields: TodoFields,
+    },
+    Delete {
+        id: Uuid,
+        changed_at: DateTime<Utc>,
+    },
+}
+
+impl Change {
+    fn id(&self) -> Uuid {
+        match self {
+            Change::Create { id, .. } | Change::Update { id, .. } | Change::Delete { id, .. } => {
+                *id
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            Change::Create { fields, .. } => {
+                fields.validate()?;
+
+                if fields.name.is_none() {
+                    return Err(field_errors("name", "required", "Required"));
+                }
+
+                Ok(())
+            }
+            Change::Update { fields, .. } => fields.validate(),
+            Change::Delete { .. } => Ok(()),
+        }
+    }
+}
+
+impl From<Change> for actions::Change {
+    fn from(value: Change) -> Self {
+        match value {
+            Change::Create { id, fields } => actions::Change::Create {
+                id,
+                fields: fields.into(),
+            },
+            Change::Update {
+                id,
+                changed_at,
+                base,
+                fields,
+            } => actions::Change::Update {
+                id,
+                changed_at,
+                base: base.into(),
+                fields: fields.into(),
+            },
+            Change::Delete { id, changed_at } => actions::Change::Delete { id, changed_at },
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct PushChanges {
+    #[serde(default)]
+    pub strategy: Strategy,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        code = "length_out_of_range",
+        message = "Must be 1 to 100 changes"
+    ))]
+    pub changes: Vec<Change>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeStatus {
+    Applied,
+    Merged,
+    Conflict,
+    Deleted,
+    NotFound,
+    Invalid,
+}
+
+/// Outcome of one pushed change. `todo` is the server version after it,
+/// `conflicts` the fields that kept the server value.
+#[derive(Serialize)]
+pub struct ChangeResult {
+    pub id: Uuid,
+    pub status: ChangeStatus,
+    pub todo: Option<Todo>,
+    pub conflicts: Vec<&'static str>,
+    pub errors: Option<ValidationErrors>,
+}
+
+impl ChangeResult {
+    fn new(id: Uuid, status: ChangeStatus) -> Self {
+        Self {
+            id,
+            status,
+            todo: None,
+            conflicts: Vec::new(),
+            errors: None,
+        }
+    }
+}
+
+fn field_errors(
+    field: &'static str,
+    code: &'static str,
+    message: &'static str,
+) -> ValidationErrors {
+    let mut error = ValidationError::new(code);
+
+    error.message = Some(message.into());
+
+    let mut errors = ValidationErrors::new();
+
+    errors.add(field, error);
+
+    errors
+}
+
+fn into_result(
+    id: Uuid,
+    outcome: Outcome,
+    values: &HashMap<Uuid, HashMap<Uuid, Value>>,
+) -> ChangeResult {
+    let (status, todo, conflicts) = match outcome {
+        Outcome::Applied(todo) => (ChangeStatus::Applied, Some(todo), Vec::new()),
+        Outcome::Merged(todo, conflicts) => (ChangeStatus::Merged, Some(todo), conflicts),
+        Outcome::Conflict(todo, conflicts) => (ChangeStatus::Conflict, Some(todo), conflicts),
+        Outcome::Deleted => (ChangeStatus::Deleted, None, Vec::new()),
+        Outcome::NotFound => (ChangeStatus::NotFound, None, Vec::new()),
+        Outcome::UnknownProject => {
+            return ChangeResult {
+                errors: Some(field_errors(
+                    "project_id",
+                    "unknown_project",
+                    "Unknown project",
+                )),
+                ..ChangeResult::new(id, ChangeStatus::Invalid)
+            };
+        }
+    };
+
+    ChangeResult {
+        // A batch may change a todo more than once, values aren't taken out.
+        todo: todo.map(|todo| Todo {
+            fields: values.get(&todo.id).cloned().unwrap_or_default(),
+            todo,
+        }),
+        conflicts,
+        ..ChangeResult::new(id, status)
+    }
+}
+
+pub async fn get_delta(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Query(query): Query<SyncQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let delta = actions::get_delta(&db, &user.id, query.since).await?;
+
+    let ids = delta.todos.iter().map(|todo| todo.id).collect::<Vec<_>>();
+
+    let mut values = actions::get_field_values(&db, &ids).await?;
+
+    let todos = delta
+        .todos
+        .into_iter()
+        .map(|todo| Todo {
+            fields: values.remove(&todo.id).unwrap_or_default(),
+            todo,
+        })
+        .collect();
+
+    Ok(Json(Delta {
+        todos,
+        deleted: delta.deleted,
+        token: delta.token.to_string(),
+    }))
+}
+
+/// Applies pushed changes one by one and reports on each, in order.
+pub async fn push_changes(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
+    user: AuthUser,
+    Json(payload): Json<PushChanges>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    // Invalid changes are reported in place, the others are applied.
+    let mut results = Vec::with_capacity(payload.changes.len());
+
+    let mut changes = Vec::new();
+
+    for change in payload.changes {
+        match change.validate() {
+            Ok(()) => {
+                results.push(None);
+
+                changes.push(actions::Change::from(change));
+            }
+            Err(errors) => results.push(Some(ChangeResult {
+                errors: Some(errors),
+                ..ChangeResult::new(change.id(), ChangeStatus::Invalid)
+            })),
+        }
+    }
+
+    let outcomes = actions::apply_changes(
+        &db,
+        events.as_ref(),
+        &user.id,
+        payload.strategy.into(),
+        &changes,
+    )
+    .await?;
+
+    let ids = outcomes
+        .iter()
+        .filter_map(|outcome| outcome.todo().map(|todo| todo.id))
+        .collect::<Vec<_>>();
+
+    let values = actions::get_field_values(&db, &ids).await?;
+
+    let mut outcomes = changes
+        .iter()
+        .map(actions::Change::id)
+        .zip(outcomes)
+        .map(|(id, outcome)| into_result(id, outcome, &values));
+
+    let results = results
+        .into_iter()
+        .filter_map(|result| result.or_else(|| outcomes.next()))
+        .collect::<Vec<_>>();
+
+    Ok(Json(results))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new().route("/sync", get(get_delta).post(push_changes)))
+}
@@ -0,0 +1,163 @@
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    event::Events,
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    template::actions::{self, ActionError},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NewTemplate {
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub name: String,
+    #[validate]
+    pub items: Vec<NewTemplateItem>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NewTemplateItem {
+    // Items become todos, which are renamed under the same rule.
+    #[validate(length(min = 5, code = "too_short", message = "Too short"))]
+    pub name: String,
+    #[validate(range(min = -3650, max = 3650, code = "out_of_range", message = "Out of range"))]
+    pub due_offset_days: Option<i32>,
+    #[serde(default)]
+    #[validate]
+    pub subtasks: Vec<NewTemplateItem>,
+}
+
+impl From<&NewTemplateItem> for actions::NewTemplateItem {
+    fn from(item: &NewTemplateItem) -> Self {
+        Self {
+            name: item.name.clone(),
+            due_offset_days: item.due_offset_days,
+            subtasks: item.subtasks.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Instantiate {
+    pub project_id: Option<Uuid>,
+    pub start_at: Option<DateTime<Utc>>,
+}
+
+pub async fn get_templates(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_templates(&db, &user.id).await?))
+}
+
+pub async fn create_template(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Json(payload): Json<NewTemplate>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let items: Vec<actions::NewTemplateItem> = payload.items.iter().map(Into::into).collect();
+
+    let template = actions::create_template(&db, &user.id, &payload.name, &items).await?;
+
+    Ok((StatusCode::CREATED, Json(template)))
+}
+
+pub async fn get_template(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_template(&db, &user.id, &id).await?))
+}
+
+pub async fn delete_template(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::delete_template(&db, &user.id, &id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn instantiate_template(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<Instantiate>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let start_at = payload.start_at.unwrap_or_else(Utc::now);
+
+    let todos = actions::instantiate_template(
+        &db,
+        events.as_ref(),
+        &user.id,
+        &id,
+        &payload.project_id,
+        &start_at,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(todos)))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route("/templates", get(get_templates).post(create_template))
+        .route("/templates/:id", get(get_template).delete(delete_template))
+        .route("/templates/:id/instantiate", post(instantiate_template)))
+}
@@ -0,0 +1,206 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::{
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    time_tracking::actions::{self, ActionError},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+        ActionError::InvalidTimeZone => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid_time_zone", error).into_response()
+        }
+        ActionError::TimerAlreadyRunning => {
+            ErrorResponse::new(StatusCode::CONFLICT, "timer_already_running", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize, Validate)]
+#[validate(schema(function = "validate_period"))]
+pub struct NewTimeEntry {
+    #[serde(default)]
+    pub note: String,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: DateTime<Utc>,
+}
+
+fn validate_period(entry: &NewTimeEntry) -> Result<(), ValidationError> {
+    if entry.stopped_at <= entry.started_at {
+        let mut error = ValidationError::new("invalid_period");
+
+        error.message = Some("Must stop after it starts".into());
+
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+pub struct TimesheetQuery {
+    pub week_start: Option<NaiveDate>,
+    #[serde(default = "TimesheetQuery::default_tz")]
+    pub tz: String,
+}
+
+impl TimesheetQuery {
+    fn default_tz() -> String {
+        "UTC".into()
+    }
+}
+
+pub async fn get_timer(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_running_timer(&db, &user.id).await?))
+}
+
+pub async fn start_timer(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let entry = actions::start_timer(&db, &user.id, &id).await?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+pub async fn stop_timer(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::stop_timer(&db, &user.id).await?))
+}
+
+pub async fn get_time_entries(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_time_entries(&db, &user.id, &id).await?))
+}
+
+pub async fn create_time_entry(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<NewTimeEntry>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let new_entry = actions::NewTimeEntry {
+        note: payload.note,
+        started_at: payload.started_at,
+        stopped_at: payload.stopped_at,
+    };
+
+    let entry = actions::create_time_entry(&db, &user.id, &id, &new_entry).await?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+pub async fn delete_time_entry(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::delete_time_entry(&db, &user.id, &id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_todo_time(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_todo_time(&db, &user.id, &id).await?))
+}
+
+pub async fn get_project_time(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_project_time(&db, &user.id, &id).await?))
+}
+
+pub async fn get_timesheet(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Query(query): Query<TimesheetQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let week_start = query.week_start.unwrap_or_else(|| {
+        let today = Utc::now().date_naive();
+
+        today - Duration::days(today.weekday().num_days_from_monday().into())
+    });
+
+    Ok(Json(
+        actions::get_timesheet(&db, &user.id, &week_start, &query.tz).await?,
+    ))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route("/timer", get(get_timer))
+        .route("/timer/stop", post(stop_timer))
+        .route("/todos/:id/timer/start", post(start_timer))
+        .route(
+            "/todos/:id/time-entries",
+            get(get_time_entries).post(create_time_entry),
+        )
+        .route("/todos/:id/time", get(get_todo_time))
+        .route("/time-entries/:id", delete(delete_time_entry))
+        .route("/projects/:id/time", get(get_project_time))
+        .route("/reports/timesheet", get(get_timesheet)))
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use axum::{
     extract::{Path, Query},
     http::StatusCode,
@@ -5,15 +7,23 @@ use axum::{
     routing::{get, post},
     Extension, Json, Router,
 };
-use sea_orm::{DatabaseConnection, DbErr};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sea_orm::{DatabaseConnection, DbErr, Order};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use thiserror::Error;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 use crate::{
-    http::extractors::AuthUser,
-    todo::actions::{self, ActionError},
+    entities::{
+        custom_field::{self, FieldKind},
+        todo,
+    },
+    event::Events,
+    http::error::ErrorResponse,
+    http::{deserializers::double_option, extractors::AuthUser},
+    todo::actions::{self, ActionError, FieldSort, TodoChanges, TodosFilter},
 };
 
 #[derive(Debug, Error)]
@@ -22,6 +32,8 @@ pub enum HandlerError {
     Action(#[from] ActionError),
     #[error("Validation: {0}")]
     Validation(#[from] validator::ValidationErrors),
+    #[error("Fields validation")]
+    Fields(HashMap<Uuid, Vec<ValidationError>>),
 }
 
 impl IntoResponse for HandlerError {
@@ -29,6 +41,7 @@ impl IntoResponse for HandlerError {
         match self {
             HandlerError::Action(inner) => action_into_response(inner),
             HandlerError::Validation(inner) => validation_into_response(inner),
+            HandlerError::Fields(inner) => fields_into_response(inner),
         }
     }
 }
@@ -36,21 +49,33 @@ impl IntoResponse for HandlerError {
 fn action_into_response(error: ActionError) -> Response {
     match error {
         ActionError::Db(inner) => db_into_response(inner),
-        ActionError::Forbidden => (StatusCode::FORBIDDEN, error.to_string()).into_response(),
-        ActionError::NotFound => (StatusCode::NOT_FOUND, error.to_string()).into_response(),
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
     }
 }
 
 fn db_into_response(error: DbErr) -> Response {
     tracing::error!("{}", error);
 
-    (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
 }
 
 fn validation_into_response(error: validator::ValidationErrors) -> Response {
     (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
 }
 
+fn fields_into_response(errors: HashMap<Uuid, Vec<ValidationError>>) -> Response {
+    (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Json(json!({ "fields": errors })),
+    )
+        .into_response()
+}
+
 #[derive(Serialize)]
 pub struct Paginated<T>
 where
@@ -60,25 +85,65 @@ where
     pub count: u64,
 }
 
+#[derive(Serialize)]
+pub struct Todo {
+    #[serde(flatten)]
+    pub todo: todo::Model,
+    pub fields: HashMap<Uuid, Value>,
+}
+
 #[derive(Deserialize, Validate)]
 pub struct NewTodo {
-    #[validate(length(min = 5, message = "Too short"))]
+    #[validate(length(min = 5, code = "too_short", message = "Too short"))]
     pub name: String,
+    pub project_id: Option<Uuid>,
+    #[validate(range(min = 1, code = "must_be_positive", message = "Must be positive"))]
+    pub estimate_minutes: Option<i32>,
+    #[serde(default)]
+    pub fields: HashMap<Uuid, Value>,
 }
 
 #[derive(Deserialize, Validate)]
 pub struct UpdateTodo {
-    #[validate(length(min = 5, message = "Too short"))]
-    pub name: String,
+    #[validate(length(min = 5, code = "too_short", message = "Too short"))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub project_id: Option<Option<Uuid>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(range(min = 1, code = "must_be_positive", message = "Must be positive"))]
+    pub estimate_minutes: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    #[serde(default)]
+    pub fields: HashMap<Uuid, Value>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
 }
 
 #[derive(Deserialize, Validate)]
 pub struct TodosQuery {
     pub is_completed: Option<bool>,
-    #[validate(range(min = 1, max = 25))]
+    pub project_id: Option<Uuid>,
+    pub field_id: Option<Uuid>,
+    pub field_value: Option<String>,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        code = "length_out_of_range",
+        message = "Must be 1 to 100 characters"
+    ))]
+    pub search: Option<String>,
+    pub sort_field_id: Option<Uuid>,
+    pub sort_order: Option<SortOrder>,
+    #[validate(range(min = 1, max = 25, code = "out_of_range", message = "Out of range"))]
     #[serde(default = "TodosQuery::default_limit")]
     pub limit: u64,
-    #[validate(range(min = 0))]
+    #[validate(range(min = 0, code = "negative", message = "Must not be negative"))]
     #[serde(default = "TodosQuery::default_offset")]
     pub offset: u64,
 }
@@ -86,6 +151,28 @@ pub struct TodosQuery {
 #[derive(Deserialize, Validate)]
 pub struct TodosDeleteQuery {
     pub is_completed: Option<bool>,
+    pub project_id: Option<Uuid>,
+}
+
+/// Range of a calendar view, at most a bit more than six weeks so a month
+/// grid with its leading and trailing days fits.
+#[derive(Deserialize, Validate)]
+#[validate(schema(function = "validate_range"))]
+pub struct CalendarQuery {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+fn validate_range(query: &CalendarQuery) -> Result<(), ValidationError> {
+    if query.to <= query.from || query.to - query.from > Duration::days(45) {
+        let mut error = ValidationError::new("invalid_range");
+
+        error.message = Some("Must end after it starts and span at most 45 days".into());
+
+        return Err(error);
+    }
+
+    Ok(())
 }
 
 impl TodosQuery {
@@ -96,6 +183,120 @@ impl TodosQuery {
     fn default_offset() -> u64 {
         0
     }
+
+    fn filter(&self) -> TodosFilter {
+        TodosFilter {
+            is_completed: self.is_completed,
+            project_id: self.project_id,
+            field: self.field_id.zip(self.field_value.clone()),
+            search: self.search.clone(),
+        }
+    }
+
+    fn sort(&self) -> Option<FieldSort> {
+        self.sort_field_id.map(|field_id| FieldSort {
+            field_id,
+            order: match self.sort_order {
+                Some(SortOrder::Desc) => Order::Desc,
+                _ => Order::Asc,
+            },
+        })
+    }
+}
+
+fn field_error(code: &'static str, message: &'static str) -> ValidationError {
+    let mut error = ValidationError::new(code);
+
+    error.message = Some(message.into());
+
+    error
+}
+
+fn validate_field_value(field: &custom_field::Model, value: &Value) -> Result<(), ValidationError> {
+    if value.is_null() {
+        return Ok(());
+    }
+
+    match field.kind {
+        FieldKind::Text if value.is_string() => Ok(()),
+        FieldKind::Number if value.is_number() => Ok(()),
+        FieldKind::Checkbox if value.is_boolean() => Ok(()),
+        FieldKind::Date => match value.as_str() {
+            Some(date) if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok() => Ok(()),
+            _ => Err(field_error("invalid_date", "Expected a date (YYYY-MM-DD)")),
+        },
+        FieldKind::Select => {
+            let options = field.options.as_array().cloned().unwrap_or_default();
+
+            if value.is_string() && options.contains(value) {
+                Ok(())
+            } else {
+                Err(field_error("invalid_option", "Not one of the options"))
+            }
+        }
+        _ => Err(field_error("invalid_type", "Invalid value type")),
+    }
+}
+
+async fn validate_fields(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: Option<Uuid>,
+    values: &HashMap<Uuid, Value>,
+) -> Result<(), HandlerError> {
+    if values.is_empty() {
+        return Ok(());
+    }
+
+    // Errors would reveal the fields of other users' projects.
+    let fields = match project_id {
+        Some(project_id) => {
+            actions::check_project(db, user_id, &project_id).await?;
+
+            actions::get_project_fields(db, &project_id).await?
+        }
+        None => Vec::new(),
+    };
+
+    let mut errors = HashMap::new();
+
+    for (field_id, value) in values {
+        let result = match fields.iter().find(|field| field.id == *field_id) {
+            Some(field) => validate_field_value(field, value),
+            None => Err(field_error("unknown_field", "Unknown field")),
+        };
+
+        if let Err(error) = result {
+            errors.insert(*field_id, vec![error]);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(HandlerError::Fields(errors))
+    }
+}
+
+async fn with_fields(
+    db: &DatabaseConnection,
+    todos: Vec<todo::Model>,
+) -> Result<Vec<Todo>, HandlerError> {
+    let ids = todos.iter().map(|todo| todo.id).collect::<Vec<_>>();
+
+    let mut values = actions::get_field_values(db, &ids).await?;
+
+    Ok(todos
+        .into_iter()
+        .map(|todo| Todo {
+            fields: values.remove(&todo.id).unwrap_or_default(),
+            todo,
+        })
+        .collect())
+}
+
+async fn with_fields_one(db: &DatabaseConnection, todo: todo::Model) -> Result<Todo, HandlerError> {
+    Ok(with_fields(db, vec![todo]).await?.remove(0))
 }
 
 pub async fn get_todos(
@@ -105,40 +306,74 @@ pub async fn get_todos(
 ) -> Result<impl IntoResponse, HandlerError> {
     query.validate()?;
 
-    let count = actions::get_todos_count(&db, &user.id, query.is_completed).await?;
+    let filter = query.filter();
+
+    let count = actions::get_todos_count(&db, &user.id, &filter).await?;
 
     let data = actions::get_todos(
         &db,
         &user.id,
-        query.is_completed,
+        &filter,
+        &query.sort(),
         &query.limit,
         &query.offset,
     )
     .await?;
 
+    let data = with_fields(&db, data).await?;
+
     Ok(Json(Paginated { data, count }))
 }
 
+pub async fn get_calendar_todos(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Query(query): Query<CalendarQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    query.validate()?;
+
+    let data = actions::get_todos_between(&db, &user.id, &query.from, &query.to).await?;
+
+    Ok(Json(with_fields(&db, data).await?))
+}
+
 pub async fn delete_todos(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
     user: AuthUser,
-    Query(query): Query<TodosQuery>,
+    Query(query): Query<TodosDeleteQuery>,
 ) -> Result<impl IntoResponse, HandlerError> {
-    actions::delete_todos(&db, &user.id, query.is_completed).await?;
+    let filter = TodosFilter {
+        is_completed: query.is_completed,
+        project_id: query.project_id,
+        ..Default::default()
+    };
+
+    actions::delete_todos(&db, events.as_ref(), &user.id, &filter).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 pub async fn create_todo(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
     user: AuthUser,
     Json(payload): Json<NewTodo>,
 ) -> Result<impl IntoResponse, HandlerError> {
     payload.validate()?;
 
-    let todo = actions::create_todo(&db, &user.id, &payload.name).await?;
+    validate_fields(&db, &user.id, payload.project_id, &payload.fields).await?;
+
+    let new_todo = actions::NewTodo {
+        name: payload.name,
+        project_id: payload.project_id,
+        estimate_minutes: payload.estimate_minutes,
+        fields: payload.fields,
+    };
 
-    Ok((StatusCode::CREATED, Json(todo)))
+    let todo = actions::create_todo(&db, events.as_ref(), &user.id, &new_todo).await?;
+
+    Ok((StatusCode::CREATED, Json(with_fields_one(&db, todo).await?)))
 }
 
 pub async fn get_todo(
@@ -146,46 +381,73 @@ pub async fn get_todo(
     user: AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, HandlerError> {
-    Ok(Json(actions::get_todo(&db, &user.id, &id).await?))
+    let todo = actions::get_todo(&db, &user.id, &id).await?;
+
+    Ok(Json(with_fields_one(&db, todo).await?))
 }
 
 pub async fn update_todo(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
     user: AuthUser,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateTodo>,
 ) -> Result<impl IntoResponse, HandlerError> {
     payload.validate()?;
 
-    Ok(Json(
-        actions::update_todo(&db, &user.id, &id, &payload.name).await?,
-    ))
+    if !payload.fields.is_empty() {
+        let project_id = match payload.project_id {
+            Some(project_id) => project_id,
+            None => actions::get_todo(&db, &user.id, &id).await?.project_id,
+        };
+
+        validate_fields(&db, &user.id, project_id, &payload.fields).await?;
+    }
+
+    let changes = TodoChanges {
+        name: payload.name,
+        project_id: payload.project_id,
+        estimate_minutes: payload.estimate_minutes,
+        due_at: payload.due_at,
+        fields: payload.fields,
+    };
+
+    let todo = actions::update_todo(&db, events.as_ref(), &user.id, &id, &changes).await?;
+
+    Ok(Json(with_fields_one(&db, todo).await?))
 }
 
 pub async fn delete_todo(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
     user: AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, HandlerError> {
-    actions::delete_todo(&db, &user.id, &id).await?;
+    actions::delete_todo(&db, events.as_ref(), &user.id, &id).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 pub async fn complete_todo(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
     user: AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, HandlerError> {
-    Ok(Json(actions::complete_todo(&db, &user.id, &id).await?))
+    let todo = actions::complete_todo(&db, events.as_ref(), &user.id, &id).await?;
+
+    Ok(Json(with_fields_one(&db, todo).await?))
 }
 
 pub async fn revert_todo(
     Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
     user: AuthUser,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, HandlerError> {
-    Ok(Json(actions::revert_todo(&db, &user.id, &id).await?))
+    let todo = actions::revert_todo(&db, events.as_ref(), &user.id, &id).await?;
+
+    Ok(Json(with_fields_one(&db, todo).await?))
 }
 
 pub async fn create_router() -> anyhow::Result<Router> {
@@ -194,6 +456,7 @@ pub async fn create_router() -> anyhow::Result<Router> {
             "/todos",
             get(get_todos).post(create_todo).delete(delete_todos),
         )
+        .route("/todos/calendar", get(get_calendar_todos))
         .route(
             "/todos/:id",
             get(get_todo).patch(update_todo).delete(delete_todo),
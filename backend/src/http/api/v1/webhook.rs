@@ -0,0 +1,212 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    event::TodoEventKind,
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    webhook::actions::{self, ActionError, WebhookChanges},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Job(_) | ActionError::Delivery(_) => {
+            tracing::error!("{}", error);
+
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+        }
+        ActionError::InvalidUrl => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid_url", error).into_response()
+        }
+        ActionError::PrivateUrl => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "private_url", error).into_response()
+        }
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NewWebhook {
+    #[validate(url(code = "invalid_url", message = "Invalid url"))]
+    pub url: String,
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub events: Vec<TodoEventKind>,
+    #[validate(length(min = 16, code = "too_short", message = "Too short"))]
+    pub secret: String,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateWebhook {
+    #[validate(url(code = "invalid_url", message = "Invalid url"))]
+    pub url: Option<String>,
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub events: Option<Vec<TodoEventKind>>,
+    #[validate(length(min = 16, code = "too_short", message = "Too short"))]
+    pub secret: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct DeliveriesQuery {
+    #[validate(range(min = 1, max = 50, code = "out_of_range", message = "Out of range"))]
+    #[serde(default = "DeliveriesQuery::default_limit")]
+    pub limit: u64,
+    #[validate(range(min = 0, code = "negative", message = "Must not be negative"))]
+    #[serde(default = "DeliveriesQuery::default_offset")]
+    pub offset: u64,
+}
+
+impl DeliveriesQuery {
+    fn default_limit() -> u64 {
+        10
+    }
+
+    fn default_offset() -> u64 {
+        0
+    }
+}
+
+pub async fn get_webhooks(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_webhooks(&db, &user.id).await?))
+}
+
+pub async fn create_webhook(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Json(payload): Json<NewWebhook>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let new_webhook = actions::NewWebhook {
+        url: payload.url,
+        events: payload.events,
+        secret: payload.secret,
+    };
+
+    let webhook = actions::create_webhook(&db, &user.id, &new_webhook).await?;
+
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+pub async fn get_webhook(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_webhook(&db, &user.id, &id).await?))
+}
+
+pub async fn update_webhook(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateWebhook>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let changes = WebhookChanges {
+        url: payload.url,
+        events: payload.events,
+        secret: payload.secret,
+        is_active: payload.is_active,
+    };
+
+    Ok(Json(
+        actions::update_webhook(&db, &user.id, &id, &changes).await?,
+    ))
+}
+
+pub async fn delete_webhook(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::delete_webhook(&db, &user.id, &id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_deliveries(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Query(query): Query<DeliveriesQuery>,
+) -> Result<impl IntoResponse, HandlerError> {
+    query.validate()?;
+
+    Ok(Json(
+        actions::get_deliveries(&db, &user.id, &id, &query.limit, &query.offset).await?,
+    ))
+}
+
+pub async fn redeliver(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, HandlerError> {
+    let delivery = actions::redeliver(&db, &user.id, &id, &delivery_id).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(delivery)))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route("/webhooks", get(get_webhooks).post(create_webhook))
+        .route(
+            "/webhooks/:id",
+            get(get_webhook)
+                .patch(update_webhook)
+                .delete(delete_webhook),
+        )
+        .route("/webhooks/:id/deliveries", get(get_deliveries))
+        .route(
+            "/webhooks/:id/deliveries/:delivery_id/redeliver",
+            post(redeliver),
+        ))
+}
@@ -0,0 +1,176 @@
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, patch, post},
+    Extension, Json, Router,
+};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    entities::status::StatusCategory,
+    event::Events,
+    http::error::ErrorResponse,
+    http::extractors::AuthUser,
+    workflow::actions::{self, ActionError, StatusChanges},
+};
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Validation: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Validation(inner) => validation_into_response(inner),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::Forbidden => {
+            ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", error).into_response()
+        }
+        ActionError::NotFound => {
+            ErrorResponse::new(StatusCode::NOT_FOUND, "not_found", error).into_response()
+        }
+        ActionError::InvalidStatus => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid_status", error).into_response()
+        }
+        ActionError::IncompleteWorkflow => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "incomplete_workflow", error)
+                .into_response()
+        }
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal", error).into_response()
+}
+
+fn validation_into_response(error: validator::ValidationErrors) -> Response {
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response()
+}
+
+#[derive(Deserialize, Validate)]
+pub struct NewStatus {
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub name: String,
+    pub category: StatusCategory,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateStatus {
+    #[validate(length(min = 1, code = "too_short", message = "Too short"))]
+    pub name: Option<String>,
+    pub category: Option<StatusCategory>,
+    #[validate(range(min = 0, code = "negative", message = "Must not be negative"))]
+    pub position: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct Transition {
+    pub status_id: Uuid,
+}
+
+pub async fn get_statuses(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(
+        actions::get_statuses(&db, &user.id, &project_id).await?,
+    ))
+}
+
+pub async fn create_status(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<NewStatus>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let new_status = actions::NewStatus {
+        name: payload.name,
+        category: payload.category,
+    };
+
+    let status = actions::create_status(&db, &user.id, &project_id, &new_status).await?;
+
+    Ok((StatusCode::CREATED, Json(status)))
+}
+
+pub async fn update_status(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateStatus>,
+) -> Result<impl IntoResponse, HandlerError> {
+    payload.validate()?;
+
+    let changes = StatusChanges {
+        name: payload.name,
+        category: payload.category,
+        position: payload.position,
+    };
+
+    Ok(Json(
+        actions::update_status(&db, events.as_ref(), &user.id, &id, &changes).await?,
+    ))
+}
+
+pub async fn delete_status(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    actions::delete_status(&db, &user.id, &id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_board(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AuthUser,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(actions::get_board(&db, &user.id, &project_id).await?))
+}
+
+pub async fn transition_todo(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
+    user: AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<Transition>,
+) -> Result<impl IntoResponse, HandlerError> {
+    Ok(Json(
+        actions::transition_todo(&db, events.as_ref(), &user.id, &id, &payload.status_id).await?,
+    ))
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route(
+            "/projects/:id/statuses",
+            get(get_statuses).post(create_status),
+        )
+        .route("/projects/:id/board", get(get_board))
+        .route("/statuses/:id", patch(update_status).delete(delete_status))
+        .route("/todos/:id/status", post(transition_todo)))
+}
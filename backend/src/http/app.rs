@@ -1,38 +1,63 @@
 use axum::{
-    http::{header, Method},
+    body::Body,
+    http::{header, Method, Request},
     Extension, Router,
 };
 use tower_http::{
     cors::{Any, CorsLayer},
-    trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer},
+    trace::{DefaultOnResponse, TraceLayer},
 };
 use tracing::Level;
 
-use super::{api, swagger};
-use crate::database;
+use super::{api, caldav, swagger};
+use crate::{database, event, job, webhook};
 
 pub async fn create_app() -> anyhow::Result<Router> {
+    // Only the path is recorded, queries may carry access tokens.
     let trace_layer = TraceLayer::new_for_http()
-        .make_span_with(DefaultMakeSpan::new().level(Level::INFO))
+        .make_span_with(|request: &Request<Body>| {
+            tracing::info_span!(
+                "request",
+                method = %request.method(),
+                uri = %request.uri().path(),
+                version = ?request.version(),
+            )
+        })
         .on_response(DefaultOnResponse::new().level(Level::INFO));
 
     let cors_layer = CorsLayer::new()
         .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
-        .allow_methods([Method::GET, Method::POST, Method::PATCH, Method::DELETE])
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
         .allow_origin(Any);
 
     let db = database::get_db().await?;
 
+    job::scheduler::spawn(db.clone());
+
+    let events = event::create_event_bus(&db).await?;
+
+    webhook::dispatcher::spawn(db.clone(), events.clone());
+
     let api_router = api::create_router().await?;
 
     let swagger_router = swagger::create_router().await?;
 
+    let caldav_router = caldav::create_router().await?;
+
     let app = Router::new()
         .nest("/api", api_router)
         .nest("/swagger", swagger_router)
+        .merge(caldav_router)
         .layer(trace_layer)
         .layer(cors_layer)
-        .layer(Extension(db));
+        .layer(Extension(db))
+        .layer(Extension(events));
 
     Ok(app)
 }
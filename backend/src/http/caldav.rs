@@ -0,0 +1,536 @@
+use axum::{
+    extract::Path,
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    routing::{any, get},
+    Extension, Router,
+};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use sea_orm::{DatabaseConnection, DbErr};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    caldav::{
+        actions::{self, ActionError, Collection, List, Object, Preconditions},
+        xml::{self, Element, Name, CALDAV, CALENDAR_SERVER, DAV},
+    },
+    event::Events,
+    http::extractors::AppPasswordUser,
+};
+
+/// Characters kept as is in resource names of hrefs.
+const NAME: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~')
+    .remove(b'@');
+
+const ALLOW: &str = "OPTIONS, GET, HEAD, PUT, DELETE, PROPFIND, REPORT";
+
+#[derive(Debug, Error)]
+pub enum HandlerError {
+    #[error("Action: {0}")]
+    Action(#[from] ActionError),
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Unsupported report")]
+    UnsupportedReport,
+    #[error("Method Not Allowed")]
+    MethodNotAllowed,
+}
+
+impl IntoResponse for HandlerError {
+    fn into_response(self) -> Response {
+        match self {
+            HandlerError::Action(inner) => action_into_response(inner),
+            HandlerError::Forbidden | HandlerError::UnsupportedReport => {
+                (StatusCode::FORBIDDEN, self.to_string()).into_response()
+            }
+            HandlerError::MethodNotAllowed => (
+                StatusCode::METHOD_NOT_ALLOWED,
+                [(header::ALLOW, ALLOW)],
+                self.to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
+
+fn action_into_response(error: ActionError) -> Response {
+    match error {
+        ActionError::Db(inner) => db_into_response(inner),
+        ActionError::InvalidCalendarData => (
+            StatusCode::FORBIDDEN,
+            [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+            xml::error(&Name::new(CALDAV, "valid-calendar-data")),
+        )
+            .into_response(),
+        ActionError::PreconditionFailed => {
+            (StatusCode::PRECONDITION_FAILED, error.to_string()).into_response()
+        }
+        ActionError::Forbidden => (StatusCode::FORBIDDEN, error.to_string()).into_response(),
+        ActionError::NotFound => (StatusCode::NOT_FOUND, error.to_string()).into_response(),
+    }
+}
+
+fn db_into_response(error: DbErr) -> Response {
+    tracing::error!("{}", error);
+
+    (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response()
+}
+
+enum Resource<'a> {
+    Root,
+    Home,
+    Collection(&'a Collection),
+    Object(&'a Object),
+}
+
+fn home_href(user: &AppPasswordUser) -> String {
+    format!("/caldav/{}/", user.id)
+}
+
+fn collection_href(user: &AppPasswordUser, list: &List) -> String {
+    format!("/caldav/{}/{}/", user.id, list.segment())
+}
+
+fn object_href(user: &AppPasswordUser, list: &List, name: &str) -> String {
+    format!(
+        "/caldav/{}/{}/{}",
+        user.id,
+        list.segment(),
+        utf8_percent_encode(name, NAME)
+    )
+}
+
+fn href(value: &str) -> String {
+    format!("<d:href>{}</d:href>", xml::escape(value))
+}
+
+/// Properties reported for `allprop` requests and bodiless `PROPFIND`s.
+fn all_props() -> Vec<Name> {
+    vec![
+        Name::new(DAV, "resourcetype"),
+        Name::new(DAV, "displayname"),
+        Name::new(DAV, "getetag"),
+        Name::new(DAV, "getcontenttype"),
+        Name::new(CALENDAR_SERVER, "getctag"),
+    ]
+}
+
+fn prop(user: &AppPasswordUser, resource: &Resource, name: &Name) -> Option<String> {
+    match (name.namespace.as_str(), name.local.as_str(), resource) {
+        (DAV, "resourcetype", Resource::Root) => Some("<d:collection/>".to_owned()),
+        (DAV, "resourcetype", Resource::Home) => {
+            Some("<d:collection/><d:principal/>".to_owned())
+        }
+        (DAV, "resourcetype", Resource::Collection(_)) => {
+            Some("<d:collection/><c:calendar/>".to_owned())
+        }
+        (DAV, "resourcetype", Resource::Object(_)) => Some(String::new()),
+        (DAV, "displayname", Resource::Home) => Some(xml::escape(&user.username)),
+        (DAV, "displayname", Resource::Collection(collection)) => {
+            Some(xml::escape(&collection.name))
+        }
+        (DAV, "current-user-principal", _) => Some(href(&home_href(user))),
+        (DAV, "principal-URL" | "owner", Resource::Home | Resource::Collection(_)) => {
+            Some(href(&home_href(user)))
+        }
+        (CALDAV, "calendar-home-set", Resource::Root | Resource::Home) => {
+            Some(href(&home_href(user)))
+        }
+        (DAV, "current-user-privilege-set", Resource::Collection(_) | Resource::Object(_)) => {
+            Some(
+                ["read", "write", "write-content", "bind", "unbind"]
+                    .map(|privilege| format!("<d:privilege><d:{privilege}/></d:privilege>"))
+                    .concat(),
+            )
+        }
+        (DAV, "supported-report-set", Resource::Collection(_)) => Some(
+            ["calendar-query", "calendar-multiget"]
+                .map(|report| {
+                    format!("<d:supported-report><d:report><c:{report}/></d:report></d:supported-report>")
+                })
+                .concat(),
+        ),
+        (CALDAV, "supported-calendar-component-set", Resource::Collection(_)) => {
+            Some("<c:comp name=\"VTODO\"/>".to_owned())
+        }
+        (CALENDAR_SERVER, "getctag", Resource::Collection(collection)) => {
+            Some(xml::escape(&collection.ctag))
+        }
+        (DAV, "getetag", Resource::Object(object)) => Some(xml::escape(&object.etag())),
+        (DAV, "getcontenttype", Resource::Object(_)) => {
+            Some("text/calendar; charset=utf-8; component=VTODO".to_owned())
+        }
+        (CALDAV, "calendar-data", Resource::Object(object)) => {
+            Some(xml::escape(&object.render()))
+        }
+        _ => None,
+    }
+}
+
+/// Requested properties, `None` when all of them are asked for.
+fn requested_props(body: &str) -> Option<Vec<Name>> {
+    let root = Element::parse(body)?;
+
+    let prop = root.child(DAV, "prop")?;
+
+    Some(
+        prop.children
+            .iter()
+            .map(|child| child.name.clone())
+            .collect(),
+    )
+}
+
+fn response(
+    user: &AppPasswordUser,
+    href: String,
+    resource: &Resource,
+    props: &Option<Vec<Name>>,
+) -> xml::Response {
+    let mut response = xml::Response::new(href);
+
+    match props {
+        Some(props) => {
+            for name in props {
+                response.prop(name.clone(), prop(user, resource, name));
+            }
+        }
+        None => {
+            for name in all_props() {
+                if let Some(value) = prop(user, resource, &name) {
+                    response.prop(name, Some(value));
+                }
+            }
+        }
+    }
+
+    response
+}
+
+fn multistatus(responses: &[xml::Response]) -> Response {
+    (
+        StatusCode::MULTI_STATUS,
+        [(header::CONTENT_TYPE, "application/xml; charset=utf-8")],
+        xml::multistatus(responses),
+    )
+        .into_response()
+}
+
+fn options() -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::ALLOW, ALLOW),
+            (header::HeaderName::from_static("dav"), "1, calendar-access"),
+        ],
+    )
+        .into_response()
+}
+
+/// `Depth: 0` lists the resource alone, anything else its members too.
+fn has_depth(headers: &HeaderMap) -> bool {
+    headers
+        .get("depth")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim() != "0")
+        .unwrap_or(true)
+}
+
+fn preconditions(headers: &HeaderMap) -> Preconditions {
+    let value = |name: header::HeaderName| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_owned())
+    };
+
+    Preconditions {
+        if_match: value(header::IF_MATCH),
+        if_none_match: value(header::IF_NONE_MATCH),
+    }
+}
+
+fn check_user(user: &AppPasswordUser, user_id: &Uuid) -> Result<(), HandlerError> {
+    match user.id == *user_id {
+        true => Ok(()),
+        false => Err(HandlerError::Forbidden),
+    }
+}
+
+fn parse_list(list: &str) -> Result<List, HandlerError> {
+    List::parse(list).ok_or(HandlerError::Action(ActionError::NotFound))
+}
+
+pub async fn well_known() -> impl IntoResponse {
+    Redirect::permanent("/caldav/")
+}
+
+pub async fn root(
+    user: AppPasswordUser,
+    method: Method,
+    body: String,
+) -> Result<Response, HandlerError> {
+    match method.as_str() {
+        "OPTIONS" => Ok(options()),
+        "PROPFIND" => {
+            let props = requested_props(&body);
+
+            Ok(multistatus(&[response(
+                &user,
+                "/caldav/".to_owned(),
+                &Resource::Root,
+                &props,
+            )]))
+        }
+        _ => Err(HandlerError::MethodNotAllowed),
+    }
+}
+
+pub async fn home(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AppPasswordUser,
+    Path(user_id): Path<Uuid>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, HandlerError> {
+    check_user(&user, &user_id)?;
+
+    match method.as_str() {
+        "OPTIONS" => Ok(options()),
+        "PROPFIND" => {
+            let props = requested_props(&body);
+
+            let mut responses = vec![response(&user, home_href(&user), &Resource::Home, &props)];
+
+            if has_depth(&headers) {
+                for collection in actions::get_collections(&db, &user.id).await? {
+                    responses.push(response(
+                        &user,
+                        collection_href(&user, &collection.list),
+                        &Resource::Collection(&collection),
+                        &props,
+                    ));
+                }
+            }
+
+            Ok(multistatus(&responses))
+        }
+        _ => Err(HandlerError::MethodNotAllowed),
+    }
+}
+
+pub async fn collection(
+    Extension(db): Extension<DatabaseConnection>,
+    user: AppPasswordUser,
+    Path((user_id, list)): Path<(Uuid, String)>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, HandlerError> {
+    check_user(&user, &user_id)?;
+
+    let list = parse_list(&list)?;
+
+    match method.as_str() {
+        "OPTIONS" => Ok(options()),
+        "PROPFIND" => {
+            let props = requested_props(&body);
+
+            let collection = actions::get_collection(&db, &user.id, &list).await?;
+
+            let mut responses = vec![response(
+                &user,
+                collection_href(&user, &list),
+                &Resource::Collection(&collection),
+                &props,
+            )];
+
+            if has_depth(&headers) {
+                for object in actions::get_objects(&db, &user.id, &list).await? {
+                    responses.push(response(
+                        &user,
+                        object_href(&user, &list, &object.name),
+                        &Resource::Object(&object),
+                        &props,
+                    ));
+                }
+            }
+
+            Ok(multistatus(&responses))
+        }
+        "REPORT" => report(&db, &user, &list, &body).await,
+        _ => Err(HandlerError::MethodNotAllowed),
+    }
+}
+
+/// Answers `calendar-query` with every todo of the collection unless only
+/// other components are asked for, and `calendar-multiget` by href.
+async fn report(
+    db: &DatabaseConnection,
+    user: &AppPasswordUser,
+    list: &List,
+    body: &str,
+) -> Result<Response, HandlerError> {
+    let root = Element::parse(body).ok_or(HandlerError::UnsupportedReport)?;
+
+    let props = root.child(DAV, "prop").map(|prop| {
+        prop.children
+            .iter()
+            .map(|child| child.name.clone())
+            .collect()
+    });
+
+    let mut responses = Vec::new();
+
+    if root.name.is(CALDAV, "calendar-query") {
+        let components: Vec<&str> = root
+            .child(CALDAV, "filter")
+            .and_then(|filter| filter.child(CALDAV, "comp-filter"))
+            .map(|calendar| {
+                calendar
+                    .children_named(CALDAV, "comp-filter")
+                    .filter_map(|filter| filter.attributes.get("name"))
+                    .map(|name| name.as_str())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if components.is_empty() || components.contains(&"VTODO") {
+            for object in actions::get_objects(db, &user.id, list).await? {
+                responses.push(response(
+                    user,
+                    object_href(user, list, &object.name),
+                    &Resource::Object(&object),
+                    &props,
+                ));
+            }
+        }
+    } else if root.name.is(CALDAV, "calendar-multiget") {
+        for element in root.children_named(DAV, "href") {
+            let href = element.text.trim();
+
+            let name = href.rsplit('/').next().unwrap_or_default();
+            let name = percent_decode_str(name).decode_utf8_lossy();
+
+            match actions::get_object(db, &user.id, list, &name).await {
+                Ok(object) => responses.push(response(
+                    user,
+                    href.to_owned(),
+                    &Resource::Object(&object),
+                    &props,
+                )),
+                Err(ActionError::NotFound) => {
+                    responses.push(xml::Response::not_found(href.to_owned()))
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    } else {
+        return Err(HandlerError::UnsupportedReport);
+    }
+
+    Ok(multistatus(&responses))
+}
+
+fn etag(object: &Object) -> (header::HeaderName, HeaderValue) {
+    (
+        header::ETAG,
+        HeaderValue::from_str(&object.etag()).expect("ETag is a valid header value"),
+    )
+}
+
+pub async fn object(
+    Extension(db): Extension<DatabaseConnection>,
+    Extension(events): Extension<Events>,
+    user: AppPasswordUser,
+    Path((user_id, list, name)): Path<(Uuid, String, String)>,
+    method: Method,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response, HandlerError> {
+    check_user(&user, &user_id)?;
+
+    let list = parse_list(&list)?;
+
+    match method.as_str() {
+        "OPTIONS" => Ok(options()),
+        "GET" | "HEAD" => {
+            let object = actions::get_object(&db, &user.id, &list, &name).await?;
+
+            Ok((
+                [
+                    (
+                        header::CONTENT_TYPE,
+                        HeaderValue::from_static("text/calendar; charset=utf-8"),
+                    ),
+                    etag(&object),
+                ],
+                object.render(),
+            )
+                .into_response())
+        }
+        "PUT" => {
+            let (object, created) = actions::put_object(
+                &db,
+                events.as_ref(),
+                &user.id,
+                &list,
+                &name,
+                &body,
+                &preconditions(&headers),
+            )
+            .await?;
+
+            let status = match created {
+                true => StatusCode::CREATED,
+                false => StatusCode::NO_CONTENT,
+            };
+
+            Ok((status, [etag(&object)]).into_response())
+        }
+        "DELETE" => {
+            actions::delete_object(
+                &db,
+                events.as_ref(),
+                &user.id,
+                &list,
+                &name,
+                &preconditions(&headers),
+            )
+            .await?;
+
+            Ok(StatusCode::NO_CONTENT.into_response())
+        }
+        "PROPFIND" => {
+            let props = requested_props(&body);
+
+            let object = actions::get_object(&db, &user.id, &list, &name).await?;
+
+            Ok(multistatus(&[response(
+                &user,
+                object_href(&user, &list, &object.name),
+                &Resource::Object(&object),
+                &props,
+            )]))
+        }
+        _ => Err(HandlerError::MethodNotAllowed),
+    }
+}
+
+pub async fn create_router() -> anyhow::Result<Router> {
+    Ok(Router::new()
+        .route("/.well-known/caldav", get(well_known))
+        .route("/caldav", any(root))
+        .route("/caldav/", any(root))
+        .route("/caldav/:user_id", any(home))
+        .route("/caldav/:user_id/", any(home))
+        .route("/caldav/:user_id/:list", any(collection))
+        .route("/caldav/:user_id/:list/", any(collection))
+        .route("/caldav/:user_id/:list/:name", any(object)))
+}
@@ -1,8 +1,12 @@
 use async_trait::async_trait;
 use axum::{
     extract::FromRequestParts,
-    headers::{authorization::Bearer, Authorization},
-    http::StatusCode,
+    headers::{
+        authorization::{Basic, Bearer},
+        Authorization,
+    },
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Extension, TypedHeader,
 };
 use chrono::{DateTime, Utc};
@@ -10,7 +14,12 @@ use sea_orm::DatabaseConnection;
 use serde::Serialize;
 use uuid::Uuid;
 
-use crate::auth::{actions, security};
+use crate::{
+    app_password,
+    auth::{actions, security},
+    event::ticket,
+    http::error::ErrorResponse,
+};
 
 #[derive(Debug, Serialize)]
 pub struct AuthUser {
@@ -24,7 +33,7 @@ impl<S> FromRequestParts<S> for AuthUser
 where
     S: Send + Sync,
 {
-    type Rejection = (StatusCode, &'static str);
+    type Rejection = ErrorResponse;
 
     async fn from_request_parts(
         parts: &mut axum::http::request::Parts,
@@ -36,37 +45,153 @@ where
                 .map_err(|err| {
                     tracing::error!("{err}");
 
-                    (StatusCode::BAD_REQUEST, "Invalid token")
+                    ErrorResponse::new(StatusCode::BAD_REQUEST, "invalid_token", "Invalid token")
                 })?;
 
-        let user_id = security::verify_access_token(token.token().to_owned())
+        let Extension(db) = Extension::<DatabaseConnection>::from_request_parts(parts, state)
             .await
             .map_err(|err| {
                 tracing::error!("{err}");
 
-                (StatusCode::UNAUTHORIZED, "Invalid or expired token")
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal",
+                    "Internal server error",
+                )
             })?;
 
-        let Extension(db) = Extension::<DatabaseConnection>::from_request_parts(parts, state)
+        AuthUser::from_token(&db, token.token()).await
+    }
+}
+
+impl AuthUser {
+    /// Resolves the user behind an access token.
+    pub async fn from_token(db: &DatabaseConnection, token: &str) -> Result<Self, ErrorResponse> {
+        let user_id = security::verify_access_token(token.to_owned())
             .await
             .map_err(|err| {
                 tracing::error!("{err}");
 
-                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+                ErrorResponse::new(
+                    StatusCode::UNAUTHORIZED,
+                    "invalid_token",
+                    "Invalid or expired token",
+                )
+            })?;
+
+        Self::from_id(db, &user_id).await
+    }
+
+    /// Redeems a single-use ticket from `POST /events/tickets`, for downloads
+    /// opened by the browser that can't send an `Authorization` header.
+    pub async fn from_ticket(db: &DatabaseConnection, ticket: &str) -> Result<Self, ErrorResponse> {
+        let user_id = ticket::redeem_ticket(db, ticket)
+            .await
+            .map_err(|err| match err {
+                ticket::ActionError::NotFound => ErrorResponse::new(
+                    StatusCode::UNAUTHORIZED,
+                    "invalid_ticket",
+                    "Invalid or expired ticket",
+                ),
+                err => {
+                    tracing::error!("{err}");
+
+                    ErrorResponse::new(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "internal",
+                        "Internal server error",
+                    )
+                }
             })?;
 
-        let user = actions::get_user_by_id(&db, &user_id)
+        Self::from_id(db, &user_id).await
+    }
+
+    async fn from_id(db: &DatabaseConnection, user_id: &Uuid) -> Result<Self, ErrorResponse> {
+        let user = actions::get_user_by_id(db, user_id)
+            .await
+            .map_err(|err| match err {
+                actions::ActionError::NotFound => {
+                    ErrorResponse::new(StatusCode::UNAUTHORIZED, "invalid_token", "Invalid token")
+                }
+                err => {
+                    tracing::error!("{err}");
+
+                    ErrorResponse::new(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "internal",
+                        "Internal server error",
+                    )
+                }
+            })?;
+
+        Ok(Self {
+            id: user.id,
+            username: user.username,
+            joined_at: user.joined_at,
+        })
+    }
+}
+
+/// User signed in with an app password over Basic auth, for clients such as
+/// CalDAV that can't obtain tokens.
+#[derive(Debug)]
+pub struct AppPasswordUser {
+    pub id: Uuid,
+    pub username: String,
+}
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Basic realm=\"Todos\"")],
+        "Invalid credentials",
+    )
+        .into_response()
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AppPasswordUser
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let TypedHeader(Authorization(credentials)) =
+            TypedHeader::<Authorization<Basic>>::from_request_parts(parts, state)
+                .await
+                .map_err(|_| unauthorized())?;
+
+        let Extension(db) = Extension::<DatabaseConnection>::from_request_parts(parts, state)
             .await
             .map_err(|err| {
                 tracing::error!("{err}");
 
-                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
             })?;
 
+        let user = app_password::actions::verify_app_password(
+            &db,
+            credentials.username(),
+            credentials.password(),
+        )
+        .await
+        .map_err(|err| match err {
+            app_password::actions::ActionError::InvalidCredentials => unauthorized(),
+            err => {
+                tracing::error!("{err}");
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error").into_response()
+            }
+        })?;
+
         Ok(Self {
             id: user.id,
             username: user.username,
-            joined_at: user.joined_at,
         })
     }
 }
@@ -0,0 +1,233 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::formats::{self, CsvMapping, ImportError, ImportedTodo, Parsed};
+use crate::{
+    entities::{project, todo, user},
+    event::{EventBus, TodoEvent, TodoEventKind},
+    workflow,
+};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Invalid import")]
+    Invalid(Vec<ImportError>),
+    #[error("Not Found")]
+    NotFound,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    Csv,
+    TodoTxt,
+    Todoist,
+    Trello,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportPreview {
+    pub headers: Vec<String>,
+    pub todos: Vec<ImportedTodo>,
+    pub errors: Vec<ImportError>,
+    /// Projects the import would create.
+    pub new_projects: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Imported {
+    pub todos: Vec<todo::Model>,
+    pub projects: Vec<project::Model>,
+}
+
+fn parse(format: ImportFormat, content: &str, mapping: &CsvMapping) -> Parsed {
+    match format {
+        ImportFormat::Csv => formats::parse_csv(content, mapping),
+        ImportFormat::TodoTxt => formats::parse_todo_txt(content),
+        ImportFormat::Todoist => formats::parse_todoist(content),
+        ImportFormat::Trello => formats::parse_trello(content),
+    }
+}
+
+async fn get_projects(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<HashMap<String, Uuid>, ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    Ok(project::Entity::find()
+        .filter(project::Column::UserId.eq(*user_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|project| (project.name, project.id))
+        .collect())
+}
+
+fn new_projects(todos: &[ImportedTodo], projects: &HashMap<String, Uuid>) -> Vec<String> {
+    let mut seen = HashSet::new();
+
+    todos
+        .iter()
+        .filter_map(|todo| todo.project.clone())
+        .filter(|name| !projects.contains_key(name) && seen.insert(name.clone()))
+        .collect()
+}
+
+/// Nesting level of each todo, todos are inserted parents first. Parents that
+/// form a cycle are dropped.
+fn depths(todos: &mut [ImportedTodo]) -> Vec<usize> {
+    let mut depths = Vec::with_capacity(todos.len());
+
+    for index in 0..todos.len() {
+        let mut depth = 0;
+        let mut current = index;
+
+        while let Some(parent) = todos[current].parent {
+            depth += 1;
+            current = parent;
+
+            if depth > todos.len() || parent >= todos.len() {
+                todos[index].parent = None;
+                depth = 0;
+
+                break;
+            }
+        }
+
+        depths.push(depth);
+    }
+
+    depths
+}
+
+/// Parses the file without storing anything, for the dry run.
+pub async fn preview_import(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    format: ImportFormat,
+    content: &str,
+    mapping: &CsvMapping,
+) -> Result<ImportPreview, ActionError> {
+    let projects = get_projects(db, user_id).await?;
+
+    let parsed = parse(format, content, mapping);
+
+    Ok(ImportPreview {
+        new_projects: new_projects(&parsed.todos, &projects),
+        headers: parsed.headers,
+        todos: parsed.todos,
+        errors: parsed.errors,
+    })
+}
+
+/// Stores every todo of the file in one transaction, nothing is stored if
+/// any line fails to parse.
+pub async fn import(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    user_id: &Uuid,
+    format: ImportFormat,
+    content: &str,
+    mapping: &CsvMapping,
+) -> Result<Imported, ActionError> {
+    let mut projects = get_projects(db, user_id).await?;
+
+    let Parsed {
+        mut todos, errors, ..
+    } = parse(format, content, mapping);
+
+    if !errors.is_empty() {
+        return Err(ActionError::Invalid(errors));
+    }
+
+    let now = Utc::now();
+
+    let txn = db.begin().await?;
+
+    let mut new_projects = Vec::new();
+
+    for name in self::new_projects(&todos, &projects) {
+        let project = project::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(*user_id),
+            name: Set(name.clone()),
+            created_at: Set(now),
+        }
+        .insert(&txn)
+        .await?;
+
+        workflow::actions::create_default_statuses(&txn, &project.id).await?;
+
+        projects.insert(name, project.id);
+
+        new_projects.push(project);
+    }
+
+    let ids: Vec<Uuid> = todos.iter().map(|_| Uuid::new_v4()).collect();
+
+    let depths = depths(&mut todos);
+
+    let mut order: Vec<usize> = (0..todos.len()).collect();
+
+    order.sort_by_key(|index| depths[*index]);
+
+    let mut models = Vec::with_capacity(todos.len());
+
+    for index in order {
+        let todo = &todos[index];
+
+        let completed_at = match todo.is_completed {
+            true => Some(todo.completed_at.unwrap_or(now)),
+            false => None,
+        };
+
+        let model = todo::ActiveModel {
+            id: Set(ids[index]),
+            user_id: Set(*user_id),
+            project_id: Set(todo
+                .project
+                .as_ref()
+                .and_then(|name| projects.get(name).copied())),
+            name: Set(todo.name.clone()),
+            is_completed: Set(todo.is_completed),
+            estimate_minutes: Set(None),
+            created_at: Set(todo.created_at.unwrap_or(now)),
+            updated_at: Set(now),
+            completed_at: Set(completed_at),
+            due_at: Set(todo.due_at),
+            parent_id: Set(todo.parent.map(|parent| ids[parent])),
+            status_id: Set(None),
+        }
+        .insert(&txn)
+        .await?;
+
+        models.push(model);
+    }
+
+    txn.commit().await?;
+
+    for todo in &models {
+        events
+            .publish(TodoEvent::new(TodoEventKind::Created, todo))
+            .await;
+    }
+
+    Ok(Imported {
+        todos: models,
+        projects: new_projects,
+    })
+}
@@ -0,0 +1,784 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Shortest name accepted, the same as the name of a new todo.
+const MIN_NAME_LENGTH: u64 = 5;
+
+/// Todo read from an import file, before it is stored.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ImportedTodo {
+    pub name: String,
+    /// Name of the project, created if the user has none with this name.
+    pub project: Option<String>,
+    pub is_completed: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+    /// Index of the parent todo in the same import.
+    pub parent: Option<usize>,
+    /// todo.txt priority, shown in the preview but not stored.
+    pub priority: Option<char>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportError {
+    /// Line of the file, or position of the item for JSON imports.
+    pub line: usize,
+    /// Stable code for clients to translate by, `message` is English.
+    pub code: &'static str,
+    pub message: String,
+    /// The value that couldn't be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Parsed {
+    /// Header row of CSV files, to pick a column mapping from.
+    pub headers: Vec<String>,
+    pub todos: Vec<ImportedTodo>,
+    pub errors: Vec<ImportError>,
+}
+
+impl Parsed {
+    fn error(&mut self, line: usize, code: &'static str, message: impl Into<String>) {
+        self.errors.push(ImportError {
+            line,
+            code,
+            message: message.into(),
+            value: None,
+        });
+    }
+
+    fn invalid_value(&mut self, line: usize, code: &'static str, message: &str, value: &str) {
+        self.errors.push(ImportError {
+            line,
+            code,
+            message: format!("{message} \"{value}\""),
+            value: Some(value.to_owned()),
+        });
+    }
+
+    /// Reports a name a new todo couldn't have, returns whether it's valid.
+    fn check_name(&mut self, line: usize, name: &str) -> bool {
+        if name.is_empty() {
+            self.error(line, "missing_name", "Missing name");
+
+            return false;
+        }
+
+        if !validator::validate_length(name, Some(MIN_NAME_LENGTH), None, None) {
+            self.invalid_value(line, "too_short", "Too short name", name);
+
+            return false;
+        }
+
+        true
+    }
+}
+
+/// CSV columns of each field, unmapped fields are read from the column
+/// with the field name if there is one.
+#[derive(Debug, Default, Clone)]
+pub struct CsvMapping {
+    pub name: Option<String>,
+    pub project: Option<String>,
+    pub is_completed: Option<String>,
+    pub created_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub due_at: Option<String>,
+}
+
+/// Accepts RFC 3339, `YYYY-MM-DD HH:MM[:SS]` read as UTC and `YYYY-MM-DD`.
+pub fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+
+    if let Ok(value) = DateTime::parse_from_rfc3339(value) {
+        return Some(value.with_timezone(&Utc));
+    }
+
+    for format in [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(value) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(Utc.from_utc_datetime(&value));
+        }
+    }
+
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|value| value.and_hms_opt(0, 0, 0))
+        .map(|value| Utc.from_utc_datetime(&value))
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "" | "false" | "no" | "0" | "n" => Some(false),
+        "true" | "yes" | "1" | "y" | "x" | "done" | "completed" => Some(true),
+        _ => None,
+    }
+}
+
+pub fn parse_csv(content: &str, mapping: &CsvMapping) -> Parsed {
+    let mut parsed = Parsed::default();
+
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    parsed.headers = match reader.headers() {
+        Ok(headers) => headers.iter().map(|header| header.to_owned()).collect(),
+        Err(err) => {
+            parsed.error(1, "invalid_file", err.to_string());
+
+            return parsed;
+        }
+    };
+
+    let column = |mapped: &Option<String>, field: &str| {
+        let header = mapped.as_deref().unwrap_or(field);
+
+        parsed
+            .headers
+            .iter()
+            .position(|column| column.eq_ignore_ascii_case(header))
+    };
+
+    let name = column(&mapping.name, "name");
+    let project = column(&mapping.project, "project");
+    let is_completed = column(&mapping.is_completed, "is_completed");
+    let created_at = column(&mapping.created_at, "created_at");
+    let completed_at = column(&mapping.completed_at, "completed_at");
+    let due_at = column(&mapping.due_at, "due_at");
+
+    let Some(name) = name else {
+        parsed.error(1, "missing_name_column", "Missing name column");
+
+        return parsed;
+    };
+
+    for (index, record) in reader.records().enumerate() {
+        let line = index + 2;
+
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                parsed.error(line, "invalid_line", err.to_string());
+
+                continue;
+            }
+        };
+
+        let value = |column: Option<usize>| {
+            column
+                .and_then(|column| record.get(column))
+                .filter(|value| !value.is_empty())
+        };
+
+        let mut todo = ImportedTodo {
+            name: value(Some(name)).unwrap_or_default().to_owned(),
+            project: value(project).map(|value| value.to_owned()),
+            ..Default::default()
+        };
+
+        if !parsed.check_name(line, &todo.name) {
+            continue;
+        }
+
+        if let Some(value) = value(is_completed) {
+            match parse_bool(value) {
+                Some(value) => todo.is_completed = value,
+                None => {
+                    parsed.invalid_value(line, "invalid_completion", "Invalid completion", value)
+                }
+            }
+        }
+
+        for (column, field) in [
+            (created_at, &mut todo.created_at),
+            (completed_at, &mut todo.completed_at),
+            (due_at, &mut todo.due_at),
+        ] {
+            if let Some(value) = value(column) {
+                match parse_date(value) {
+                    Some(value) => *field = Some(value),
+                    None => parsed.invalid_value(line, "invalid_date", "Invalid date", value),
+                }
+            }
+        }
+
+        parsed.todos.push(todo);
+    }
+
+    parsed
+}
+
+/// Reads todo.txt lines: `x`, `(A)`, completion and creation dates, `+project`
+/// and `due:YYYY-MM-DD`. Contexts and other tags are kept in the name.
+pub fn parse_todo_txt(content: &str) -> Parsed {
+    let mut parsed = Parsed::default();
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+
+        let mut tokens = line.split_whitespace().peekable();
+
+        if tokens.peek().is_none() {
+            continue;
+        }
+
+        let mut todo = ImportedTodo::default();
+
+        if tokens.peek() == Some(&"x") {
+            todo.is_completed = true;
+
+            tokens.next();
+        }
+
+        if let Some(token) = tokens.peek() {
+            if let [b'(', priority @ b'A'..=b'Z', b')'] = token.as_bytes() {
+                todo.priority = Some(*priority as char);
+
+                tokens.next();
+            }
+        }
+
+        let mut dates = Vec::new();
+
+        while dates.len() < 2 {
+            match tokens.peek().and_then(|token| parse_todo_txt_date(token)) {
+                Some(date) => {
+                    dates.push(date);
+
+                    tokens.next();
+                }
+                None => break,
+            }
+        }
+
+        match (todo.is_completed, dates.as_slice()) {
+            (true, [completed_at, created_at]) => {
+                todo.completed_at = Some(*completed_at);
+                todo.created_at = Some(*created_at);
+            }
+            (true, [completed_at]) => todo.completed_at = Some(*completed_at),
+            (false, [created_at, ..]) => todo.created_at = Some(*created_at),
+            _ => {}
+        }
+
+        let mut words = Vec::new();
+
+        for token in tokens {
+            if let Some(project) = token.strip_prefix('+').filter(|value| !value.is_empty()) {
+                if todo.project.is_none() {
+                    todo.project = Some(project.to_owned());
+
+                    continue;
+                }
+            } else if let Some(due_at) = token.strip_prefix("due:") {
+                match parse_todo_txt_date(due_at) {
+                    Some(due_at) => todo.due_at = Some(due_at),
+                    None => {
+                        parsed.invalid_value(line_number, "invalid_date", "Invalid date", due_at)
+                    }
+                }
+
+                continue;
+            } else if let Some(priority) = token.strip_prefix("pri:") {
+                if let [priority @ b'A'..=b'Z'] = priority.as_bytes() {
+                    todo.priority = Some(*priority as char);
+
+                    continue;
+                }
+            }
+
+            words.push(token);
+        }
+
+        todo.name = words.join(" ");
+
+        if !parsed.check_name(line_number, &todo.name) {
+            continue;
+        }
+
+        parsed.todos.push(todo);
+    }
+
+    parsed
+}
+
+fn parse_todo_txt_date(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|value| value.and_hms_opt(0, 0, 0))
+        .map(|value| Utc.from_utc_datetime(&value))
+}
+
+/// Ids are strings in current exports and numbers in older ones.
+fn id_key(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(value) => Some(value.to_owned()),
+        value => Some(value.to_string()),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Bool(value) => *value,
+        Value::Number(value) => value.as_i64().unwrap_or(0) != 0,
+        _ => false,
+    }
+}
+
+#[derive(Deserialize)]
+struct TodoistDue {
+    date: Option<String>,
+    datetime: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TodoistTask {
+    #[serde(default)]
+    id: Value,
+    content: String,
+    #[serde(default)]
+    project_id: Value,
+    #[serde(default)]
+    parent_id: Value,
+    #[serde(default)]
+    checked: Value,
+    #[serde(default)]
+    is_completed: Value,
+    due: Option<TodoistDue>,
+    #[serde(alias = "added_at", alias = "date_added")]
+    created_at: Option<String>,
+    #[serde(alias = "date_completed")]
+    completed_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TodoistProject {
+    id: Value,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TodoistExport {
+    Tasks(Vec<TodoistTask>),
+    Backup {
+        #[serde(default)]
+        projects: Vec<TodoistProject>,
+        #[serde(alias = "tasks")]
+        items: Vec<TodoistTask>,
+    },
+}
+
+/// Reads a list of tasks from the REST API, or a Sync API dump with
+/// `projects` and `items`.
+pub fn parse_todoist(content: &str) -> Parsed {
+    let mut parsed = Parsed::default();
+
+    let (projects, tasks) = match serde_json::from_str(content) {
+        Ok(TodoistExport::Tasks(tasks)) => (Vec::new(), tasks),
+        Ok(TodoistExport::Backup { projects, items }) => (projects, items),
+        Err(err) => {
+            parsed.error(err.line(), "invalid_file", err.to_string());
+
+            return parsed;
+        }
+    };
+
+    let projects: HashMap<String, String> = projects
+        .into_iter()
+        .filter_map(|project| Some((id_key(&project.id)?, project.name)))
+        .collect();
+
+    let positions: HashMap<String, usize> = tasks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, task)| Some((id_key(&task.id)?, index)))
+        .collect();
+
+    for (index, task) in tasks.iter().enumerate() {
+        let due_at = task
+            .due
+            .as_ref()
+            .and_then(|due| due.datetime.as_ref().or(due.date.as_ref()));
+
+        let todo = ImportedTodo {
+            name: task.content.trim().to_owned(),
+            project: id_key(&task.project_id).and_then(|id| projects.get(&id).cloned()),
+            is_completed: is_truthy(&task.checked) || is_truthy(&task.is_completed),
+            created_at: task.created_at.as_deref().and_then(parse_date),
+            completed_at: task.completed_at.as_deref().and_then(parse_date),
+            due_at: due_at.and_then(|value| parse_date(value)),
+            parent: id_key(&task.parent_id).and_then(|id| positions.get(&id).copied()),
+            priority: None,
+        };
+
+        parsed.check_name(index + 1, &todo.name);
+
+        parsed.todos.push(todo);
+    }
+
+    parsed
+}
+
+#[derive(Deserialize)]
+struct TrelloList {
+    id: String,
+    name: String,
+    #[serde(default)]
+    closed: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrelloCard {
+    id: String,
+    name: String,
+    id_list: String,
+    #[serde(default)]
+    closed: bool,
+    due: Option<String>,
+    #[serde(default)]
+    due_complete: bool,
+}
+
+#[derive(Deserialize)]
+struct TrelloCheckItem {
+    name: String,
+    state: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrelloChecklist {
+    id_card: String,
+    #[serde(default)]
+    check_items: Vec<TrelloCheckItem>,
+}
+
+#[derive(Deserialize)]
+struct TrelloBoard {
+    #[serde(default)]
+    lists: Vec<TrelloList>,
+    #[serde(default)]
+    cards: Vec<TrelloCard>,
+    #[serde(default)]
+    checklists: Vec<TrelloChecklist>,
+}
+
+/// Reads a board export, lists become projects and checklist items subtasks
+/// of their card. Archived cards and lists are skipped.
+pub fn parse_trello(content: &str) -> Parsed {
+    let mut parsed = Parsed::default();
+
+    let board: TrelloBoard = match serde_json::from_str(content) {
+        Ok(board) => board,
+        Err(err) => {
+            parsed.error(err.line(), "invalid_file", err.to_string());
+
+            return parsed;
+        }
+    };
+
+    let lists: HashMap<&str, &TrelloList> = board
+        .lists
+        .iter()
+        .map(|list| (list.id.as_str(), list))
+        .collect();
+
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+
+    for card in &board.cards {
+        let list = lists.get(card.id_list.as_str());
+
+        if card.closed || list.map(|list| list.closed).unwrap_or(false) {
+            continue;
+        }
+
+        positions.insert(card.id.as_str(), parsed.todos.len());
+
+        parsed.check_name(parsed.todos.len() + 1, card.name.trim());
+
+        parsed.todos.push(ImportedTodo {
+            name: card.name.trim().to_owned(),
+            project: list.map(|list| list.name.clone()),
+            is_completed: card.due_complete,
+            due_at: card.due.as_deref().and_then(parse_date),
+            ..Default::default()
+        });
+    }
+
+    for checklist in &board.checklists {
+        let Some(parent) = positions.get(checklist.id_card.as_str()).copied() else {
+            continue;
+        };
+
+        let project = parsed.todos[parent].project.clone();
+
+        for item in &checklist.check_items {
+            parsed.check_name(parsed.todos.len() + 1, item.name.trim());
+
+            parsed.todos.push(ImportedTodo {
+                name: item.name.trim().to_owned(),
+                project: project.clone(),
+                is_completed: item.state == "complete",
+                parent: Some(parent),
+                ..Default::default()
+            });
+        }
+    }
+
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> Option<DateTime<Utc>> {
+        Some(
+            DateTime::parse_from_rfc3339(value)
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    fn codes(parsed: &Parsed) -> Vec<(usize, &str)> {
+        parsed
+            .errors
+            .iter()
+            .map(|error| (error.line, error.code))
+            .collect()
+    }
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(
+            parse_date("2023-04-05T06:07:08+02:00"),
+            date("2023-04-05T04:07:08Z")
+        );
+        assert_eq!(parse_date("2023-04-05 06:07"), date("2023-04-05T06:07:00Z"));
+        assert_eq!(
+            parse_date(" 2023-04-05T06:07:08.5 "),
+            date("2023-04-05T06:07:08.5Z")
+        );
+        assert_eq!(parse_date("2023-04-05"), date("2023-04-05T00:00:00Z"));
+        assert_eq!(parse_date("05/04/2023"), None);
+    }
+
+    #[test]
+    fn reads_csv_with_default_columns() {
+        let parsed = parse_csv(
+            "Name,Project,Is_Completed,Due_At\n\
+             Buy milk,Home,yes,2023-04-05\n\
+             Call mom,,no,\n",
+            &CsvMapping::default(),
+        );
+
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(
+            parsed.headers,
+            ["Name", "Project", "Is_Completed", "Due_At"]
+        );
+        assert_eq!(parsed.todos.len(), 2);
+
+        assert_eq!(parsed.todos[0].name, "Buy milk");
+        assert_eq!(parsed.todos[0].project.as_deref(), Some("Home"));
+        assert!(parsed.todos[0].is_completed);
+        assert_eq!(parsed.todos[0].due_at, date("2023-04-05T00:00:00Z"));
+
+        assert_eq!(parsed.todos[1].project, None);
+        assert!(!parsed.todos[1].is_completed);
+        assert_eq!(parsed.todos[1].due_at, None);
+    }
+
+    #[test]
+    fn reads_csv_with_mapped_columns() {
+        let parsed = parse_csv(
+            "Task,Done\n\"Write, then send the report\",x\n",
+            &CsvMapping {
+                name: Some("task".to_owned()),
+                is_completed: Some("done".to_owned()),
+                ..Default::default()
+            },
+        );
+
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(parsed.todos[0].name, "Write, then send the report");
+        assert!(parsed.todos[0].is_completed);
+    }
+
+    #[test]
+    fn reports_csv_errors_by_line() {
+        let parsed = parse_csv(
+            "name,is_completed,due_at\n\
+             Buy milk,maybe,\n\
+             ,no,\n\
+             Milk,no,\n\
+             Call mom,no,tomorrow\n",
+            &CsvMapping::default(),
+        );
+
+        assert_eq!(
+            codes(&parsed),
+            [
+                (2, "invalid_completion"),
+                (3, "missing_name"),
+                (4, "too_short"),
+                (5, "invalid_date"),
+            ]
+        );
+        assert_eq!(parsed.errors[2].value.as_deref(), Some("Milk"));
+        assert_eq!(parsed.todos.len(), 2);
+    }
+
+    #[test]
+    fn requires_a_csv_name_column() {
+        let parsed = parse_csv("title\nBuy milk\n", &CsvMapping::default());
+
+        assert_eq!(codes(&parsed), [(1, "missing_name_column")]);
+        assert!(parsed.todos.is_empty());
+    }
+
+    #[test]
+    fn reads_todo_txt() {
+        let parsed = parse_todo_txt(
+            "x (A) 2023-04-06 2023-04-01 Buy milk +Home @store due:2023-04-10\n\
+             \n\
+             2023-04-02 Call mom +Family +Home pri:B\n",
+        );
+
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(parsed.todos.len(), 2);
+
+        let done = &parsed.todos[0];
+
+        assert!(done.is_completed);
+        assert_eq!(done.priority, Some('A'));
+        assert_eq!(done.completed_at, date("2023-04-06T00:00:00Z"));
+        assert_eq!(done.created_at, date("2023-04-01T00:00:00Z"));
+        assert_eq!(done.due_at, date("2023-04-10T00:00:00Z"));
+        assert_eq!(done.project.as_deref(), Some("Home"));
+        assert_eq!(done.name, "Buy milk @store");
+
+        let open = &parsed.todos[1];
+
+        assert!(!open.is_completed);
+        assert_eq!(open.created_at, date("2023-04-02T00:00:00Z"));
+        assert_eq!(open.completed_at, None);
+        assert_eq!(open.priority, Some('B'));
+        assert_eq!(open.project.as_deref(), Some("Family"));
+        assert_eq!(open.name, "Call mom +Home");
+    }
+
+    #[test]
+    fn reports_todo_txt_errors_by_line() {
+        let parsed = parse_todo_txt("Buy milk due:soon\nx 2023-04-06 +Home\n(A) Milk\n");
+
+        assert_eq!(
+            codes(&parsed),
+            [(1, "invalid_date"), (2, "missing_name"), (3, "too_short")]
+        );
+        assert_eq!(parsed.todos.len(), 1);
+    }
+
+    #[test]
+    fn reads_todoist_tasks() {
+        let parsed = parse_todoist(
+            r#"[
+                {"id": "1", "content": "Plan the trip", "due": {"date": "2023-04-05"}},
+                {"id": "2", "content": " Book flights ", "parent_id": "1", "is_completed": true,
+                 "completed_at": "2023-04-02T10:00:00Z"}
+            ]"#,
+        );
+
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(parsed.todos[0].due_at, date("2023-04-05T00:00:00Z"));
+        assert_eq!(parsed.todos[1].name, "Book flights");
+        assert_eq!(parsed.todos[1].parent, Some(0));
+        assert!(parsed.todos[1].is_completed);
+        assert_eq!(parsed.todos[1].completed_at, date("2023-04-02T10:00:00Z"));
+    }
+
+    #[test]
+    fn reads_todoist_backups() {
+        let parsed = parse_todoist(
+            r#"{
+                "projects": [{"id": 7, "name": "Work"}],
+                "items": [
+                    {"id": 1, "content": "Write the report", "project_id": 7, "checked": 1,
+                     "date_added": "2023-04-01T08:00:00Z",
+                     "due": {"date": "2023-04-05", "datetime": "2023-04-05T17:00:00Z"}},
+                    {"id": 2, "content": "Milk"}
+                ]
+            }"#,
+        );
+
+        assert_eq!(codes(&parsed), [(2, "too_short")]);
+        assert_eq!(parsed.todos[0].project.as_deref(), Some("Work"));
+        assert!(parsed.todos[0].is_completed);
+        assert_eq!(parsed.todos[0].created_at, date("2023-04-01T08:00:00Z"));
+        assert_eq!(parsed.todos[0].due_at, date("2023-04-05T17:00:00Z"));
+    }
+
+    #[test]
+    fn refuses_unreadable_json() {
+        assert_eq!(
+            codes(&parse_todoist("{\"items\": [")),
+            [(1, "invalid_file")]
+        );
+        assert_eq!(
+            codes(&parse_trello("{\"cards\": 1}")),
+            [(1, "invalid_file")]
+        );
+    }
+
+    #[test]
+    fn reads_trello_boards() {
+        let parsed = parse_trello(
+            r#"{
+                "lists": [
+                    {"id": "l1", "name": "Doing"},
+                    {"id": "l2", "name": "Old", "closed": true}
+                ],
+                "cards": [
+                    {"id": "c1", "name": "Move house", "idList": "l1", "due": "2023-04-05T12:00:00.000Z"},
+                    {"id": "c2", "name": "Archived card", "idList": "l1", "closed": true},
+                    {"id": "c3", "name": "In an archived list", "idList": "l2"},
+                    {"id": "c4", "name": "Sell the car", "idList": "l1", "dueComplete": true}
+                ],
+                "checklists": [
+                    {"idCard": "c1", "checkItems": [
+                        {"name": "Pack boxes", "state": "complete"},
+                        {"name": "Keys", "state": "incomplete"}
+                    ]},
+                    {"idCard": "c2", "checkItems": [{"name": "Never seen", "state": "complete"}]}
+                ]
+            }"#,
+        );
+
+        let names: Vec<_> = parsed.todos.iter().map(|todo| todo.name.as_str()).collect();
+
+        assert_eq!(names, ["Move house", "Sell the car", "Pack boxes", "Keys"]);
+        assert_eq!(codes(&parsed), [(4, "too_short")]);
+
+        assert_eq!(parsed.todos[0].project.as_deref(), Some("Doing"));
+        assert_eq!(parsed.todos[0].due_at, date("2023-04-05T12:00:00Z"));
+        assert!(parsed.todos[1].is_completed);
+        assert_eq!(parsed.todos[2].parent, Some(0));
+        assert_eq!(parsed.todos[2].project.as_deref(), Some("Doing"));
+        assert!(parsed.todos[2].is_completed);
+        assert!(!parsed.todos[3].is_completed);
+    }
+}
@@ -0,0 +1,312 @@
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    sea_query::{Expr, LockBehavior, LockType},
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, Order, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entities::job::{self, JobKind, JobStatus};
+
+/// Attempts made before a job is marked as failed.
+pub const MAX_ATTEMPTS: i32 = 5;
+
+/// Running jobs locked for longer than this are considered abandoned by a
+/// crashed instance and are picked up again.
+const LOCK_TIMEOUT_MINUTES: i64 = 5;
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+}
+
+pub async fn enqueue<C>(
+    db: &C,
+    kind: JobKind,
+    payload: Value,
+    run_at: DateTime<Utc>,
+) -> Result<job::Model, ActionError>
+where
+    C: ConnectionTrait,
+{
+    let now = Utc::now();
+
+    let new_job = job::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        kind: Set(kind),
+        payload: Set(payload),
+        status: Set(JobStatus::Pending),
+        run_at: Set(run_at),
+        attempts: Set(0),
+        max_attempts: Set(MAX_ATTEMPTS),
+        last_error: Set(None),
+        locked_at: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    Ok(new_job.insert(db).await?)
+}
+
+/// Locks up to `limit` due jobs and marks them as running.
+///
+/// Rows locked by other instances are skipped, so every job is claimed once.
+pub async fn claim_due(
+    db: &DatabaseConnection,
+    limit: u64,
+) -> Result<Vec<job::Model>, ActionError> {
+    let now = Utc::now();
+
+    let txn = db.begin().await?;
+
+    let mut stmt = job::Entity::find()
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(job::Column::Status.eq(JobStatus::Pending))
+                        .add(job::Column::RunAt.lte(now)),
+                )
+                .add(
+                    Condition::all()
+                        .add(job::Column::Status.eq(JobStatus::Running))
+                        .add(
+                            job::Column::LockedAt.lt(now - Duration::minutes(LOCK_TIMEOUT_MINUTES)),
+                        ),
+                ),
+        )
+        .order_by(job::Column::RunAt, Order::Asc)
+        .limit(limit);
+
+    QuerySelect::query(&mut stmt).lock_with_behavior(LockType::Update, LockBehavior::SkipLocked);
+
+    let jobs = stmt.all(&txn).await?;
+
+    if !jobs.is_empty() {
+        job::Entity::update_many()
+            .col_expr(job::Column::Status, Expr::value(JobStatus::Running))
+            .col_expr(
+                job::Column::Attempts,
+                Expr::col(job::Column::Attempts).add(1),
+            )
+            .col_expr(job::Column::LockedAt, Expr::value(now))
+            .col_expr(job::Column::UpdatedAt, Expr::value(now))
+            .filter(job::Column::Id.is_in(jobs.iter().map(|job| job.id)))
+            .exec(&txn)
+            .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(jobs
+        .into_iter()
+        .map(|job| job::Model {
+            status: JobStatus::Running,
+            attempts: job.attempts + 1,
+            locked_at: Some(now),
+            updated_at: now,
+            ..job
+        })
+        .collect())
+}
+
+pub async fn complete(db: &DatabaseConnection, job: job::Model) -> Result<(), ActionError> {
+    let mut job = job.into_active_model();
+
+    job.status = Set(JobStatus::Done);
+    job.last_error = Set(None);
+    job.locked_at = Set(None);
+    job.updated_at = Set(Utc::now());
+
+    job.update(db).await?;
+
+    Ok(())
+}
+
+/// Wait before the next attempt, doubling from a minute after the first.
+fn backoff(attempts: i32) -> Duration {
+    Duration::seconds(30 * 2_i64.pow(attempts.clamp(0, 10) as u32))
+}
+
+/// Records a failed attempt and schedules a retry with exponential backoff
+/// until the attempts are exhausted.
+pub async fn fail(
+    db: &DatabaseConnection,
+    job: job::Model,
+    error: &str,
+) -> Result<(), ActionError> {
+    let now = Utc::now();
+
+    let exhausted = job.attempts >= job.max_attempts;
+
+    let run_at = now + backoff(job.attempts);
+
+    let mut job = job.into_active_model();
+
+    if exhausted {
+        job.status = Set(JobStatus::Failed);
+    } else {
+        job.status = Set(JobStatus::Pending);
+        job.run_at = Set(run_at);
+    }
+
+    job.last_error = Set(Some(error.to_owned()));
+    job.locked_at = Set(None);
+    job.updated_at = Set(now);
+
+    job.update(db).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::database::testing;
+
+    #[test]
+    fn backoff_doubles_up_to_a_limit() {
+        let seconds: Vec<_> = (1..=5)
+            .map(|attempts| backoff(attempts).num_seconds())
+            .collect();
+
+        assert_eq!(seconds, [60, 120, 240, 480, 960]);
+        assert_eq!(backoff(0), Duration::seconds(30));
+        assert_eq!(backoff(50), backoff(10));
+        assert_eq!(backoff(-1), backoff(0));
+    }
+
+    async fn find(db: &DatabaseConnection, id: &Uuid) -> job::Model {
+        job::Entity::find_by_id(*id).one(db).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn due_jobs_are_claimed_once() {
+        let (db, _guard) = testing::db().await;
+
+        let due = enqueue(&db, JobKind::Reminder, json!({}), Utc::now())
+            .await
+            .unwrap();
+
+        let later = enqueue(
+            &db,
+            JobKind::Reminder,
+            json!({}),
+            Utc::now() + Duration::hours(1),
+        )
+        .await
+        .unwrap();
+
+        let (first, second) = tokio::join!(claim_due(&db, 10), claim_due(&db, 10));
+
+        let claimed: Vec<_> = [first.unwrap(), second.unwrap()].concat();
+
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, due.id);
+        assert_eq!(claimed[0].attempts, 1);
+
+        let stored = find(&db, &due.id).await;
+
+        assert_eq!(stored.status, JobStatus::Running);
+        assert_eq!(stored.attempts, 1);
+
+        assert_eq!(find(&db, &later.id).await.status, JobStatus::Pending);
+        assert!(claim_due(&db, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn abandoned_jobs_are_claimed_again() {
+        let (db, _guard) = testing::db().await;
+
+        let job = enqueue(&db, JobKind::Reminder, json!({}), Utc::now())
+            .await
+            .unwrap();
+
+        claim_due(&db, 10).await.unwrap();
+
+        let mut abandoned = find(&db, &job.id).await.into_active_model();
+
+        abandoned.locked_at = Set(Some(
+            Utc::now() - Duration::minutes(LOCK_TIMEOUT_MINUTES + 1),
+        ));
+
+        abandoned.update(&db).await.unwrap();
+
+        let claimed = claim_due(&db, 10).await.unwrap();
+
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].attempts, 2);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn failed_jobs_are_retried_until_exhausted() {
+        let (db, _guard) = testing::db().await;
+
+        let job = enqueue(&db, JobKind::Reminder, json!({}), Utc::now())
+            .await
+            .unwrap();
+
+        for attempts in 1..=MAX_ATTEMPTS {
+            let claimed = claim_due(&db, 10).await.unwrap().remove(0);
+
+            assert_eq!(claimed.attempts, attempts);
+
+            let before = Utc::now();
+
+            fail(&db, claimed, "Boom").await.unwrap();
+
+            let stored = find(&db, &job.id).await;
+
+            assert_eq!(stored.last_error.as_deref(), Some("Boom"));
+            assert_eq!(stored.locked_at, None);
+
+            if attempts == MAX_ATTEMPTS {
+                assert_eq!(stored.status, JobStatus::Failed);
+
+                break;
+            }
+
+            assert_eq!(stored.status, JobStatus::Pending);
+            assert!(stored.run_at >= before + backoff(attempts));
+            assert!(stored.run_at <= Utc::now() + backoff(attempts));
+
+            // Due right away instead of after the backoff.
+            let mut due = stored.into_active_model();
+
+            due.run_at = Set(Utc::now());
+
+            due.update(&db).await.unwrap();
+        }
+
+        assert!(claim_due(&db, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn completed_jobs_are_done() {
+        let (db, _guard) = testing::db().await;
+
+        let job = enqueue(&db, JobKind::Reminder, json!({}), Utc::now())
+            .await
+            .unwrap();
+
+        let claimed = claim_due(&db, 10).await.unwrap().remove(0);
+
+        complete(&db, claimed).await.unwrap();
+
+        let stored = find(&db, &job.id).await;
+
+        assert_eq!(stored.status, JobStatus::Done);
+        assert_eq!(stored.locked_at, None);
+        assert!(claim_due(&db, 10).await.unwrap().is_empty());
+    }
+}
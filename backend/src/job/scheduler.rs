@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use sea_orm::DatabaseConnection;
+use tokio::task::JoinHandle;
+
+use super::actions;
+use crate::{entities::job, reminder, webhook};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const BATCH_SIZE: u64 = 10;
+
+/// Starts the background task that polls the `job` table and runs due jobs.
+pub fn spawn(db: DatabaseConnection) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(err) = run_due(&db).await {
+                tracing::error!("Scheduler: {}", err);
+            }
+        }
+    })
+}
+
+/// Runs due jobs until none are left.
+pub(crate) async fn run_due(db: &DatabaseConnection) -> Result<(), actions::ActionError> {
+    loop {
+        let jobs = actions::claim_due(db, BATCH_SIZE).await?;
+
+        if jobs.is_empty() {
+            return Ok(());
+        }
+
+        for job in jobs {
+            match execute(db, &job).await {
+                Ok(()) => actions::complete(db, job).await?,
+                Err(err) => {
+                    tracing::warn!("Job {} ({:?}) failed: {}", job.id, job.kind, err);
+
+                    actions::fail(db, job, &err.to_string()).await?
+                }
+            }
+        }
+    }
+}
+
+async fn execute(db: &DatabaseConnection, job: &job::Model) -> anyhow::Result<()> {
+    match job.kind {
+        job::JobKind::Reminder => {
+            let payload = serde_json::from_value(job.payload.clone())?;
+
+            reminder::actions::fire_reminder(db, &payload).await?;
+        }
+        job::JobKind::WebhookDelivery => {
+            let payload = serde_json::from_value(job.payload.clone())?;
+
+            let is_last_attempt = job.attempts >= job.max_attempts;
+
+            webhook::actions::deliver(db, &payload, is_last_attempt).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,5 +1,23 @@
+pub mod app_password;
+pub mod archive;
 pub mod auth;
+pub mod caldav;
+pub mod calendar;
+pub mod custom_field;
 pub mod database;
 pub mod entities;
+pub mod event;
+pub mod export;
 pub mod http;
+pub mod import;
+pub mod job;
+pub mod notification;
+pub mod preference;
+pub mod project;
+pub mod reminder;
+pub mod sync;
+pub mod template;
+pub mod time_tracking;
 pub mod todo;
+pub mod webhook;
+pub mod workflow;
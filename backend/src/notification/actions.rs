@@ -0,0 +1,109 @@
+use chrono::Utc;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::entities::notification::{self, NotificationKind};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+}
+
+pub async fn create_notification<C>(
+    db: &C,
+    user_id: &Uuid,
+    todo_id: Option<Uuid>,
+    kind: NotificationKind,
+    message: &str,
+) -> Result<notification::Model, ActionError>
+where
+    C: ConnectionTrait,
+{
+    let new_notification = notification::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id.to_owned()),
+        todo_id: Set(todo_id),
+        kind: Set(kind),
+        message: Set(message.into()),
+        is_read: Set(false),
+        created_at: Set(Utc::now()),
+    };
+
+    Ok(new_notification.insert(db).await?)
+}
+
+pub async fn get_notifications_count(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    is_read: &Option<bool>,
+) -> Result<u64, ActionError> {
+    let mut stmt = notification::Entity::find().filter(notification::Column::UserId.eq(*user_id));
+
+    if let Some(is_read) = is_read {
+        stmt = stmt.filter(notification::Column::IsRead.eq(*is_read));
+    }
+
+    Ok(stmt.count(db).await?)
+}
+
+pub async fn get_notifications(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    is_read: &Option<bool>,
+    limit: &u64,
+    offset: &u64,
+) -> Result<Vec<notification::Model>, ActionError> {
+    let mut stmt = notification::Entity::find().filter(notification::Column::UserId.eq(*user_id));
+
+    if let Some(is_read) = is_read {
+        stmt = stmt.filter(notification::Column::IsRead.eq(*is_read));
+    }
+
+    Ok(stmt
+        .order_by(notification::Column::CreatedAt, Order::Desc)
+        .limit(*limit)
+        .offset(*offset)
+        .all(db)
+        .await?)
+}
+
+pub async fn mark_read(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<notification::Model, ActionError> {
+    let notification = notification::Entity::find_by_id(*id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if notification.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    let mut notification = notification.into_active_model();
+
+    notification.is_read = Set(true);
+
+    Ok(notification.update(db).await?)
+}
+
+pub async fn mark_all_read(db: &DatabaseConnection, user_id: &Uuid) -> Result<(), ActionError> {
+    notification::Entity::update_many()
+        .col_expr(notification::Column::IsRead, Expr::value(true))
+        .filter(notification::Column::UserId.eq(*user_id))
+        .filter(notification::Column::IsRead.eq(false))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
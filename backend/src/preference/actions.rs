@@ -0,0 +1,94 @@
+use chrono::Utc;
+use sea_orm::{sea_query::OnConflict, DatabaseConnection, DbErr, EntityTrait, Set};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    database,
+    entities::user_preferences::{self, DateFormat, DefaultView, Theme, WeekStart},
+};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Invalid Time Zone")]
+    InvalidTimeZone,
+}
+
+pub struct PreferencesChanges {
+    pub time_zone: Option<String>,
+    pub page_size: i32,
+    pub theme: Theme,
+    pub date_format: DateFormat,
+    pub default_view: DefaultView,
+    pub week_start: WeekStart,
+    pub locale: String,
+}
+
+/// Preferences of the user, the defaults until they are saved once.
+pub async fn get_preferences(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<user_preferences::Model, ActionError> {
+    let preferences = user_preferences::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .unwrap_or_else(|| user_preferences::Model {
+            user_id: *user_id,
+            time_zone: None,
+            page_size: 10,
+            theme: Theme::System,
+            date_format: DateFormat::Locale,
+            default_view: DefaultView::Active,
+            week_start: WeekStart::Monday,
+            locale: "en".to_owned(),
+            updated_at: Utc::now(),
+        });
+
+    Ok(preferences)
+}
+
+pub async fn update_preferences(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    changes: PreferencesChanges,
+) -> Result<user_preferences::Model, ActionError> {
+    if let Some(time_zone) = &changes.time_zone {
+        if !database::is_valid_time_zone(db, time_zone).await? {
+            return Err(ActionError::InvalidTimeZone);
+        }
+    }
+
+    let preferences = user_preferences::ActiveModel {
+        user_id: Set(*user_id),
+        time_zone: Set(changes.time_zone),
+        page_size: Set(changes.page_size),
+        theme: Set(changes.theme),
+        date_format: Set(changes.date_format),
+        default_view: Set(changes.default_view),
+        week_start: Set(changes.week_start),
+        locale: Set(changes.locale),
+        updated_at: Set(Utc::now()),
+    };
+
+    let on_conflict = OnConflict::column(user_preferences::Column::UserId)
+        .update_columns([
+            user_preferences::Column::TimeZone,
+            user_preferences::Column::PageSize,
+            user_preferences::Column::Theme,
+            user_preferences::Column::DateFormat,
+            user_preferences::Column::DefaultView,
+            user_preferences::Column::WeekStart,
+            user_preferences::Column::Locale,
+            user_preferences::Column::UpdatedAt,
+        ])
+        .to_owned();
+
+    user_preferences::Entity::insert(preferences)
+        .on_conflict(on_conflict)
+        .exec(db)
+        .await?;
+
+    get_preferences(db, user_id).await
+}
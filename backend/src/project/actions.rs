@@ -0,0 +1,101 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
+    ModelTrait, Order, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    entities::{project, user},
+    workflow,
+};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+}
+
+pub async fn get_projects(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<Vec<project::Model>, ActionError> {
+    Ok(project::Entity::find()
+        .filter(project::Column::UserId.eq(*user_id))
+        .order_by(project::Column::Name, Order::Asc)
+        .all(db)
+        .await?)
+}
+
+pub async fn create_project(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    name: &str,
+) -> Result<project::Model, ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let new_project = project::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id.to_owned()),
+        name: Set(name.into()),
+        created_at: Set(Utc::now()),
+    };
+
+    let txn = db.begin().await?;
+
+    let project = new_project.insert(&txn).await?;
+
+    workflow::actions::create_default_statuses(&txn, &project.id).await?;
+
+    txn.commit().await?;
+
+    Ok(project)
+}
+
+pub async fn get_project(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<project::Model, ActionError> {
+    let project = project::Entity::find_by_id(*id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if project.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    Ok(project)
+}
+
+pub async fn update_project(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+    name: &str,
+) -> Result<project::Model, ActionError> {
+    let mut project = get_project(db, user_id, id).await?.into_active_model();
+
+    project.name = Set(name.to_owned());
+
+    Ok(project.update(db).await?)
+}
+
+pub async fn delete_project(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<(), ActionError> {
+    get_project(db, user_id, id).await?.delete(db).await?;
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
+    ModelTrait, Order, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    entities::{job::JobKind, notification::NotificationKind, reminder, todo},
+    job, notification,
+};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Job: {0}")]
+    Job(#[from] job::actions::ActionError),
+    #[error("Notification: {0}")]
+    Notification(#[from] notification::actions::ActionError),
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+}
+
+/// Payload of the job that fires a reminder.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReminderJob {
+    pub reminder_id: Uuid,
+}
+
+async fn get_owned_todo(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    todo_id: &Uuid,
+) -> Result<todo::Model, ActionError> {
+    let todo = todo::Entity::find_by_id(*todo_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if todo.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    Ok(todo)
+}
+
+pub async fn get_reminders(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    todo_id: &Uuid,
+) -> Result<Vec<reminder::Model>, ActionError> {
+    let todo = get_owned_todo(db, user_id, todo_id).await?;
+
+    Ok(todo
+        .find_related(reminder::Entity)
+        .order_by(reminder::Column::RemindAt, Order::Asc)
+        .all(db)
+        .await?)
+}
+
+pub async fn create_reminder(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    todo_id: &Uuid,
+    remind_at: &DateTime<Utc>,
+) -> Result<reminder::Model, ActionError> {
+    let todo = get_owned_todo(db, user_id, todo_id).await?;
+
+    let new_reminder = reminder::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id.to_owned()),
+        todo_id: Set(todo.id),
+        remind_at: Set(*remind_at),
+        fired_at: Set(None),
+        created_at: Set(Utc::now()),
+    };
+
+    let txn = db.begin().await?;
+
+    let new_reminder = new_reminder.insert(&txn).await?;
+
+    let payload = ReminderJob {
+        reminder_id: new_reminder.id,
+    };
+
+    job::actions::enqueue(
+        &txn,
+        JobKind::Reminder,
+        serde_json::to_value(payload).expect("reminder payload is serializable"),
+        *remind_at,
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(new_reminder)
+}
+
+pub async fn delete_reminder(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<(), ActionError> {
+    let reminder = reminder::Entity::find_by_id(*id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if reminder.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    reminder.delete(db).await?;
+
+    Ok(())
+}
+
+/// Creates the notification for a due reminder.
+///
+/// Reminders that were deleted or already fired are skipped, which keeps
+/// retried jobs from notifying twice.
+pub async fn fire_reminder(
+    db: &DatabaseConnection,
+    payload: &ReminderJob,
+) -> Result<(), ActionError> {
+    let txn = db.begin().await?;
+
+    let Some((reminder, Some(todo))) = reminder::Entity::find_by_id(payload.reminder_id)
+        .filter(reminder::Column::FiredAt.is_null())
+        .find_also_related(todo::Entity)
+        .one(&txn)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    notification::actions::create_notification(
+        &txn,
+        &reminder.user_id,
+        Some(todo.id),
+        NotificationKind::Reminder,
+        &todo.name,
+    )
+    .await?;
+
+    let mut reminder = reminder.into_active_model();
+
+    reminder.fired_at = Set(Some(Utc::now()));
+
+    reminder.update(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
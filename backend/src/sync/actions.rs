@@ -0,0 +1,873 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    sea_query::{Expr, SimpleExpr},
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, DbErr,
+    EntityTrait, IntoActiveModel, ModelTrait, Order, QueryFilter, QueryOrder, Set, Statement,
+    TransactionTrait, Value as DbValue,
+};
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    entities::{custom_field_value, project, todo, todo_tombstone, user},
+    event::{EventBus, TodoEvent, TodoEventKind},
+};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Not Found")]
+    NotFound,
+}
+
+/// Todos written and deleted since a sync token, with the token to pass
+/// next time.
+#[derive(Debug, Clone)]
+pub struct Delta {
+    pub todos: Vec<todo::Model>,
+    pub deleted: Vec<todo_tombstone::Model>,
+    pub token: u64,
+}
+
+/// How an update made on an outdated todo is settled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// The later change wins as a whole, compared by `changed_at`.
+    #[default]
+    LastWriterWins,
+    /// Fields the server didn't change since `base` are taken, the others
+    /// are conflicts.
+    Merge,
+}
+
+/// Todo fields a client can set, `None` leaves a field alone.
+#[derive(Debug, Default, Clone)]
+pub struct TodoFields {
+    pub name: Option<String>,
+    pub project_id: Option<Option<Uuid>>,
+    pub estimate_minutes: Option<Option<i32>>,
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    pub is_completed: Option<bool>,
+}
+
+/// A change made on a client. `changed_at` is when it was made there.
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// `id` is chosen by the client, creating the same todo again is a no-op.
+    Create {
+        id: Uuid,
+        fields: TodoFields,
+    },
+    /// `base` holds the values the client saw before changing `fields`.
+    Update {
+        id: Uuid,
+        changed_at: DateTime<Utc>,
+        base: TodoFields,
+        fields: TodoFields,
+    },
+    Delete {
+        id: Uuid,
+        changed_at: DateTime<Utc>,
+    },
+}
+
+impl Change {
+    pub fn id(&self) -> Uuid {
+        match self {
+            Change::Create { id, .. } | Change::Update { id, .. } | Change::Delete { id, .. } => {
+                *id
+            }
+        }
+    }
+}
+
+/// What became of a change, conflicts name the fields kept from the server.
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Applied(todo::Model),
+    /// Only some fields were taken.
+    Merged(todo::Model, Vec<&'static str>),
+    /// Nothing was taken, the todo is the server version.
+    Conflict(todo::Model, Vec<&'static str>),
+    /// The todo is gone, deleted by this change or before it.
+    Deleted,
+    NotFound,
+    UnknownProject,
+}
+
+impl Outcome {
+    pub fn todo(&self) -> Option<&todo::Model> {
+        match self {
+            Outcome::Applied(todo) | Outcome::Merged(todo, _) | Outcome::Conflict(todo, _) => {
+                Some(todo)
+            }
+            Outcome::Deleted | Outcome::NotFound | Outcome::UnknownProject => None,
+        }
+    }
+}
+
+/// Oldest transaction still running, or the next one when none is. Everything
+/// written below it is committed and visible from now on.
+async fn current_token<C>(db: &C) -> Result<u64, DbErr>
+where
+    C: ConnectionTrait,
+{
+    let stmt = Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT pg_snapshot_xmin(pg_current_snapshot())::TEXT AS xmin".to_owned(),
+    );
+
+    let xmin = db
+        .query_one(stmt)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound("Snapshot".to_owned()))?
+        .try_get::<String>("", "xmin")?;
+
+    xmin.parse()
+        .map_err(|_| DbErr::Custom(format!("Invalid snapshot xmin: {xmin}")))
+}
+
+fn written_since(table: &str, since: u64) -> SimpleExpr {
+    Expr::cust_with_values(
+        &format!("{table}.sync_xid >= $1::TEXT::XID8"),
+        [DbValue::from(since.to_string())],
+    )
+}
+
+/// Changes since `since`, all todos without one. Changes made while this
+/// runs may be sent again next time, clients apply them by id.
+pub async fn get_delta(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    since: Option<u64>,
+) -> Result<Delta, ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    // Taken first, anything committed later is at or above it.
+    let token = current_token(db).await?;
+
+    let mut todos = todo::Entity::find().filter(todo::Column::UserId.eq(*user_id));
+
+    if let Some(since) = since {
+        todos = todos.filter(written_since("todo", since));
+    }
+
+    let todos = todos
+        .order_by(todo::Column::CreatedAt, Order::Asc)
+        .all(db)
+        .await?;
+
+    // Without a token the client starts over, earlier deletes don't matter.
+    let deleted = match since {
+        Some(since) => {
+            todo_tombstone::Entity::find()
+                .filter(todo_tombstone::Column::UserId.eq(*user_id))
+                .filter(written_since("todo_tombstone", since))
+                .order_by(todo_tombstone::Column::DeletedAt, Order::Asc)
+                .all(db)
+                .await?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(Delta {
+        todos,
+        deleted,
+        token,
+    })
+}
+
+/// Custom field values of the given todos, by todo and field.
+pub async fn get_field_values(
+    db: &DatabaseConnection,
+    todo_ids: &[Uuid],
+) -> Result<HashMap<Uuid, HashMap<Uuid, Value>>, ActionError> {
+    let mut values: HashMap<Uuid, HashMap<Uuid, Value>> = HashMap::new();
+
+    if todo_ids.is_empty() {
+        return Ok(values);
+    }
+
+    let field_values = custom_field_value::Entity::find()
+        .filter(custom_field_value::Column::TodoId.is_in(todo_ids.iter().copied()))
+        .all(db)
+        .await?;
+
+    for field_value in field_values {
+        values
+            .entry(field_value.todo_id)
+            .or_default()
+            .insert(field_value.field_id, field_value.value);
+    }
+
+    Ok(values)
+}
+
+fn field_names(fields: &TodoFields) -> Vec<&'static str> {
+    [
+        ("name", fields.name.is_some()),
+        ("project_id", fields.project_id.is_some()),
+        ("estimate_minutes", fields.estimate_minutes.is_some()),
+        ("due_at", fields.due_at.is_some()),
+        ("is_completed", fields.is_completed.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(name, is_set)| is_set.then_some(name))
+    .collect()
+}
+
+/// Takes `value` when the server still has `base`, or without a base when the
+/// change is the later one. Values the server already has are skipped.
+fn merge_field<T>(
+    name: &'static str,
+    current: &T,
+    base: &Option<T>,
+    value: &Option<T>,
+    is_newer: bool,
+    taken: &mut Option<T>,
+    conflicts: &mut Vec<&'static str>,
+) where
+    T: Clone + PartialEq,
+{
+    let Some(value) = value else {
+        return;
+    };
+
+    if value == current {
+        return;
+    }
+
+    if base.as_ref().map_or(is_newer, |base| base == current) {
+        *taken = Some(value.clone());
+    } else {
+        conflicts.push(name);
+    }
+}
+
+/// Settles which of `fields` to take over `todo` and which conflict.
+fn settle(
+    todo: &todo::Model,
+    strategy: Strategy,
+    changed_at: &DateTime<Utc>,
+    base: &TodoFields,
+    fields: &TodoFields,
+) -> (TodoFields, Vec<&'static str>) {
+    let is_newer = todo.updated_at <= *changed_at;
+
+    if strategy == Strategy::LastWriterWins {
+        return match is_newer {
+            true => (fields.clone(), Vec::new()),
+            false => (TodoFields::default(), field_names(fields)),
+        };
+    }
+
+    let mut taken = TodoFields::default();
+
+    let mut conflicts = Vec::new();
+
+    merge_field(
+        "name",
+        &todo.name,
+        &base.name,
+        &fields.name,
+        is_newer,
+        &mut taken.name,
+        &mut conflicts,
+    );
+    merge_field(
+        "project_id",
+        &todo.project_id,
+        &base.project_id,
+        &fields.project_id,
+        is_newer,
+        &mut taken.project_id,
+        &mut conflicts,
+    );
+    merge_field(
+        "estimate_minutes",
+        &todo.estimate_minutes,
+        &base.estimate_minutes,
+        &fields.estimate_minutes,
+        is_newer,
+        &mut taken.estimate_minutes,
+        &mut conflicts,
+    );
+    merge_field(
+        "due_at",
+        &todo.due_at,
+        &base.due_at,
+        &fields.due_at,
+        is_newer,
+        &mut taken.due_at,
+        &mut conflicts,
+    );
+    merge_field(
+        "is_completed",
+        &todo.is_completed,
+        &base.is_completed,
+        &fields.is_completed,
+        is_newer,
+        &mut taken.is_completed,
+        &mut conflicts,
+    );
+
+    (taken, conflicts)
+}
+
+async fn is_own_project(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: &Option<Uuid>,
+) -> Result<bool, DbErr> {
+    let Some(project_id) = project_id else {
+        return Ok(true);
+    };
+
+    let project = project::Entity::find_by_id(*project_id).one(db).await?;
+
+    Ok(project.is_some_and(|project| project.user_id == *user_id))
+}
+
+async fn is_deleted(db: &DatabaseConnection, user_id: &Uuid, id: &Uuid) -> Result<bool, DbErr> {
+    let tombstone = todo_tombstone::Entity::find_by_id(*id).one(db).await?;
+
+    Ok(tombstone.is_some_and(|tombstone| tombstone.user_id == *user_id))
+}
+
+async fn create(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    user_id: &Uuid,
+    id: &Uuid,
+    fields: &TodoFields,
+) -> Result<Outcome, DbErr> {
+    if let Some(todo) = todo::Entity::find_by_id(*id).one(db).await? {
+        return Ok(match todo.user_id == *user_id {
+            true => Outcome::Applied(todo),
+            false => Outcome::NotFound,
+        });
+    }
+
+    if is_deleted(db, user_id, id).await? {
+        return Ok(Outcome::Deleted);
+    }
+
+    let project_id = fields.project_id.flatten();
+
+    if !is_own_project(db, user_id, &project_id).await? {
+        return Ok(Outcome::UnknownProject);
+    }
+
+    let now = Utc::now();
+
+    let is_completed = fields.is_completed.unwrap_or(false);
+
+    let todo = todo::ActiveModel {
+        id: Set(*id),
+        user_id: Set(*user_id),
+        project_id: Set(project_id),
+        name: Set(fields.name.clone().unwrap_or_default()),
+        is_completed: Set(is_completed),
+        estimate_minutes: Set(fields.estimate_minutes.flatten()),
+        created_at: Set(now),
+        updated_at: Set(now),
+        completed_at: Set(is_completed.then_some(now)),
+        due_at: Set(fields.due_at.flatten()),
+        parent_id: Set(None),
+        status_id: Set(None),
+    }
+    .insert(db)
+    .await?;
+
+    events
+        .publish(TodoEvent::new(TodoEventKind::Created, &todo))
+        .await;
+
+    Ok(Outcome::Applied(todo))
+}
+
+async fn update(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    todo: todo::Model,
+    fields: &TodoFields,
+) -> Result<todo::Model, DbErr> {
+    let kind = match (todo.is_completed, fields.is_completed) {
+        (false, Some(true)) => TodoEventKind::Completed,
+        (true, Some(false)) => TodoEventKind::Reverted,
+        _ => TodoEventKind::Updated,
+    };
+
+    let now = Utc::now();
+
+    let mut todo = todo.into_active_model();
+
+    if let Some(name) = &fields.name {
+        todo.name = Set(name.to_owned());
+    }
+
+    if let Some(project_id) = fields.project_id {
+        todo.project_id = Set(project_id);
+        todo.status_id = Set(None);
+    }
+
+    if let Some(estimate_minutes) = fields.estimate_minutes {
+        todo.estimate_minutes = Set(estimate_minutes);
+    }
+
+    if let Some(due_at) = fields.due_at {
+        todo.due_at = Set(due_at);
+    }
+
+    if let Some(is_completed) = fields.is_completed {
+        todo.is_completed = Set(is_completed);
+        todo.completed_at = Set(is_completed.then_some(now));
+        todo.status_id = Set(None);
+    }
+
+    todo.updated_at = Set(now);
+
+    let txn = db.begin().await?;
+
+    let todo = todo.update(&txn).await?;
+
+    // Values of the fields of the previous project go, like on a regular
+    // update.
+    if fields.project_id.is_some() {
+        let mut stmt = custom_field_value::Entity::delete_many()
+            .filter(custom_field_value::Column::TodoId.eq(todo.id));
+
+        if let Some(project_id) = todo.project_id {
+            stmt = stmt.filter(Expr::cust_with_values(
+                "custom_field_value.field_id NOT IN (SELECT id FROM custom_field WHERE project_id = $1)",
+                [DbValue::from(project_id)],
+            ));
+        }
+
+        stmt.exec(&txn).await?;
+    }
+
+    txn.commit().await?;
+
+    events.publish(TodoEvent::new(kind, &todo)).await;
+
+    Ok(todo)
+}
+
+async fn apply(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    user_id: &Uuid,
+    strategy: Strategy,
+    change: &Change,
+) -> Result<Outcome, DbErr> {
+    let (id, changed_at) = match change {
+        Change::Create { id, fields } => return create(db, events, user_id, id, fields).await,
+        Change::Update { id, changed_at, .. } | Change::Delete { id, changed_at } => {
+            (id, changed_at)
+        }
+    };
+
+    let Some(todo) = todo::Entity::find_by_id(*id)
+        .one(db)
+        .await?
+        .filter(|todo| todo.user_id == *user_id)
+    else {
+        return Ok(match is_deleted(db, user_id, id).await? {
+            true => Outcome::Deleted,
+            false => Outcome::NotFound,
+        });
+    };
+
+    let Change::Update { base, fields, .. } = change else {
+        // Deleting over changes made after it would lose them.
+        if todo.updated_at > *changed_at {
+            return Ok(Outcome::Conflict(todo, Vec::new()));
+        }
+
+        todo.delete(db).await?;
+
+        events.publish(TodoEvent::deleted(*user_id, *id)).await;
+
+        return Ok(Outcome::Deleted);
+    };
+
+    let (taken, conflicts) = settle(&todo, strategy, changed_at, base, fields);
+
+    if let Some(project_id) = &taken.project_id {
+        if !is_own_project(db, user_id, project_id).await? {
+            return Ok(Outcome::UnknownProject);
+        }
+    }
+
+    if field_names(&taken).is_empty() {
+        return Ok(match conflicts.is_empty() {
+            true => Outcome::Applied(todo),
+            false => Outcome::Conflict(todo, conflicts),
+        });
+    }
+
+    let todo = update(db, events, todo, &taken).await?;
+
+    Ok(match conflicts.is_empty() {
+        true => Outcome::Applied(todo),
+        false => Outcome::Merged(todo, conflicts),
+    })
+}
+
+/// Applies `changes` in order, each on its own so one failing doesn't hold
+/// back the others.
+pub async fn apply_changes(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    user_id: &Uuid,
+    strategy: Strategy,
+    changes: &[Change],
+) -> Result<Vec<Outcome>, ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let mut outcomes = Vec::with_capacity(changes.len());
+
+    for change in changes {
+        outcomes.push(apply(db, events, user_id, strategy, change).await?);
+    }
+
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration as StdDuration;
+
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{database::testing, event::memory::MemoryEventBus};
+
+    fn named(name: &str) -> TodoFields {
+        TodoFields {
+            name: Some(name.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn server_todo(name: &str, updated_at: DateTime<Utc>) -> todo::Model {
+        todo::Model {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            project_id: None,
+            name: name.to_owned(),
+            is_completed: false,
+            estimate_minutes: None,
+            created_at: updated_at,
+            updated_at,
+            completed_at: None,
+            due_at: None,
+            parent_id: None,
+            status_id: None,
+        }
+    }
+
+    #[test]
+    fn last_writer_wins_as_a_whole() {
+        let now = Utc::now();
+
+        let todo = server_todo("Buy milk", now);
+
+        let fields = TodoFields {
+            name: Some("Buy oat milk".to_owned()),
+            is_completed: Some(true),
+            ..Default::default()
+        };
+
+        let (taken, conflicts) = settle(
+            &todo,
+            Strategy::LastWriterWins,
+            &(now + Duration::seconds(1)),
+            &TodoFields::default(),
+            &fields,
+        );
+
+        assert_eq!(taken.name.as_deref(), Some("Buy oat milk"));
+        assert_eq!(taken.is_completed, Some(true));
+        assert!(conflicts.is_empty());
+
+        let (taken, conflicts) = settle(
+            &todo,
+            Strategy::LastWriterWins,
+            &(now - Duration::seconds(1)),
+            &TodoFields::default(),
+            &fields,
+        );
+
+        assert!(field_names(&taken).is_empty());
+        assert_eq!(conflicts, ["name", "is_completed"]);
+    }
+
+    #[test]
+    fn merge_takes_fields_the_server_left_alone() {
+        let now = Utc::now();
+
+        let todo = server_todo("Buy soy milk", now);
+
+        let base = TodoFields {
+            name: Some("Buy milk".to_owned()),
+            is_completed: Some(false),
+            ..Default::default()
+        };
+
+        let fields = TodoFields {
+            name: Some("Buy oat milk".to_owned()),
+            is_completed: Some(true),
+            due_at: Some(Some(now)),
+            ..Default::default()
+        };
+
+        let (taken, conflicts) = settle(
+            &todo,
+            Strategy::Merge,
+            &(now - Duration::seconds(1)),
+            &base,
+            &fields,
+        );
+
+        assert_eq!(taken.name, None);
+        assert_eq!(taken.is_completed, Some(true));
+        // Without a base an older change loses.
+        assert_eq!(taken.due_at, None);
+        assert_eq!(conflicts, ["name", "due_at"]);
+    }
+
+    async fn create(db: &DatabaseConnection, user_id: &Uuid, name: &str) -> todo::Model {
+        let outcomes = apply_changes(
+            db,
+            &MemoryEventBus::new(),
+            user_id,
+            Strategy::LastWriterWins,
+            &[Change::Create {
+                id: Uuid::new_v4(),
+                fields: named(name),
+            }],
+        )
+        .await
+        .unwrap();
+
+        outcomes[0].todo().unwrap().clone()
+    }
+
+    fn ids(todos: &[todo::Model]) -> Vec<Uuid> {
+        todos.iter().map(|todo| todo.id).collect()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn token_returns_todos_written_since() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let old = create(&db, &user.id, "Buy milk").await;
+
+        let first = get_delta(&db, &user.id, None).await.unwrap();
+
+        assert_eq!(ids(&first.todos), [old.id]);
+
+        let new = create(&db, &user.id, "Call mom").await;
+
+        let second = get_delta(&db, &user.id, Some(first.token)).await.unwrap();
+
+        assert!(ids(&second.todos).contains(&new.id));
+        assert!(second.token >= first.token);
+
+        // Transactions of other tests may hold the snapshot xmin back a moment,
+        // then a token resends what it could have skipped.
+        for _ in 0..50 {
+            let third = get_delta(&db, &user.id, Some(second.token)).await.unwrap();
+
+            if third.todos.is_empty() {
+                return;
+            }
+
+            tokio::time::sleep(StdDuration::from_millis(20)).await;
+        }
+
+        panic!("Todos written before the token are sent again");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn token_covers_writes_committed_after_it() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let txn = db.begin().await.unwrap();
+
+        let now = Utc::now();
+
+        let slow = todo::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user.id),
+            project_id: Set(None),
+            name: Set("Slow write".to_owned()),
+            is_completed: Set(false),
+            estimate_minutes: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+            completed_at: Set(None),
+            due_at: Set(None),
+            parent_id: Set(None),
+            status_id: Set(None),
+        }
+        .insert(&txn)
+        .await
+        .unwrap();
+
+        let delta = get_delta(&db, &user.id, None).await.unwrap();
+
+        assert!(delta.todos.is_empty());
+
+        txn.commit().await.unwrap();
+
+        let delta = get_delta(&db, &user.id, Some(delta.token)).await.unwrap();
+
+        assert_eq!(ids(&delta.todos), [slow.id]);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn deletes_leave_tombstones() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let events = MemoryEventBus::new();
+
+        let todo = create(&db, &user.id, "Buy milk").await;
+
+        let token = get_delta(&db, &user.id, None).await.unwrap().token;
+
+        let outcomes = apply_changes(
+            &db,
+            &events,
+            &user.id,
+            Strategy::LastWriterWins,
+            &[Change::Delete {
+                id: todo.id,
+                changed_at: Utc::now(),
+            }],
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(outcomes[0], Outcome::Deleted));
+
+        let delta = get_delta(&db, &user.id, Some(token)).await.unwrap();
+
+        assert!(delta.todos.is_empty());
+        assert_eq!(delta.deleted.len(), 1);
+        assert_eq!(delta.deleted[0].todo_id, todo.id);
+
+        // Without a token the client starts over and needs no tombstones.
+        assert!(get_delta(&db, &user.id, None)
+            .await
+            .unwrap()
+            .deleted
+            .is_empty());
+
+        // A client that missed the delete can't bring the todo back.
+        let outcomes = apply_changes(
+            &db,
+            &events,
+            &user.id,
+            Strategy::LastWriterWins,
+            &[
+                Change::Create {
+                    id: todo.id,
+                    fields: named("Buy milk"),
+                },
+                Change::Update {
+                    id: todo.id,
+                    changed_at: Utc::now(),
+                    base: TodoFields::default(),
+                    fields: named("Buy oat milk"),
+                },
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(outcomes[0], Outcome::Deleted));
+        assert!(matches!(outcomes[1], Outcome::Deleted));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn reports_conflicts_of_older_changes() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let events = MemoryEventBus::new();
+
+        let todo = create(&db, &user.id, "Buy milk").await;
+
+        let outcomes = apply_changes(
+            &db,
+            &events,
+            &user.id,
+            Strategy::LastWriterWins,
+            &[
+                Change::Update {
+                    id: todo.id,
+                    changed_at: todo.updated_at - Duration::minutes(1),
+                    base: TodoFields::default(),
+                    fields: named("Buy soy milk"),
+                },
+                Change::Delete {
+                    id: todo.id,
+                    changed_at: todo.updated_at - Duration::minutes(1),
+                },
+                Change::Update {
+                    id: todo.id,
+                    changed_at: todo.updated_at + Duration::minutes(1),
+                    base: TodoFields::default(),
+                    fields: named("Buy oat milk"),
+                },
+            ],
+        )
+        .await
+        .unwrap();
+
+        match &outcomes[0] {
+            Outcome::Conflict(server, conflicts) => {
+                assert_eq!(server.name, "Buy milk");
+                assert_eq!(conflicts, &["name"]);
+            }
+            outcome => panic!("Expected a conflict, got {outcome:?}"),
+        }
+
+        match &outcomes[1] {
+            Outcome::Conflict(server, conflicts) => {
+                assert_eq!(server.id, todo.id);
+                assert!(conflicts.is_empty());
+            }
+            outcome => panic!("Expected a conflict, got {outcome:?}"),
+        }
+
+        match &outcomes[2] {
+            Outcome::Applied(server) => assert_eq!(server.name, "Buy oat milk"),
+            outcome => panic!("Expected the change to apply, got {outcome:?}"),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, ModelTrait, Order,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use serde::Serialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    entities::{project, template, template_item, todo, user},
+    event::{EventBus, TodoEvent, TodoEventKind},
+};
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct NewTemplateItem {
+    pub name: String,
+    pub due_offset_days: Option<i32>,
+    pub subtasks: Vec<NewTemplateItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateDetails {
+    #[serde(flatten)]
+    pub template: template::Model,
+    pub items: Vec<TemplateItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateItem {
+    pub id: Uuid,
+    pub name: String,
+    pub due_offset_days: Option<i32>,
+    pub subtasks: Vec<TemplateItem>,
+}
+
+/// Builds the item tree from a flat list ordered by position.
+fn build_items(items: &[template_item::Model], parent_id: Option<Uuid>) -> Vec<TemplateItem> {
+    items
+        .iter()
+        .filter(|item| item.parent_id == parent_id)
+        .map(|item| TemplateItem {
+            id: item.id,
+            name: item.name.clone(),
+            due_offset_days: item.due_offset_days,
+            subtasks: build_items(items, Some(item.id)),
+        })
+        .collect()
+}
+
+pub async fn get_templates(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<Vec<template::Model>, ActionError> {
+    Ok(template::Entity::find()
+        .filter(template::Column::UserId.eq(*user_id))
+        .order_by(template::Column::Name, Order::Asc)
+        .all(db)
+        .await?)
+}
+
+pub async fn create_template(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    name: &str,
+    items: &[NewTemplateItem],
+) -> Result<TemplateDetails, ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let txn = db.begin().await?;
+
+    let template = template::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id.to_owned()),
+        name: Set(name.into()),
+        created_at: Set(Utc::now()),
+    }
+    .insert(&txn)
+    .await?;
+
+    // Items are stored in pre-order, so a parent always has a lower position
+    // than its subtasks.
+    let mut stack: Vec<(Option<Uuid>, &NewTemplateItem)> =
+        items.iter().rev().map(|item| (None, item)).collect();
+
+    let mut models = Vec::new();
+
+    while let Some((parent_id, item)) = stack.pop() {
+        let model = template_item::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            template_id: Set(template.id),
+            parent_id: Set(parent_id),
+            name: Set(item.name.clone()),
+            due_offset_days: Set(item.due_offset_days),
+            position: Set(models.len() as i32),
+        }
+        .insert(&txn)
+        .await?;
+
+        stack.extend(
+            item.subtasks
+                .iter()
+                .rev()
+                .map(|subtask| (Some(model.id), subtask)),
+        );
+
+        models.push(model);
+    }
+
+    txn.commit().await?;
+
+    Ok(TemplateDetails {
+        items: build_items(&models, None),
+        template,
+    })
+}
+
+async fn get_owned_template(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<template::Model, ActionError> {
+    let template = template::Entity::find_by_id(*id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if template.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    Ok(template)
+}
+
+async fn get_items(
+    db: &DatabaseConnection,
+    template: &template::Model,
+) -> Result<Vec<template_item::Model>, ActionError> {
+    Ok(template
+        .find_related(template_item::Entity)
+        .order_by(template_item::Column::Position, Order::Asc)
+        .all(db)
+        .await?)
+}
+
+pub async fn get_template(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<TemplateDetails, ActionError> {
+    let template = get_owned_template(db, user_id, id).await?;
+
+    let items = get_items(db, &template).await?;
+
+    Ok(TemplateDetails {
+        items: build_items(&items, None),
+        template,
+    })
+}
+
+pub async fn delete_template(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<(), ActionError> {
+    get_owned_template(db, user_id, id)
+        .await?
+        .delete(db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn instantiate_template(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    user_id: &Uuid,
+    id: &Uuid,
+    project_id: &Option<Uuid>,
+    start_at: &DateTime<Utc>,
+) -> Result<Vec<todo::Model>, ActionError> {
+    let template = get_owned_template(db, user_id, id).await?;
+
+    if let Some(project_id) = project_id {
+        let project = project::Entity::find_by_id(*project_id)
+            .one(db)
+            .await?
+            .ok_or(ActionError::NotFound)?;
+
+        if project.user_id != *user_id {
+            return Err(ActionError::Forbidden);
+        }
+    }
+
+    let items = get_items(db, &template).await?;
+
+    let now = Utc::now();
+
+    let mut todo_ids: HashMap<Uuid, Uuid> = HashMap::new();
+
+    let mut todos = Vec::new();
+
+    let txn = db.begin().await?;
+
+    for item in items {
+        let todo = todo::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user_id.to_owned()),
+            project_id: Set(*project_id),
+            name: Set(item.name),
+            is_completed: Set(false),
+            estimate_minutes: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+            completed_at: Set(None),
+            due_at: Set(item
+                .due_offset_days
+                .map(|days| *start_at + Duration::days(days.into()))),
+            parent_id: Set(item
+                .parent_id
+                .and_then(|parent_id| todo_ids.get(&parent_id).copied())),
+            status_id: Set(None),
+        }
+        .insert(&txn)
+        .await?;
+
+        todo_ids.insert(item.id, todo.id);
+
+        todos.push(todo);
+    }
+
+    txn.commit().await?;
+
+    for todo in &todos {
+        events
+            .publish(TodoEvent::new(TodoEventKind::Created, todo))
+            .await;
+    }
+
+    Ok(todos)
+}
@@ -0,0 +1,533 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, DbBackend, DbErr,
+    EntityTrait, FromQueryResult, IntoActiveModel, JoinType, ModelTrait, Order, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait, RuntimeErr, Set, Statement, TransactionTrait,
+};
+use serde::Serialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    database,
+    entities::{project, time_entry, todo},
+};
+
+static TRACKED_SECONDS: &str = "COALESCE(SUM(EXTRACT(EPOCH FROM COALESCE(time_entry.stopped_at, NOW()) - time_entry.started_at)), 0)::BIGINT";
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+    #[error("Invalid Time Zone")]
+    InvalidTimeZone,
+    #[error("Timer Already Running")]
+    TimerAlreadyRunning,
+}
+
+/// Whether `error` comes from the index allowing one running timer per user.
+fn is_running_timer_conflict(error: &DbErr) -> bool {
+    match error {
+        DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(error)))
+        | DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::Database(error))) => {
+            error.constraint() == Some("time_entry_running_idx")
+        }
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewTimeEntry {
+    pub note: String,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TodoTime {
+    pub todo_id: Uuid,
+    pub estimate_minutes: Option<i32>,
+    pub tracked_seconds: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectTime {
+    pub project_id: Uuid,
+    pub estimate_minutes: i64,
+    pub tracked_seconds: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Timesheet {
+    pub week_start: NaiveDate,
+    pub time_zone: String,
+    pub days: Vec<NaiveDate>,
+    pub rows: Vec<TimesheetRow>,
+    pub total_seconds: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimesheetRow {
+    pub todo_id: Uuid,
+    pub todo_name: String,
+    pub project_id: Option<Uuid>,
+    pub seconds: Vec<i64>,
+    pub total_seconds: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct TimesheetCell {
+    day: NaiveDate,
+    todo_id: Uuid,
+    todo_name: String,
+    project_id: Option<Uuid>,
+    seconds: i64,
+}
+
+async fn get_owned_todo(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    todo_id: &Uuid,
+) -> Result<todo::Model, ActionError> {
+    let todo = todo::Entity::find_by_id(*todo_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if todo.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    Ok(todo)
+}
+
+pub async fn get_running_timer(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<Option<time_entry::Model>, ActionError> {
+    Ok(time_entry::Entity::find()
+        .filter(time_entry::Column::UserId.eq(*user_id))
+        .filter(time_entry::Column::StoppedAt.is_null())
+        .one(db)
+        .await?)
+}
+
+pub async fn start_timer(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    todo_id: &Uuid,
+) -> Result<time_entry::Model, ActionError> {
+    get_owned_todo(db, user_id, todo_id).await?;
+
+    let now = Utc::now();
+
+    let txn = db.begin().await?;
+
+    time_entry::Entity::update_many()
+        .col_expr(time_entry::Column::StoppedAt, Expr::value(now))
+        .filter(time_entry::Column::UserId.eq(*user_id))
+        .filter(time_entry::Column::StoppedAt.is_null())
+        .exec(&txn)
+        .await?;
+
+    let new_entry = time_entry::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id.to_owned()),
+        todo_id: Set(todo_id.to_owned()),
+        note: Set(String::new()),
+        started_at: Set(now),
+        stopped_at: Set(None),
+    };
+
+    // A concurrent start commits its timer after the update above.
+    let new_entry = new_entry
+        .insert(&txn)
+        .await
+        .map_err(|err| match is_running_timer_conflict(&err) {
+            true => ActionError::TimerAlreadyRunning,
+            false => ActionError::Db(err),
+        })?;
+
+    txn.commit().await?;
+
+    Ok(new_entry)
+}
+
+pub async fn stop_timer(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<time_entry::Model, ActionError> {
+    let mut entry = get_running_timer(db, user_id)
+        .await?
+        .ok_or(ActionError::NotFound)?
+        .into_active_model();
+
+    entry.stopped_at = Set(Some(Utc::now()));
+
+    Ok(entry.update(db).await?)
+}
+
+pub async fn get_time_entries(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    todo_id: &Uuid,
+) -> Result<Vec<time_entry::Model>, ActionError> {
+    get_owned_todo(db, user_id, todo_id).await?;
+
+    Ok(time_entry::Entity::find()
+        .filter(time_entry::Column::TodoId.eq(*todo_id))
+        .order_by(time_entry::Column::StartedAt, Order::Desc)
+        .all(db)
+        .await?)
+}
+
+pub async fn create_time_entry(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    todo_id: &Uuid,
+    new_entry: &NewTimeEntry,
+) -> Result<time_entry::Model, ActionError> {
+    get_owned_todo(db, user_id, todo_id).await?;
+
+    let new_entry = time_entry::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id.to_owned()),
+        todo_id: Set(todo_id.to_owned()),
+        note: Set(new_entry.note.clone()),
+        started_at: Set(new_entry.started_at),
+        stopped_at: Set(Some(new_entry.stopped_at)),
+    };
+
+    Ok(new_entry.insert(db).await?)
+}
+
+pub async fn delete_time_entry(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<(), ActionError> {
+    let entry = time_entry::Entity::find_by_id(*id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if entry.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    entry.delete(db).await?;
+
+    Ok(())
+}
+
+pub async fn get_todo_time(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    todo_id: &Uuid,
+) -> Result<TodoTime, ActionError> {
+    let todo = get_owned_todo(db, user_id, todo_id).await?;
+
+    let tracked_seconds = time_entry::Entity::find()
+        .select_only()
+        .column_as(Expr::cust(TRACKED_SECONDS), "tracked_seconds")
+        .filter(time_entry::Column::TodoId.eq(todo.id))
+        .into_tuple::<i64>()
+        .one(db)
+        .await?
+        .unwrap_or_default();
+
+    Ok(TodoTime {
+        todo_id: todo.id,
+        estimate_minutes: todo.estimate_minutes,
+        tracked_seconds,
+    })
+}
+
+pub async fn get_project_time(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: &Uuid,
+) -> Result<ProjectTime, ActionError> {
+    let project = project::Entity::find_by_id(*project_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if project.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    let tracked_seconds = time_entry::Entity::find()
+        .select_only()
+        .column_as(Expr::cust(TRACKED_SECONDS), "tracked_seconds")
+        .join(JoinType::InnerJoin, time_entry::Relation::Todo.def())
+        .filter(todo::Column::ProjectId.eq(project.id))
+        .into_tuple::<i64>()
+        .one(db)
+        .await?
+        .unwrap_or_default();
+
+    let estimate_minutes = todo::Entity::find()
+        .select_only()
+        .column_as(
+            Expr::cust("COALESCE(SUM(todo.estimate_minutes), 0)::BIGINT"),
+            "estimate_minutes",
+        )
+        .filter(todo::Column::ProjectId.eq(project.id))
+        .into_tuple::<i64>()
+        .one(db)
+        .await?
+        .unwrap_or_default();
+
+    Ok(ProjectTime {
+        project_id: project.id,
+        estimate_minutes,
+        tracked_seconds,
+    })
+}
+
+pub async fn get_timesheet(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    week_start: &NaiveDate,
+    time_zone: &str,
+) -> Result<Timesheet, ActionError> {
+    if !database::is_valid_time_zone(db, time_zone).await? {
+        return Err(ActionError::InvalidTimeZone);
+    }
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+        SELECT
+            (time_entry.started_at AT TIME ZONE $2)::DATE AS day,
+            todo.id AS todo_id,
+            todo.name AS todo_name,
+            todo.project_id AS project_id,
+            SUM(EXTRACT(EPOCH FROM COALESCE(time_entry.stopped_at, NOW()) - time_entry.started_at))::BIGINT AS seconds
+        FROM time_entry
+        JOIN todo ON todo.id = time_entry.todo_id
+        WHERE time_entry.user_id = $1
+            AND time_entry.started_at >= $3::DATE::TIMESTAMP AT TIME ZONE $2
+            AND time_entry.started_at < ($3::DATE + 7)::TIMESTAMP AT TIME ZONE $2
+        GROUP BY day, todo.id, todo.name, todo.project_id
+        ORDER BY todo.name, day
+        "#,
+        [(*user_id).into(), time_zone.into(), (*week_start).into()],
+    );
+
+    let cells = TimesheetCell::find_by_statement(stmt).all(db).await?;
+
+    let days = (0..7)
+        .map(|i| *week_start + Duration::days(i))
+        .collect::<Vec<_>>();
+
+    let mut rows: Vec<TimesheetRow> = Vec::new();
+
+    for cell in cells {
+        let index = (cell.day - *week_start).num_days() as usize;
+
+        if index >= days.len() {
+            continue;
+        }
+
+        let row = match rows.iter_mut().find(|row| row.todo_id == cell.todo_id) {
+            Some(row) => row,
+            None => {
+                rows.push(TimesheetRow {
+                    todo_id: cell.todo_id,
+                    todo_name: cell.todo_name,
+                    project_id: cell.project_id,
+                    seconds: vec![0; days.len()],
+                    total_seconds: 0,
+                });
+
+                rows.last_mut().unwrap()
+            }
+        };
+
+        row.seconds[index] += cell.seconds;
+        row.total_seconds += cell.seconds;
+    }
+
+    let total_seconds = rows.iter().map(|row| row.total_seconds).sum();
+
+    Ok(Timesheet {
+        week_start: *week_start,
+        time_zone: time_zone.to_owned(),
+        days,
+        rows,
+        total_seconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration as StdDuration;
+
+    use super::*;
+    use crate::database::testing;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    async fn track(db: &DatabaseConnection, user_id: &Uuid, todo_id: &Uuid, from: &str, to: &str) {
+        create_time_entry(
+            db,
+            user_id,
+            todo_id,
+            &NewTimeEntry {
+                note: String::new(),
+                started_at: at(from),
+                stopped_at: at(to),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn timesheet_days_follow_the_time_zone() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let todo = testing::todo(&db, &user.id, "Write the report").await;
+
+        // Monday 00:30 in Berlin, still Sunday in UTC.
+        track(
+            &db,
+            &user.id,
+            &todo.id,
+            "2023-03-19T23:30:00Z",
+            "2023-03-20T00:30:00Z",
+        )
+        .await;
+        // Sunday 23:30 in Berlin after the switch to summer time.
+        track(
+            &db,
+            &user.id,
+            &todo.id,
+            "2023-03-26T21:30:00Z",
+            "2023-03-26T21:45:00Z",
+        )
+        .await;
+        // Monday 00:30 of the next week in Berlin.
+        track(
+            &db,
+            &user.id,
+            &todo.id,
+            "2023-03-26T22:30:00Z",
+            "2023-03-26T23:30:00Z",
+        )
+        .await;
+
+        let berlin = get_timesheet(&db, &user.id, &date("2023-03-20"), "Europe/Berlin")
+            .await
+            .unwrap();
+
+        assert_eq!(berlin.days.first(), Some(&date("2023-03-20")));
+        assert_eq!(berlin.days.last(), Some(&date("2023-03-26")));
+        assert_eq!(berlin.rows.len(), 1);
+        assert_eq!(berlin.rows[0].seconds, [3600, 0, 0, 0, 0, 0, 900]);
+        assert_eq!(berlin.total_seconds, 4500);
+
+        let utc = get_timesheet(&db, &user.id, &date("2023-03-20"), "UTC")
+            .await
+            .unwrap();
+
+        assert_eq!(utc.rows[0].seconds, [0, 0, 0, 0, 0, 0, 900 + 3600]);
+        assert_eq!(utc.total_seconds, 4500);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn timesheet_refuses_unknown_time_zones() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let result = get_timesheet(&db, &user.id, &date("2023-03-20"), "Mars/Olympus_Mons").await;
+
+        assert!(matches!(result, Err(ActionError::InvalidTimeZone)));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn starting_a_timer_stops_the_running_one() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let first = testing::todo(&db, &user.id, "Write the report").await;
+        let second = testing::todo(&db, &user.id, "Send the report").await;
+
+        let stopped = start_timer(&db, &user.id, &first.id).await.unwrap();
+        let running = start_timer(&db, &user.id, &second.id).await.unwrap();
+
+        let entries = time_entry::Entity::find().all(&db).await.unwrap();
+
+        assert_eq!(entries.len(), 2);
+
+        for entry in entries {
+            match entry.id == stopped.id {
+                true => assert_eq!(entry.stopped_at, Some(running.started_at)),
+                false => assert_eq!(entry.stopped_at, None),
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn concurrent_timer_start_is_a_conflict() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let todo = testing::todo(&db, &user.id, "Write the report").await;
+
+        // A start that isn't committed yet, the other one waits on the index.
+        let txn = db.begin().await.unwrap();
+
+        time_entry::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user.id),
+            todo_id: Set(todo.id),
+            note: Set(String::new()),
+            started_at: Set(Utc::now()),
+            stopped_at: Set(None),
+        }
+        .insert(&txn)
+        .await
+        .unwrap();
+
+        let start = tokio::spawn({
+            let db = db.clone();
+
+            async move { start_timer(&db, &user.id, &todo.id).await }
+        });
+
+        tokio::time::sleep(StdDuration::from_millis(200)).await;
+
+        txn.commit().await.unwrap();
+
+        let result = start.await.unwrap();
+
+        assert!(
+            matches!(result, Err(ActionError::TimerAlreadyRunning)),
+            "{result:?}"
+        );
+    }
+}
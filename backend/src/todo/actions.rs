@@ -1,12 +1,20 @@
-use chrono::Utc;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
-    ModelTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    sea_query::{Expr, OnConflict},
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, ModelTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait, Value as DbValue,
 };
+use serde_json::Value;
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::entities::{todo, user};
+use crate::{
+    entities::{custom_field, custom_field_value, project, todo, user},
+    event::{EventBus, TodoEvent, TodoEventKind},
+};
 
 #[derive(Debug, Error)]
 pub enum ActionError {
@@ -18,31 +26,200 @@ pub enum ActionError {
     NotFound,
 }
 
-pub async fn get_todos_count(
+#[derive(Debug, Default, Clone)]
+pub struct TodosFilter {
+    pub is_completed: Option<bool>,
+    pub project_id: Option<Uuid>,
+    pub field: Option<(Uuid, String)>,
+    /// Part of the name, matched case insensitively.
+    pub search: Option<String>,
+}
+
+impl TodosFilter {
+    fn apply<Q>(&self, mut stmt: Q) -> Q
+    where
+        Q: QueryFilter,
+    {
+        if let Some(is_completed) = self.is_completed {
+            stmt = stmt.filter(todo::Column::IsCompleted.eq(is_completed));
+        }
+
+        if let Some(project_id) = self.project_id {
+            stmt = stmt.filter(todo::Column::ProjectId.eq(project_id));
+        }
+
+        if let Some((field_id, value)) = &self.field {
+            stmt = stmt.filter(Expr::cust_with_values(
+                "EXISTS (SELECT 1 FROM custom_field_value WHERE custom_field_value.todo_id = todo.id AND custom_field_value.field_id = $1 AND custom_field_value.value #>> '{}' = $2)",
+                [DbValue::from(*field_id), DbValue::from(value.as_str())],
+            ));
+        }
+
+        if let Some(search) = &self.search {
+            let pattern = search
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+
+            stmt = stmt.filter(Expr::cust_with_values(
+                "todo.name ILIKE $1",
+                [DbValue::from(format!("%{pattern}%"))],
+            ));
+        }
+
+        stmt
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldSort {
+    pub field_id: Uuid,
+    pub order: Order,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct NewTodo {
+    pub name: String,
+    pub project_id: Option<Uuid>,
+    pub estimate_minutes: Option<i32>,
+    pub fields: HashMap<Uuid, Value>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TodoChanges {
+    pub name: Option<String>,
+    pub project_id: Option<Option<Uuid>>,
+    pub estimate_minutes: Option<Option<i32>>,
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    pub fields: HashMap<Uuid, Value>,
+}
+
+async fn set_field_values<C>(
+    db: &C,
+    todo_id: &Uuid,
+    fields: &HashMap<Uuid, Value>,
+) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    for (field_id, value) in fields {
+        if value.is_null() {
+            custom_field_value::Entity::delete_many()
+                .filter(custom_field_value::Column::TodoId.eq(*todo_id))
+                .filter(custom_field_value::Column::FieldId.eq(*field_id))
+                .exec(db)
+                .await?;
+
+            continue;
+        }
+
+        let field_value = custom_field_value::ActiveModel {
+            todo_id: Set(*todo_id),
+            field_id: Set(*field_id),
+            value: Set(value.clone()),
+        };
+
+        custom_field_value::Entity::insert(field_value)
+            .on_conflict(
+                OnConflict::columns([
+                    custom_field_value::Column::TodoId,
+                    custom_field_value::Column::FieldId,
+                ])
+                .update_column(custom_field_value::Column::Value)
+                .to_owned(),
+            )
+            .exec(db)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn get_project_fields(
+    db: &DatabaseConnection,
+    project_id: &Uuid,
+) -> Result<Vec<custom_field::Model>, ActionError> {
+    Ok(custom_field::Entity::find()
+        .filter(custom_field::Column::ProjectId.eq(*project_id))
+        .all(db)
+        .await?)
+}
+
+pub async fn get_field_values(
+    db: &DatabaseConnection,
+    todo_ids: &[Uuid],
+) -> Result<HashMap<Uuid, HashMap<Uuid, Value>>, ActionError> {
+    let mut values: HashMap<Uuid, HashMap<Uuid, Value>> = HashMap::new();
+
+    if todo_ids.is_empty() {
+        return Ok(values);
+    }
+
+    let field_values = custom_field_value::Entity::find()
+        .filter(custom_field_value::Column::TodoId.is_in(todo_ids.iter().copied()))
+        .all(db)
+        .await?;
+
+    for field_value in field_values {
+        values
+            .entry(field_value.todo_id)
+            .or_default()
+            .insert(field_value.field_id, field_value.value);
+    }
+
+    Ok(values)
+}
+
+/// Fails unless the project exists and belongs to the user.
+pub async fn check_project(
     db: &DatabaseConnection,
     user_id: &Uuid,
-    is_completed: Option<bool>,
-) -> Result<u64, ActionError> {
-    let mut stmt = todo::Entity::find().filter(todo::Column::UserId.eq(*user_id));
+    project_id: &Uuid,
+) -> Result<(), ActionError> {
+    let project = project::Entity::find_by_id(*project_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
 
-    if let Some(is_completed) = is_completed {
-        stmt = stmt.filter(todo::Column::IsCompleted.eq(is_completed));
+    if project.user_id != *user_id {
+        return Err(ActionError::Forbidden);
     }
 
-    Ok(stmt.count(db).await?)
+    Ok(())
+}
+
+pub async fn get_todos_count(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    filter: &TodosFilter,
+) -> Result<u64, ActionError> {
+    let stmt = todo::Entity::find().filter(todo::Column::UserId.eq(*user_id));
+
+    Ok(filter.apply(stmt).count(db).await?)
 }
 
 pub async fn get_todos(
     db: &DatabaseConnection,
     user_id: &Uuid,
-    is_completed: Option<bool>,
+    filter: &TodosFilter,
+    sort: &Option<FieldSort>,
     limit: &u64,
     offset: &u64,
 ) -> Result<Vec<todo::Model>, ActionError> {
-    let mut stmt = todo::Entity::find().filter(todo::Column::UserId.eq(*user_id));
-
-    if let Some(is_completed) = is_completed {
-        stmt = stmt.filter(todo::Column::IsCompleted.eq(is_completed));
+    let mut stmt = filter.apply(todo::Entity::find().filter(todo::Column::UserId.eq(*user_id)));
+
+    if let Some(sort) = sort {
+        let value = "(SELECT custom_field_value.value FROM custom_field_value WHERE custom_field_value.todo_id = todo.id AND custom_field_value.field_id = $1)";
+
+        stmt = stmt
+            .order_by(
+                Expr::cust_with_values(&format!("{value} IS NULL"), [DbValue::from(sort.field_id)]),
+                Order::Asc,
+            )
+            .order_by(
+                Expr::cust_with_values(value, [DbValue::from(sort.field_id)]),
+                sort.order.clone(),
+            );
     }
 
     Ok(stmt
@@ -53,43 +230,143 @@ pub async fn get_todos(
         .await?)
 }
 
+/// Page of todos oldest first, starting after the given `(created_at, id)`,
+/// for walking through all todos without offsets.
+pub async fn get_todos_after(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    filter: &TodosFilter,
+    after: &Option<(DateTime<Utc>, Uuid)>,
+    limit: u64,
+) -> Result<Vec<todo::Model>, ActionError> {
+    let mut stmt = filter.apply(todo::Entity::find().filter(todo::Column::UserId.eq(*user_id)));
+
+    if let Some((created_at, id)) = after {
+        stmt = stmt.filter(
+            Condition::any()
+                .add(todo::Column::CreatedAt.gt(*created_at))
+                .add(
+                    Condition::all()
+                        .add(todo::Column::CreatedAt.eq(*created_at))
+                        .add(todo::Column::Id.gt(*id)),
+                ),
+        );
+    }
+
+    Ok(stmt
+        .order_by(todo::Column::CreatedAt, Order::Asc)
+        .order_by(todo::Column::Id, Order::Asc)
+        .limit(limit)
+        .all(db)
+        .await?)
+}
+
+/// Todos due or completed in `[from, to)`, for calendar views, placed by
+/// due date first.
+pub async fn get_todos_between(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    from: &DateTime<Utc>,
+    to: &DateTime<Utc>,
+) -> Result<Vec<todo::Model>, ActionError> {
+    Ok(todo::Entity::find()
+        .filter(todo::Column::UserId.eq(*user_id))
+        .filter(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(todo::Column::DueAt.gte(*from))
+                        .add(todo::Column::DueAt.lt(*to)),
+                )
+                .add(
+                    Condition::all()
+                        .add(todo::Column::DueAt.is_null())
+                        .add(todo::Column::CompletedAt.gte(*from))
+                        .add(todo::Column::CompletedAt.lt(*to)),
+                ),
+        )
+        .order_by(
+            Expr::cust("COALESCE(todo.due_at, todo.completed_at)"),
+            Order::Asc,
+        )
+        .all(db)
+        .await?)
+}
+
 pub async fn create_todo(
     db: &DatabaseConnection,
+    events: &dyn EventBus,
     user_id: &Uuid,
-    name: &str,
+    new_todo: &NewTodo,
 ) -> Result<todo::Model, ActionError> {
     user::Entity::find_by_id(*user_id)
         .one(db)
         .await?
         .ok_or(ActionError::NotFound)?;
 
+    if let Some(project_id) = &new_todo.project_id {
+        check_project(db, user_id, project_id).await?;
+    }
+
     let now = Utc::now();
 
+    let fields = new_todo.fields.clone();
+
     let new_todo = todo::ActiveModel {
         id: Set(Uuid::new_v4()),
         user_id: Set(user_id.to_owned()),
-        name: Set(name.into()),
+        project_id: Set(new_todo.project_id),
+        name: Set(new_todo.name.clone()),
         is_completed: Set(false),
+        estimate_minutes: Set(new_todo.estimate_minutes),
         created_at: Set(now),
         updated_at: Set(now),
         completed_at: Set(None),
+        due_at: Set(None),
+        parent_id: Set(None),
+        status_id: Set(None),
     };
 
-    Ok(new_todo.insert(db).await?)
+    let txn = db.begin().await?;
+
+    let new_todo = new_todo.insert(&txn).await?;
+
+    set_field_values(&txn, &new_todo.id, &fields).await?;
+
+    txn.commit().await?;
+
+    events
+        .publish(TodoEvent::new(TodoEventKind::Created, &new_todo))
+        .await;
+
+    Ok(new_todo)
 }
 
 pub async fn delete_todos(
     db: &DatabaseConnection,
+    events: &dyn EventBus,
     user_id: &Uuid,
-    is_completed: Option<bool>,
+    filter: &TodosFilter,
 ) -> Result<(), ActionError> {
-    let mut stmt = todo::Entity::delete_many().filter(todo::Column::UserId.eq(*user_id));
+    let stmt = todo::Entity::find()
+        .select_only()
+        .column(todo::Column::Id)
+        .filter(todo::Column::UserId.eq(*user_id));
+
+    let ids: Vec<Uuid> = filter.apply(stmt).into_tuple().all(db).await?;
 
-    if let Some(is_completed) = is_completed {
-        stmt = stmt.filter(todo::Column::IsCompleted.eq(is_completed));
+    if ids.is_empty() {
+        return Ok(());
     }
 
-    stmt.exec(db).await?;
+    todo::Entity::delete_many()
+        .filter(todo::Column::Id.is_in(ids.iter().copied()))
+        .exec(db)
+        .await?;
+
+    for id in ids {
+        events.publish(TodoEvent::deleted(*user_id, id)).await;
+    }
 
     Ok(())
 }
@@ -118,9 +395,10 @@ pub async fn get_todo(
 
 pub async fn update_todo(
     db: &DatabaseConnection,
+    events: &dyn EventBus,
     user_id: &Uuid,
     id: &Uuid,
-    name: &str,
+    changes: &TodoChanges,
 ) -> Result<todo::Model, ActionError> {
     let user = user::Entity::find_by_id(*user_id)
         .one(db)
@@ -136,16 +414,63 @@ pub async fn update_todo(
         return Err(ActionError::Forbidden);
     }
 
+    if let Some(Some(project_id)) = &changes.project_id {
+        check_project(db, user_id, project_id).await?;
+    }
+
     let mut todo = todo.into_active_model();
 
-    todo.name = Set(name.to_owned());
+    if let Some(name) = &changes.name {
+        todo.name = Set(name.to_owned());
+    }
+
+    if let Some(project_id) = changes.project_id {
+        todo.project_id = Set(project_id);
+        todo.status_id = Set(None);
+    }
+
+    if let Some(estimate_minutes) = changes.estimate_minutes {
+        todo.estimate_minutes = Set(estimate_minutes);
+    }
+
+    if let Some(due_at) = changes.due_at {
+        todo.due_at = Set(due_at);
+    }
+
     todo.updated_at = Set(Utc::now());
 
-    Ok(todo.update(db).await?)
+    let txn = db.begin().await?;
+
+    let todo = todo.update(&txn).await?;
+
+    if changes.project_id.is_some() {
+        let mut stmt = custom_field_value::Entity::delete_many()
+            .filter(custom_field_value::Column::TodoId.eq(todo.id));
+
+        if let Some(project_id) = todo.project_id {
+            stmt = stmt.filter(Expr::cust_with_values(
+                "custom_field_value.field_id NOT IN (SELECT id FROM custom_field WHERE project_id = $1)",
+                [DbValue::from(project_id)],
+            ));
+        }
+
+        stmt.exec(&txn).await?;
+    }
+
+    set_field_values(&txn, &todo.id, &changes.fields).await?;
+
+    txn.commit().await?;
+
+    events
+        .publish(TodoEvent::new(TodoEventKind::Updated, &todo))
+        .await;
+
+    Ok(todo)
 }
 
 pub async fn delete_todo(
     db: &DatabaseConnection,
+    events: &dyn EventBus,
     user_id: &Uuid,
     id: &Uuid,
 ) -> Result<(), ActionError> {
@@ -163,13 +488,18 @@ pub async fn delete_todo(
         return Err(ActionError::Forbidden);
     }
 
+    let id = todo.id;
+
     todo.delete(db).await?;
 
+    events.publish(TodoEvent::deleted(*user_id, id)).await;
+
     Ok(())
 }
 
 pub async fn complete_todo(
     db: &DatabaseConnection,
+    events: &dyn EventBus,
     user_id: &Uuid,
     id: &Uuid,
 ) -> Result<todo::Model, ActionError> {
@@ -194,12 +524,20 @@ pub async fn complete_todo(
     todo.is_completed = Set(true);
     todo.completed_at = Set(Some(now));
     todo.updated_at = Set(now);
+    todo.status_id = Set(None);
 
-    Ok(todo.update(db).await?)
+    let todo = todo.update(db).await?;
+
+    events
+        .publish(TodoEvent::new(TodoEventKind::Completed, &todo))
+        .await;
+
+    Ok(todo)
 }
 
 pub async fn revert_todo(
     db: &DatabaseConnection,
+    events: &dyn EventBus,
     user_id: &Uuid,
     id: &Uuid,
 ) -> Result<todo::Model, ActionError> {
@@ -222,6 +560,13 @@ pub async fn revert_todo(
     todo.is_completed = Set(false);
     todo.completed_at = Set(None);
     todo.updated_at = Set(Utc::now());
+    todo.status_id = Set(None);
 
-    Ok(todo.update(db).await?)
+    let todo = todo.update(db).await?;
+
+    events
+        .publish(TodoEvent::new(TodoEventKind::Reverted, &todo))
+        .await;
+
+    Ok(todo)
 }
@@ -0,0 +1,525 @@
+use chrono::NaiveDate;
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, FromQueryResult, Statement};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::database;
+
+/// Longest range that can be requested, in days.
+const MAX_RANGE_DAYS: i64 = 731;
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Invalid Time Zone")]
+    InvalidTimeZone,
+    #[error("Invalid Range")]
+    InvalidRange,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Period {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl Period {
+    /// Field name understood by `date_trunc`, weeks start on Monday.
+    fn as_str(&self) -> &'static str {
+        match self {
+            Period::Day => "day",
+            Period::Week => "week",
+            Period::Month => "month",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub period: Period,
+    pub time_zone: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub buckets: Vec<Bucket>,
+    /// Current counts, regardless of the range.
+    pub open: i64,
+    pub done: i64,
+    /// Average time from creation to completion of the todos completed in
+    /// the range.
+    pub average_completion_seconds: Option<i64>,
+    /// Consecutive days with at least one completed todo, the current streak
+    /// is kept until the end of the day after the last completion.
+    pub current_streak: i64,
+    pub longest_streak: i64,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct Bucket {
+    pub start: NaiveDate,
+    pub created: i64,
+    pub completed: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct Totals {
+    open: i64,
+    done: i64,
+    average_completion_seconds: Option<i64>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct Streaks {
+    current_streak: i64,
+    longest_streak: i64,
+}
+
+/// Throughput of the user's todos between `from` and `to`, both inclusive,
+/// grouped by `period` in `time_zone`.
+pub async fn get_stats(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    period: Period,
+    from: &NaiveDate,
+    to: &NaiveDate,
+    time_zone: &str,
+) -> Result<Stats, ActionError> {
+    if from > to || (*to - *from).num_days() > MAX_RANGE_DAYS {
+        return Err(ActionError::InvalidRange);
+    }
+
+    if !database::is_valid_time_zone(db, time_zone).await? {
+        return Err(ActionError::InvalidTimeZone);
+    }
+
+    let buckets = Bucket::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+        WITH buckets AS (
+            SELECT generate_series(
+                date_trunc($2, $3::DATE::TIMESTAMP),
+                date_trunc($2, $4::DATE::TIMESTAMP),
+                ('1 ' || $2)::INTERVAL
+            )::DATE AS start
+        ),
+        created AS (
+            SELECT date_trunc($2, todo.created_at AT TIME ZONE $5)::DATE AS start, COUNT(*) AS count
+            FROM todo
+            WHERE todo.user_id = $1
+                AND todo.created_at >= date_trunc($2, $3::DATE::TIMESTAMP) AT TIME ZONE $5
+                AND todo.created_at < ($4::DATE + 1)::TIMESTAMP AT TIME ZONE $5
+            GROUP BY 1
+        ),
+        completed AS (
+            SELECT date_trunc($2, todo.completed_at AT TIME ZONE $5)::DATE AS start, COUNT(*) AS count
+            FROM todo
+            WHERE todo.user_id = $1
+                AND todo.completed_at >= date_trunc($2, $3::DATE::TIMESTAMP) AT TIME ZONE $5
+                AND todo.completed_at < ($4::DATE + 1)::TIMESTAMP AT TIME ZONE $5
+            GROUP BY 1
+        )
+        SELECT
+            buckets.start,
+            COALESCE(created.count, 0)::BIGINT AS created,
+            COALESCE(completed.count, 0)::BIGINT AS completed
+        FROM buckets
+        LEFT JOIN created USING (start)
+        LEFT JOIN completed USING (start)
+        ORDER BY buckets.start
+        "#,
+        [
+            (*user_id).into(),
+            period.as_str().into(),
+            (*from).into(),
+            (*to).into(),
+            time_zone.into(),
+        ],
+    ))
+    .all(db)
+    .await?;
+
+    let totals = Totals::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE NOT todo.is_completed)::BIGINT AS open,
+            COUNT(*) FILTER (WHERE todo.is_completed)::BIGINT AS done,
+            AVG(EXTRACT(EPOCH FROM todo.completed_at - todo.created_at)) FILTER (
+                WHERE todo.completed_at >= $2::DATE::TIMESTAMP AT TIME ZONE $4
+                    AND todo.completed_at < ($3::DATE + 1)::TIMESTAMP AT TIME ZONE $4
+            )::BIGINT AS average_completion_seconds
+        FROM todo
+        WHERE todo.user_id = $1
+        "#,
+        [
+            (*user_id).into(),
+            (*from).into(),
+            (*to).into(),
+            time_zone.into(),
+        ],
+    ))
+    .one(db)
+    .await?
+    .unwrap_or(Totals {
+        open: 0,
+        done: 0,
+        average_completion_seconds: None,
+    });
+
+    let streaks = Streaks::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+        WITH days AS (
+            SELECT DISTINCT (todo.completed_at AT TIME ZONE $2)::DATE AS day
+            FROM todo
+            WHERE todo.user_id = $1 AND todo.completed_at IS NOT NULL
+        ),
+        streaks AS (
+            SELECT MAX(day) AS last_day, COUNT(*) AS length
+            FROM (SELECT day, day - (ROW_NUMBER() OVER (ORDER BY day))::INT AS island FROM days) AS islands
+            GROUP BY island
+        )
+        SELECT
+            COALESCE(MAX(length) FILTER (WHERE last_day >= (NOW() AT TIME ZONE $2)::DATE - 1), 0)::BIGINT AS current_streak,
+            COALESCE(MAX(length), 0)::BIGINT AS longest_streak
+        FROM streaks
+        "#,
+        [(*user_id).into(), time_zone.into()],
+    ))
+    .one(db)
+    .await?
+    .unwrap_or(Streaks {
+        current_streak: 0,
+        longest_streak: 0,
+    });
+
+    Ok(Stats {
+        period,
+        time_zone: time_zone.to_owned(),
+        from: *from,
+        to: *to,
+        buckets,
+        open: totals.open,
+        done: totals.done,
+        average_completion_seconds: totals.average_completion_seconds,
+        current_streak: streaks.current_streak,
+        longest_streak: streaks.longest_streak,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+    use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+
+    use super::*;
+    use crate::database::testing;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    async fn todo(
+        db: &DatabaseConnection,
+        user_id: &Uuid,
+        created_at: DateTime<Utc>,
+        completed_at: Option<DateTime<Utc>>,
+    ) {
+        let mut todo = testing::todo(db, user_id, "Counted todo")
+            .await
+            .into_active_model();
+
+        todo.created_at = Set(created_at);
+        todo.is_completed = Set(completed_at.is_some());
+        todo.completed_at = Set(completed_at);
+
+        todo.update(db).await.unwrap();
+    }
+
+    fn counts(stats: &Stats) -> Vec<(NaiveDate, i64, i64)> {
+        stats
+            .buckets
+            .iter()
+            .map(|bucket| (bucket.start, bucket.created, bucket.completed))
+            .collect()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn daily_buckets_follow_the_time_zone() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        // April 3rd 00:30 in Berlin, done April 5th 01:00.
+        todo(
+            &db,
+            &user.id,
+            at("2023-04-02T22:30:00Z"),
+            Some(at("2023-04-04T23:00:00Z")),
+        )
+        .await;
+        // April 6th in Berlin, after the range.
+        todo(&db, &user.id, at("2023-04-05T22:30:00Z"), None).await;
+
+        let stats = get_stats(
+            &db,
+            &user.id,
+            Period::Day,
+            &date("2023-04-03"),
+            &date("2023-04-05"),
+            "Europe/Berlin",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            counts(&stats),
+            [
+                (date("2023-04-03"), 1, 0),
+                (date("2023-04-04"), 0, 0),
+                (date("2023-04-05"), 0, 1),
+            ]
+        );
+
+        let utc = get_stats(
+            &db,
+            &user.id,
+            Period::Day,
+            &date("2023-04-03"),
+            &date("2023-04-05"),
+            "UTC",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            counts(&utc),
+            [
+                (date("2023-04-03"), 0, 0),
+                (date("2023-04-04"), 0, 1),
+                (date("2023-04-05"), 1, 0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn weeks_and_months_cover_the_whole_range() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        // Monday before `from`, still in its week.
+        todo(&db, &user.id, at("2023-04-03T10:00:00Z"), None).await;
+        todo(
+            &db,
+            &user.id,
+            at("2023-04-12T10:00:00Z"),
+            Some(at("2023-05-02T10:00:00Z")),
+        )
+        .await;
+
+        let weeks = get_stats(
+            &db,
+            &user.id,
+            Period::Week,
+            &date("2023-04-05"),
+            &date("2023-04-18"),
+            "UTC",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            counts(&weeks),
+            [
+                (date("2023-04-03"), 1, 0),
+                (date("2023-04-10"), 1, 0),
+                (date("2023-04-17"), 0, 0),
+            ]
+        );
+
+        let months = get_stats(
+            &db,
+            &user.id,
+            Period::Month,
+            &date("2023-03-15"),
+            &date("2023-05-02"),
+            "UTC",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            counts(&months),
+            [
+                (date("2023-03-01"), 0, 0),
+                (date("2023-04-01"), 2, 0),
+                (date("2023-05-01"), 0, 1),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn averages_completions_in_the_range() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let created_at = at("2023-04-03T08:00:00Z");
+
+        todo(
+            &db,
+            &user.id,
+            created_at,
+            Some(created_at + Duration::hours(1)),
+        )
+        .await;
+        todo(
+            &db,
+            &user.id,
+            created_at,
+            Some(created_at + Duration::hours(3)),
+        )
+        .await;
+        // Done after the range, counted as done but not averaged.
+        todo(
+            &db,
+            &user.id,
+            created_at,
+            Some(created_at + Duration::days(30)),
+        )
+        .await;
+        todo(&db, &user.id, created_at, None).await;
+
+        let stats = get_stats(
+            &db,
+            &user.id,
+            Period::Day,
+            &date("2023-04-01"),
+            &date("2023-04-07"),
+            "UTC",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(stats.open, 1);
+        assert_eq!(stats.done, 3);
+        assert_eq!(stats.average_completion_seconds, Some(2 * 3600));
+
+        let empty = get_stats(
+            &db,
+            &user.id,
+            Period::Day,
+            &date("2023-01-01"),
+            &date("2023-01-07"),
+            "UTC",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(empty.average_completion_seconds, None);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn counts_streaks_of_completion_days() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let now = Utc::now();
+
+        // Yesterday and the two days before, twice on one of them.
+        for days in [1, 2, 2, 3] {
+            let completed_at = now - Duration::days(days);
+
+            todo(&db, &user.id, completed_at, Some(completed_at)).await;
+        }
+
+        // An older, longer streak.
+        for days in 10..14 {
+            let completed_at = now - Duration::days(days);
+
+            todo(&db, &user.id, completed_at, Some(completed_at)).await;
+        }
+
+        let today = now.date_naive();
+
+        let stats = get_stats(&db, &user.id, Period::Day, &today, &today, "UTC")
+            .await
+            .unwrap();
+
+        assert_eq!(stats.current_streak, 3);
+        assert_eq!(stats.longest_streak, 4);
+
+        let other = testing::user(&db).await;
+
+        let completed_at = now - Duration::days(2);
+
+        todo(&db, &other.id, completed_at, Some(completed_at)).await;
+
+        let stats = get_stats(&db, &other.id, Period::Day, &today, &today, "UTC")
+            .await
+            .unwrap();
+
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.longest_streak, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn refuses_invalid_ranges_and_time_zones() {
+        let (db, _guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let backwards = get_stats(
+            &db,
+            &user.id,
+            Period::Day,
+            &date("2023-04-05"),
+            &date("2023-04-01"),
+            "UTC",
+        )
+        .await;
+
+        assert!(matches!(backwards, Err(ActionError::InvalidRange)));
+
+        let too_long = get_stats(
+            &db,
+            &user.id,
+            Period::Day,
+            &date("2020-01-01"),
+            &date("2023-01-01"),
+            "UTC",
+        )
+        .await;
+
+        assert!(matches!(too_long, Err(ActionError::InvalidRange)));
+
+        let unknown = get_stats(
+            &db,
+            &user.id,
+            Period::Day,
+            &date("2023-04-01"),
+            &date("2023-04-05"),
+            "Mars/Olympus_Mons",
+        )
+        .await;
+
+        assert!(matches!(unknown, Err(ActionError::InvalidTimeZone)));
+    }
+}
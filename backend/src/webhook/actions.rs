@@ -0,0 +1,813 @@
+use std::{
+    env,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use once_cell::sync;
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    IntoActiveModel, ModelTrait, Order, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        job::JobKind,
+        user, webhook,
+        webhook_delivery::{self, DeliveryStatus},
+    },
+    event::{TodoEvent, TodoEventKind},
+    job,
+};
+
+/// Consecutive failed attempts after which a webhook is disabled.
+const MAX_FAILURES: i32 = 10;
+
+/// Longest part of a response body kept in the delivery log.
+const MAX_RESPONSE_BODY: usize = 1024;
+
+/// Lets webhooks point at loopback and private addresses, for local stubs.
+/// Tests serve their stubs on loopback, so it's on there by default.
+static ALLOW_PRIVATE_URLS: sync::Lazy<bool> =
+    sync::Lazy::new(|| match env::var("WEBHOOK_ALLOW_PRIVATE_URLS") {
+        Ok(value) => value
+            .parse::<bool>()
+            .expect("Environment variable \"WEBHOOK_ALLOW_PRIVATE_URLS\" is not boolean"),
+        Err(_) => cfg!(test),
+    });
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Job: {0}")]
+    Job(#[from] job::actions::ActionError),
+    #[error("Delivery failed: {0}")]
+    Delivery(String),
+    #[error("Invalid url")]
+    InvalidUrl,
+    #[error("Url points to a private address")]
+    PrivateUrl,
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+}
+
+/// Payload of the job that sends a delivery.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeliveryJob {
+    pub delivery_id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewWebhook {
+    pub url: String,
+    pub events: Vec<TodoEventKind>,
+    pub secret: String,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct WebhookChanges {
+    pub url: Option<String>,
+    pub events: Option<Vec<TodoEventKind>>,
+    pub secret: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+/// Hex encoded HMAC-SHA256 of the body, sent as `X-Todos-Signature: sha256=<signature>`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+
+    mac.update(body);
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Whether a webhook may be delivered to the address, i.e. it is not
+/// loopback, private, link-local or otherwise reserved.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_v4(ip);
+            }
+
+            let segments = ip.segments();
+
+            // NAT64 embeds the IPv4 address in the last 32 bits.
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [.., a, b, c, d] = ip.octets();
+
+                return is_public_v4(Ipv4Addr::new(a, b, c, d));
+            }
+
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || segments[0] & 0xfe00 == 0xfc00
+                || segments[0] & 0xffc0 == 0xfe80
+                || segments[..2] == [0x2001, 0xdb8])
+        }
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        || a >= 240
+        || (a == 100 && b & 0xc0 == 64)
+        || (a == 198 && b & 0xfe == 18))
+}
+
+/// Resolves the host of a webhook url and returns it with the address to
+/// deliver to, refusing urls that resolve to any non-public address.
+async fn resolve_url(url: &str, allow_private: bool) -> Result<(String, SocketAddr), ActionError> {
+    let url = reqwest::Url::parse(url).map_err(|_| ActionError::InvalidUrl)?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ActionError::InvalidUrl);
+    }
+
+    let host = url.host_str().ok_or(ActionError::InvalidUrl)?;
+    let port = url.port_or_known_default().ok_or(ActionError::InvalidUrl)?;
+
+    // Lookup doesn't accept the brackets of IPv6 literals.
+    let addrs = tokio::net::lookup_host((host.trim_matches(['[', ']']), port))
+        .await
+        .map_err(|_| ActionError::InvalidUrl)?
+        .collect::<Vec<_>>();
+
+    if !allow_private && addrs.iter().any(|addr| !is_public(addr.ip())) {
+        return Err(ActionError::PrivateUrl);
+    }
+
+    let addr = addrs.into_iter().next().ok_or(ActionError::InvalidUrl)?;
+
+    Ok((host.to_owned(), addr))
+}
+
+fn events_json(events: &[TodoEventKind]) -> serde_json::Value {
+    serde_json::to_value(events).expect("event kinds are serializable")
+}
+
+fn subscribes_to(webhook: &webhook::Model, kind: TodoEventKind) -> bool {
+    serde_json::from_value::<Vec<TodoEventKind>>(webhook.events.clone())
+        .map(|events| events.contains(&kind))
+        .unwrap_or_default()
+}
+
+pub async fn get_webhooks(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+) -> Result<Vec<webhook::Model>, ActionError> {
+    Ok(webhook::Entity::find()
+        .filter(webhook::Column::UserId.eq(*user_id))
+        .order_by(webhook::Column::CreatedAt, Order::Asc)
+        .all(db)
+        .await?)
+}
+
+pub async fn create_webhook(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    new_webhook: &NewWebhook,
+) -> Result<webhook::Model, ActionError> {
+    user::Entity::find_by_id(*user_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    resolve_url(&new_webhook.url, *ALLOW_PRIVATE_URLS).await?;
+
+    let now = Utc::now();
+
+    let new_webhook = webhook::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id.to_owned()),
+        url: Set(new_webhook.url.clone()),
+        events: Set(events_json(&new_webhook.events)),
+        secret: Set(new_webhook.secret.clone()),
+        is_active: Set(true),
+        failure_count: Set(0),
+        created_at: Set(now),
+        updated_at: Set(now),
+    };
+
+    Ok(new_webhook.insert(db).await?)
+}
+
+pub async fn get_webhook(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<webhook::Model, ActionError> {
+    let webhook = webhook::Entity::find_by_id(*id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if webhook.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    Ok(webhook)
+}
+
+pub async fn update_webhook(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+    changes: &WebhookChanges,
+) -> Result<webhook::Model, ActionError> {
+    let mut webhook = get_webhook(db, user_id, id).await?.into_active_model();
+
+    if let Some(url) = &changes.url {
+        resolve_url(url, *ALLOW_PRIVATE_URLS).await?;
+
+        webhook.url = Set(url.to_owned());
+    }
+
+    if let Some(events) = &changes.events {
+        webhook.events = Set(events_json(events));
+    }
+
+    if let Some(secret) = &changes.secret {
+        webhook.secret = Set(secret.to_owned());
+    }
+
+    if let Some(is_active) = changes.is_active {
+        webhook.is_active = Set(is_active);
+
+        // Re-enabling a webhook gives it a fresh failure budget.
+        if is_active {
+            webhook.failure_count = Set(0);
+        }
+    }
+
+    webhook.updated_at = Set(Utc::now());
+
+    Ok(webhook.update(db).await?)
+}
+
+pub async fn delete_webhook(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<(), ActionError> {
+    get_webhook(db, user_id, id).await?.delete(db).await?;
+
+    Ok(())
+}
+
+pub async fn get_deliveries(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    webhook_id: &Uuid,
+    limit: &u64,
+    offset: &u64,
+) -> Result<Vec<webhook_delivery::Model>, ActionError> {
+    let webhook = get_webhook(db, user_id, webhook_id).await?;
+
+    Ok(webhook
+        .find_related(webhook_delivery::Entity)
+        .order_by(webhook_delivery::Column::CreatedAt, Order::Desc)
+        .limit(*limit)
+        .offset(*offset)
+        .all(db)
+        .await?)
+}
+
+pub async fn redeliver(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    webhook_id: &Uuid,
+    id: &Uuid,
+) -> Result<webhook_delivery::Model, ActionError> {
+    let webhook = get_webhook(db, user_id, webhook_id).await?;
+
+    let delivery = webhook
+        .find_related(webhook_delivery::Entity)
+        .filter(webhook_delivery::Column::Id.eq(*id))
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    let mut delivery = delivery.into_active_model();
+
+    delivery.status = Set(DeliveryStatus::Pending);
+
+    let txn = db.begin().await?;
+
+    let delivery = delivery.update(&txn).await?;
+
+    enqueue_delivery(&txn, &delivery.id).await?;
+
+    txn.commit().await?;
+
+    Ok(delivery)
+}
+
+async fn enqueue_delivery<C>(db: &C, delivery_id: &Uuid) -> Result<(), ActionError>
+where
+    C: ConnectionTrait,
+{
+    let payload = DeliveryJob {
+        delivery_id: *delivery_id,
+    };
+
+    job::actions::enqueue(
+        db,
+        JobKind::WebhookDelivery,
+        serde_json::to_value(payload).expect("delivery payload is serializable"),
+        Utc::now(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Queues a delivery of the event to every active webhook subscribed to it.
+///
+/// Every instance sees every event when the bus fans out across instances,
+/// the unique `(webhook_id, event_id)` pair makes only one of them queue it.
+pub async fn enqueue_deliveries(
+    db: &DatabaseConnection,
+    event: &TodoEvent,
+) -> Result<(), ActionError> {
+    let webhooks = webhook::Entity::find()
+        .filter(webhook::Column::UserId.eq(event.user_id))
+        .filter(webhook::Column::IsActive.eq(true))
+        .all(db)
+        .await?;
+
+    let payload = serde_json::to_value(event).expect("event is serializable");
+
+    let event_kind = payload["kind"].as_str().unwrap_or_default().to_owned();
+
+    for webhook in webhooks
+        .iter()
+        .filter(|webhook| subscribes_to(webhook, event.kind))
+    {
+        let delivery = webhook_delivery::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            webhook_id: Set(webhook.id),
+            event_id: Set(event.id),
+            event_kind: Set(event_kind.clone()),
+            payload: Set(payload.clone()),
+            status: Set(DeliveryStatus::Pending),
+            attempts: Set(0),
+            response_status: Set(None),
+            response_body: Set(None),
+            error: Set(None),
+            created_at: Set(Utc::now()),
+            delivered_at: Set(None),
+        };
+
+        let txn = db.begin().await?;
+
+        let result = webhook_delivery::Entity::insert(delivery)
+            .on_conflict(
+                OnConflict::columns([
+                    webhook_delivery::Column::WebhookId,
+                    webhook_delivery::Column::EventId,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec(&txn)
+            .await;
+
+        match result {
+            Ok(inserted) => enqueue_delivery(&txn, &inserted.last_insert_id).await?,
+            Err(DbErr::RecordNotInserted) => continue,
+            Err(err) => return Err(err.into()),
+        }
+
+        txn.commit().await?;
+    }
+
+    Ok(())
+}
+
+/// Sends a queued delivery.
+///
+/// A failed attempt is recorded and returned as an error so the job is
+/// retried with backoff; the delivery is marked as failed once the job has no
+/// attempts left.
+pub async fn deliver(
+    db: &DatabaseConnection,
+    payload: &DeliveryJob,
+    is_last_attempt: bool,
+) -> Result<(), ActionError> {
+    let Some((delivery, Some(webhook))) = webhook_delivery::Entity::find_by_id(payload.delivery_id)
+        .find_also_related(webhook::Entity)
+        .one(db)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let mut delivery = delivery.into_active_model();
+
+    delivery.attempts = Set(delivery.attempts.as_ref() + 1);
+
+    if !webhook.is_active {
+        delivery.status = Set(DeliveryStatus::Failed);
+        delivery.error = Set(Some("Webhook is disabled".to_owned()));
+
+        delivery.update(db).await?;
+
+        return Ok(());
+    }
+
+    let body = serde_json::to_vec(delivery.payload.as_ref()).expect("payload is serializable");
+
+    let signature = sign(&webhook.secret, &body);
+
+    // Resolved again for every attempt, the host may point elsewhere since
+    // the webhook was saved. The client is pinned to the checked address and
+    // doesn't follow redirects so neither can lead to a private one.
+    let result = match resolve_url(&webhook.url, *ALLOW_PRIVATE_URLS).await {
+        Ok((host, addr)) => {
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .redirect(reqwest::redirect::Policy::none())
+                .resolve(&host, addr)
+                .build()
+                .expect("Webhook client can't be built");
+
+            client
+                .post(&webhook.url)
+                .header("Content-Type", "application/json")
+                .header("User-Agent", "todos-webhooks")
+                .header("X-Todos-Event", delivery.event_kind.as_ref().as_str())
+                .header("X-Todos-Delivery", delivery.id.as_ref().to_string())
+                .header("X-Todos-Signature", format!("sha256={signature}"))
+                .body(body)
+                .send()
+                .await
+                .map_err(|err| err.to_string())
+        }
+        Err(err) => Err(err.to_string()),
+    };
+
+    let error = match result {
+        Ok(response) => {
+            let status = response.status();
+
+            let text = response.text().await.unwrap_or_default();
+
+            let text = text.chars().take(MAX_RESPONSE_BODY).collect::<String>();
+
+            delivery.response_status = Set(Some(status.as_u16().into()));
+            delivery.response_body = Set(Some(text));
+
+            (!status.is_success()).then(|| format!("Responded with {status}"))
+        }
+        Err(err) => {
+            delivery.response_status = Set(None);
+            delivery.response_body = Set(None);
+
+            Some(err)
+        }
+    };
+
+    // Counted in SQL, deliveries of the same webhook fail concurrently.
+    let webhook_update = webhook::Entity::update_many().filter(webhook::Column::Id.eq(webhook.id));
+
+    let webhook_update = match &error {
+        None => {
+            delivery.status = Set(DeliveryStatus::Succeeded);
+            delivery.error = Set(None);
+            delivery.delivered_at = Set(Some(Utc::now()));
+
+            webhook_update.col_expr(webhook::Column::FailureCount, Expr::value(0))
+        }
+        Some(error) => {
+            if is_last_attempt {
+                delivery.status = Set(DeliveryStatus::Failed);
+            }
+
+            delivery.error = Set(Some(error.clone()));
+
+            webhook_update
+                .col_expr(
+                    webhook::Column::FailureCount,
+                    Expr::col(webhook::Column::FailureCount).add(1),
+                )
+                .col_expr(
+                    webhook::Column::IsActive,
+                    Expr::cust_with_values("is_active AND failure_count + 1 < $1", [MAX_FAILURES]),
+                )
+        }
+    };
+
+    let txn = db.begin().await?;
+
+    delivery.update(&txn).await?;
+
+    webhook_update.exec(&txn).await?;
+
+    txn.commit().await?;
+
+    match error {
+        Some(error) => Err(ActionError::Delivery(error)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::VecDeque,
+        net::{Ipv6Addr, TcpListener},
+        sync::{Arc, Mutex},
+    };
+
+    use axum::{
+        body::Bytes,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Extension, Router,
+    };
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{
+        database::testing,
+        entities::job::{self as job_entity, JobStatus},
+        job::scheduler,
+    };
+
+    #[derive(Clone, Default)]
+    struct Stub {
+        statuses: Arc<Mutex<VecDeque<StatusCode>>>,
+        received: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+    }
+
+    async fn receive(
+        Extension(stub): Extension<Stub>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        stub.received.lock().unwrap().push((headers, body));
+
+        stub.statuses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or(StatusCode::OK)
+    }
+
+    /// Serves the stub on a free local port and answers with `statuses` in
+    /// order, then with 200.
+    fn serve(statuses: &[StatusCode]) -> (Stub, SocketAddr) {
+        let stub = Stub::default();
+
+        stub.statuses.lock().unwrap().extend(statuses);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+
+        let addr = listener.local_addr().unwrap();
+
+        let app = Router::new()
+            .route("/hook", post(receive))
+            .layer(Extension(stub.clone()));
+
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service()),
+        );
+
+        (stub, addr)
+    }
+
+    async fn setup(addr: SocketAddr) -> (DatabaseConnection, testing::Guard, webhook::Model) {
+        let (db, guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let webhook = create_webhook(
+            &db,
+            &user.id,
+            &NewWebhook {
+                url: format!("http://{addr}/hook"),
+                events: vec![TodoEventKind::Deleted],
+                secret: "secret".to_owned(),
+            },
+        )
+        .await
+        .unwrap();
+
+        (db, guard, webhook)
+    }
+
+    async fn delivery_job(db: &DatabaseConnection) -> job_entity::Model {
+        job_entity::Entity::find()
+            .filter(job_entity::Column::Kind.eq(JobKind::WebhookDelivery))
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    async fn make_due(db: &DatabaseConnection, job: job_entity::Model) {
+        let mut job = job.into_active_model();
+
+        job.run_at = Set(Utc::now());
+
+        job.update(db).await.unwrap();
+    }
+
+    #[test]
+    fn sign_matches_known_vector() {
+        assert_eq!(
+            sign("key", b"The quick brown fox jumps over the lazy dog"),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn only_public_addresses_are_public() {
+        let private = [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "100.64.0.1",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+        ];
+
+        for ip in private {
+            assert!(!is_public(ip.parse().unwrap()), "{ip}");
+        }
+
+        assert!(is_public(IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34))));
+        assert!(is_public(IpAddr::V6(Ipv6Addr::new(
+            0x2606, 0x2800, 0x220, 0x1, 0, 0, 0, 0x1
+        ))));
+    }
+
+    #[tokio::test]
+    async fn private_urls_are_refused() {
+        for url in [
+            "http://127.0.0.1:9000/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/hook",
+            "http://localhost/hook",
+        ] {
+            assert!(
+                matches!(resolve_url(url, false).await, Err(ActionError::PrivateUrl)),
+                "{url}"
+            );
+        }
+
+        assert!(matches!(
+            resolve_url("ftp://93.184.216.34/", false).await,
+            Err(ActionError::InvalidUrl)
+        ));
+
+        let (host, addr) = resolve_url("http://127.0.0.1:9000/hook", true)
+            .await
+            .unwrap();
+
+        assert_eq!(host, "127.0.0.1");
+        assert_eq!(addr, "127.0.0.1:9000".parse().unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn delivery_is_signed_and_retried_with_backoff() {
+        let (stub, addr) = serve(&[StatusCode::INTERNAL_SERVER_ERROR]);
+
+        let (db, _guard, webhook) = setup(addr).await;
+
+        let event = TodoEvent::deleted(webhook.user_id, Uuid::new_v4());
+
+        enqueue_deliveries(&db, &event).await.unwrap();
+
+        scheduler::run_due(&db).await.unwrap();
+
+        let job = delivery_job(&db).await;
+
+        assert_eq!(job.status, JobStatus::Pending);
+        assert_eq!(job.attempts, 1);
+        assert!(job.run_at > Utc::now() + Duration::seconds(50));
+
+        let webhook = webhook::Entity::find_by_id(webhook.id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(webhook.failure_count, 1);
+
+        make_due(&db, job).await;
+
+        scheduler::run_due(&db).await.unwrap();
+
+        assert_eq!(delivery_job(&db).await.status, JobStatus::Done);
+
+        let delivery = webhook_delivery::Entity::find()
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(delivery.status, DeliveryStatus::Succeeded);
+        assert_eq!(delivery.attempts, 2);
+
+        let webhook = webhook::Entity::find_by_id(webhook.id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(webhook.failure_count, 0);
+
+        let received = stub.received.lock().unwrap();
+
+        assert_eq!(received.len(), 2);
+
+        for (headers, body) in received.iter() {
+            assert_eq!(
+                headers["X-Todos-Signature"],
+                format!("sha256={}", sign("secret", body))
+            );
+            assert_eq!(headers["X-Todos-Event"], "deleted");
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn webhook_is_disabled_after_max_failures() {
+        let (_stub, addr) = serve(&[StatusCode::INTERNAL_SERVER_ERROR; 2]);
+
+        let (db, _guard, webhook) = setup(addr).await;
+
+        let mut almost_failed = webhook.clone().into_active_model();
+
+        almost_failed.failure_count = Set(MAX_FAILURES - 2);
+
+        almost_failed.update(&db).await.unwrap();
+
+        for _ in 0..2 {
+            let event = TodoEvent::deleted(webhook.user_id, Uuid::new_v4());
+
+            enqueue_deliveries(&db, &event).await.unwrap();
+        }
+
+        scheduler::run_due(&db).await.unwrap();
+
+        let webhook = webhook::Entity::find_by_id(webhook.id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(webhook.failure_count, MAX_FAILURES);
+        assert!(!webhook.is_active);
+
+        // Later events aren't queued for the disabled webhook.
+        let event = TodoEvent::deleted(webhook.user_id, Uuid::new_v4());
+
+        enqueue_deliveries(&db, &event).await.unwrap();
+
+        assert_eq!(
+            webhook_delivery::Entity::find()
+                .all(&db)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,28 @@
+use sea_orm::DatabaseConnection;
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+
+use super::actions;
+use crate::event::Events;
+
+/// Starts the background task that turns todo events into webhook deliveries.
+pub fn spawn(db: DatabaseConnection, events: Events) -> JoinHandle<()> {
+    let mut receiver = events.subscribe();
+
+    tokio::spawn(async move {
+        loop {
+            let event = match receiver.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Webhook dispatcher skipped {} events", skipped);
+
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+
+            if let Err(err) = actions::enqueue_deliveries(&db, &event).await {
+                tracing::error!("Webhook dispatcher: {}", err);
+            }
+        }
+    })
+}
@@ -0,0 +1,605 @@
+use chrono::Utc;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, ModelTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+use serde::Serialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    entities::{
+        project,
+        status::{self, StatusCategory},
+        todo,
+    },
+    event::{EventBus, TodoEvent, TodoEventKind},
+};
+
+/// Statuses of new projects.
+const DEFAULT_WORKFLOW: [(&str, StatusCategory); 4] = [
+    ("Backlog", StatusCategory::Todo),
+    ("In progress", StatusCategory::InProgress),
+    ("Review", StatusCategory::InProgress),
+    ("Done", StatusCategory::Done),
+];
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error("DbErr: {0}")]
+    Db(#[from] DbErr),
+    #[error("Forbidden")]
+    Forbidden,
+    #[error("Not Found")]
+    NotFound,
+    #[error("Status of another project")]
+    InvalidStatus,
+    #[error("Workflow needs a todo and a done status")]
+    IncompleteWorkflow,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewStatus {
+    pub name: String,
+    pub category: StatusCategory,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct StatusChanges {
+    pub name: Option<String>,
+    pub category: Option<StatusCategory>,
+    pub position: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Column {
+    pub status: status::Model,
+    pub todos: Vec<todo::Model>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Board {
+    pub project: project::Model,
+    pub columns: Vec<Column>,
+}
+
+async fn get_owned_project(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: &Uuid,
+) -> Result<project::Model, ActionError> {
+    let project = project::Entity::find_by_id(*project_id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if project.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    Ok(project)
+}
+
+/// Column of todos that have no status, or whose status disagrees with
+/// `is_completed` after they were completed or reverted elsewhere: the first
+/// done status for completed todos and the first todo status otherwise.
+fn default_status<'a>(
+    statuses: &'a [status::Model],
+    todo: &todo::Model,
+) -> Option<&'a status::Model> {
+    let category = match todo.is_completed {
+        true => StatusCategory::Done,
+        false => StatusCategory::Todo,
+    };
+
+    statuses
+        .iter()
+        .find(|status| status.category == category)
+        .or_else(|| statuses.first())
+}
+
+fn is_complete(statuses: &[status::Model]) -> bool {
+    statuses
+        .iter()
+        .any(|status| status.category == StatusCategory::Todo)
+        && statuses
+            .iter()
+            .any(|status| status.category == StatusCategory::Done)
+}
+
+/// Adds the default workflow to a new project.
+pub async fn create_default_statuses<C>(db: &C, project_id: &Uuid) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+{
+    let now = Utc::now();
+
+    for (position, (name, category)) in DEFAULT_WORKFLOW.iter().enumerate() {
+        status::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            project_id: Set(*project_id),
+            name: Set(name.to_string()),
+            category: Set(*category),
+            position: Set(position as i32),
+            created_at: Set(now),
+        }
+        .insert(db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn get_statuses(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: &Uuid,
+) -> Result<Vec<status::Model>, ActionError> {
+    let project = get_owned_project(db, user_id, project_id).await?;
+
+    Ok(project
+        .find_related(status::Entity)
+        .order_by(status::Column::Position, Order::Asc)
+        .order_by(status::Column::CreatedAt, Order::Asc)
+        .all(db)
+        .await?)
+}
+
+pub async fn create_status(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: &Uuid,
+    new_status: &NewStatus,
+) -> Result<status::Model, ActionError> {
+    let project = get_owned_project(db, user_id, project_id).await?;
+
+    let position = project.find_related(status::Entity).count(db).await?;
+
+    let new_status = status::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        project_id: Set(project.id),
+        name: Set(new_status.name.clone()),
+        category: Set(new_status.category),
+        position: Set(position as i32),
+        created_at: Set(Utc::now()),
+    };
+
+    Ok(new_status.insert(db).await?)
+}
+
+pub async fn get_status(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<status::Model, ActionError> {
+    let (status, project) = status::Entity::find_by_id(*id)
+        .find_also_related(project::Entity)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    match project {
+        Some(project) if project.user_id == *user_id => Ok(status),
+        _ => Err(ActionError::Forbidden),
+    }
+}
+
+/// Changing the category from or to done completes or reverts the todos in
+/// the status, publishing an event for each like `transition_todo`.
+pub async fn update_status(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    user_id: &Uuid,
+    id: &Uuid,
+    changes: &StatusChanges,
+) -> Result<status::Model, ActionError> {
+    let status = get_status(db, user_id, id).await?;
+
+    if let Some(category) = changes.category {
+        let mut statuses = get_statuses(db, user_id, &status.project_id).await?;
+
+        for other in statuses.iter_mut().filter(|other| other.id == status.id) {
+            other.category = category;
+        }
+
+        if !is_complete(&statuses) {
+            return Err(ActionError::IncompleteWorkflow);
+        }
+    }
+
+    let was_done = status.category == StatusCategory::Done;
+
+    let mut status = status.into_active_model();
+
+    if let Some(name) = &changes.name {
+        status.name = Set(name.to_owned());
+    }
+
+    if let Some(category) = changes.category {
+        status.category = Set(category);
+    }
+
+    if let Some(position) = changes.position {
+        status.position = Set(position);
+    }
+
+    let txn = db.begin().await?;
+
+    let status = status.update(&txn).await?;
+
+    let is_done = status.category == StatusCategory::Done;
+
+    let mut changed = Vec::new();
+
+    if was_done != is_done {
+        // Todos already completed or reverted elsewhere keep their state.
+        let ids = todo::Entity::find()
+            .filter(todo::Column::StatusId.eq(status.id))
+            .filter(todo::Column::IsCompleted.eq(!is_done))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|todo| todo.id)
+            .collect::<Vec<_>>();
+
+        let now = Utc::now();
+
+        todo::Entity::update_many()
+            .col_expr(todo::Column::IsCompleted, Expr::value(is_done))
+            .col_expr(
+                todo::Column::CompletedAt,
+                Expr::value(is_done.then_some(now)),
+            )
+            .col_expr(todo::Column::UpdatedAt, Expr::value(now))
+            .filter(todo::Column::Id.is_in(ids.clone()))
+            .exec(&txn)
+            .await?;
+
+        changed = todo::Entity::find()
+            .filter(todo::Column::Id.is_in(ids))
+            .all(&txn)
+            .await?;
+    }
+
+    txn.commit().await?;
+
+    let kind = match is_done {
+        true => TodoEventKind::Completed,
+        false => TodoEventKind::Reverted,
+    };
+
+    for todo in &changed {
+        events.publish(TodoEvent::new(kind, todo)).await;
+    }
+
+    Ok(status)
+}
+
+/// Todos in the status fall back to the default column.
+pub async fn delete_status(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    id: &Uuid,
+) -> Result<(), ActionError> {
+    let status = get_status(db, user_id, id).await?;
+
+    let statuses = get_statuses(db, user_id, &status.project_id)
+        .await?
+        .into_iter()
+        .filter(|other| other.id != status.id)
+        .collect::<Vec<_>>();
+
+    if !is_complete(&statuses) {
+        return Err(ActionError::IncompleteWorkflow);
+    }
+
+    status.delete(db).await?;
+
+    Ok(())
+}
+
+pub async fn get_board(
+    db: &DatabaseConnection,
+    user_id: &Uuid,
+    project_id: &Uuid,
+) -> Result<Board, ActionError> {
+    let project = get_owned_project(db, user_id, project_id).await?;
+
+    let statuses = get_statuses(db, user_id, project_id).await?;
+
+    let todos = project
+        .find_related(todo::Entity)
+        .order_by(todo::Column::UpdatedAt, Order::Desc)
+        .all(db)
+        .await?;
+
+    let mut columns = statuses
+        .iter()
+        .map(|status| Column {
+            status: status.clone(),
+            todos: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+
+    for todo in todos {
+        let status = statuses
+            .iter()
+            .find(|status| {
+                Some(status.id) == todo.status_id
+                    && (status.category == StatusCategory::Done) == todo.is_completed
+            })
+            .or_else(|| default_status(&statuses, &todo));
+
+        let Some(status) = status else {
+            continue;
+        };
+
+        if let Some(column) = columns
+            .iter_mut()
+            .find(|column| column.status.id == status.id)
+        {
+            column.todos.push(todo);
+        }
+    }
+
+    Ok(Board { project, columns })
+}
+
+/// Moves a todo to a status of its project, completing it when the status is
+/// done and reverting it when it leaves one.
+pub async fn transition_todo(
+    db: &DatabaseConnection,
+    events: &dyn EventBus,
+    user_id: &Uuid,
+    id: &Uuid,
+    status_id: &Uuid,
+) -> Result<todo::Model, ActionError> {
+    let todo = todo::Entity::find_by_id(*id)
+        .one(db)
+        .await?
+        .ok_or(ActionError::NotFound)?;
+
+    if todo.user_id != *user_id {
+        return Err(ActionError::Forbidden);
+    }
+
+    let status = get_status(db, user_id, status_id).await?;
+
+    if todo.project_id != Some(status.project_id) {
+        return Err(ActionError::InvalidStatus);
+    }
+
+    let is_completed = status.category == StatusCategory::Done;
+
+    let kind = match (todo.is_completed, is_completed) {
+        (false, true) => TodoEventKind::Completed,
+        (true, false) => TodoEventKind::Reverted,
+        _ => TodoEventKind::Updated,
+    };
+
+    let now = Utc::now();
+
+    let completed_at = match is_completed {
+        true => todo.completed_at.or(Some(now)),
+        false => None,
+    };
+
+    let mut todo = todo.into_active_model();
+
+    todo.status_id = Set(Some(status.id));
+    todo.is_completed = Set(is_completed);
+    todo.completed_at = Set(completed_at);
+    todo.updated_at = Set(now);
+
+    let todo = todo.update(db).await?;
+
+    events.publish(TodoEvent::new(kind, &todo)).await;
+
+    Ok(todo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        database::testing, event::memory::MemoryEventBus, project::actions::create_project,
+    };
+
+    /// A project with the default workflow and a todo in it.
+    async fn setup() -> (
+        DatabaseConnection,
+        testing::Guard,
+        Uuid,
+        Vec<status::Model>,
+        todo::Model,
+    ) {
+        let (db, guard) = testing::db().await;
+
+        let user = testing::user(&db).await;
+
+        let project = create_project(&db, &user.id, "Home").await.unwrap();
+
+        let statuses = get_statuses(&db, &user.id, &project.id).await.unwrap();
+
+        let mut todo = testing::todo(&db, &user.id, "Water the plants")
+            .await
+            .into_active_model();
+
+        todo.project_id = Set(Some(project.id));
+
+        let todo = todo.update(&db).await.unwrap();
+
+        (db, guard, user.id, statuses, todo)
+    }
+
+    fn by_name<'a>(statuses: &'a [status::Model], name: &str) -> &'a status::Model {
+        statuses.iter().find(|status| status.name == name).unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn workflow_keeps_a_todo_and_a_done_status() {
+        let (db, _guard, user_id, statuses, _) = setup().await;
+
+        let events = MemoryEventBus::new();
+
+        let done = by_name(&statuses, "Done");
+
+        let result = update_status(
+            &db,
+            &events,
+            &user_id,
+            &done.id,
+            &StatusChanges {
+                category: Some(StatusCategory::InProgress),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(ActionError::IncompleteWorkflow)));
+
+        let result = delete_status(&db, &user_id, &by_name(&statuses, "Backlog").id).await;
+
+        assert!(matches!(result, Err(ActionError::IncompleteWorkflow)));
+
+        delete_status(&db, &user_id, &by_name(&statuses, "Review").id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_statuses(&db, &user_id, &done.project_id)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn changing_the_category_completes_and_reverts_with_events() {
+        let (db, _guard, user_id, statuses, todo) = setup().await;
+
+        let events = MemoryEventBus::new();
+
+        let review = by_name(&statuses, "Review");
+
+        transition_todo(&db, &events, &user_id, &todo.id, &review.id)
+            .await
+            .unwrap();
+
+        let mut receiver = events.subscribe();
+
+        let category = |category| StatusChanges {
+            category: Some(category),
+            ..Default::default()
+        };
+
+        update_status(
+            &db,
+            &events,
+            &user_id,
+            &review.id,
+            &category(StatusCategory::Done),
+        )
+        .await
+        .unwrap();
+
+        let event = receiver.try_recv().unwrap();
+
+        assert_eq!(event.kind, TodoEventKind::Completed);
+        assert_eq!(event.todo_id, todo.id);
+        assert!(event.todo.as_ref().unwrap().is_completed);
+        assert!(event.todo.unwrap().completed_at.is_some());
+
+        // Renaming and reordering within done changes no todo.
+        update_status(
+            &db,
+            &events,
+            &user_id,
+            &review.id,
+            &StatusChanges {
+                name: Some("Shipped".to_owned()),
+                position: Some(5),
+                category: Some(StatusCategory::Done),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(receiver.try_recv().is_err());
+
+        update_status(
+            &db,
+            &events,
+            &user_id,
+            &review.id,
+            &category(StatusCategory::InProgress),
+        )
+        .await
+        .unwrap();
+
+        let event = receiver.try_recv().unwrap();
+
+        assert_eq!(event.kind, TodoEventKind::Reverted);
+        assert!(!event.todo.as_ref().unwrap().is_completed);
+        assert_eq!(event.todo.unwrap().completed_at, None);
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs TEST_DATABASE_URL"]
+    async fn board_puts_todos_with_a_stale_status_in_the_default_column() {
+        let (db, _guard, user_id, statuses, todo) = setup().await;
+
+        let events = MemoryEventBus::new();
+
+        let column = |board: &Board, id: &Uuid| {
+            board
+                .columns
+                .iter()
+                .find(|column| column.todos.iter().any(|todo| todo.id == *id))
+                .map(|column| column.status.name.clone())
+        };
+
+        let board = get_board(&db, &user_id, &todo.project_id.unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(column(&board, &todo.id).as_deref(), Some("Backlog"));
+
+        let review = by_name(&statuses, "Review");
+
+        transition_todo(&db, &events, &user_id, &todo.id, &review.id)
+            .await
+            .unwrap();
+
+        // Completed elsewhere, e.g. from the list, without a transition.
+        let mut completed = todo::Entity::find_by_id(todo.id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into_active_model();
+
+        completed.is_completed = Set(true);
+
+        completed.update(&db).await.unwrap();
+
+        let board = get_board(&db, &user_id, &todo.project_id.unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(column(&board, &todo.id).as_deref(), Some("Done"));
+        assert_eq!(
+            board
+                .columns
+                .iter()
+                .map(|column| column.status.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Backlog", "In progress", "Review", "Done"]
+        );
+    }
+}
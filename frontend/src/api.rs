@@ -6,12 +6,22 @@ use yew::prelude::*;
 use yewdux::prelude::*;
 
 use self::{
-    error::ApiError,
-    types::{Credentials, NewTodo, Paginated, RenameTodo, Todo, TodosDeleteQuery, TodosQuery},
+    error::{ApiError, FieldError},
+    types::{
+        AccountDeletion, Board, CalendarQuery, Credentials, CustomField, DueDateChange,
+        ExportQuery, ImportPreview, Imported, InstantiateTemplate, NewImport, NewStatus, NewTodo,
+        Notification, Notifications, Paginated, PasswordChange, Preferences, Project, RenameTodo,
+        Stats, StatsQuery, Status, StreamTicket, Template, TimeEntry, Todo, TodoFieldsChange,
+        TodosDeleteQuery, TodosQuery, Transition, UpdateStatus,
+    },
+};
+use crate::{
+    i18n,
+    store::{Action, Store, Token, User},
 };
-use crate::store::{Action, Store, Token, User};
 
 pub mod error;
+pub mod events;
 pub mod ext;
 pub mod types;
 
@@ -46,42 +56,63 @@ impl Api {
     impl_methods!(
         (get, get_with_auth),
         (post, post_with_auth),
+        (put, put_with_auth),
         (patch, patch_with_auth),
         (delete, delete_with_auth),
     );
 
-    async fn try_auth(&self, builder: RequestBuilder) -> ApiResult<RequestBuilder> {
+    async fn access_token(&self) -> ApiResult<String> {
         let token = &self
             .token
             .as_ref()
             .ok_or_else(|| ApiError::Unauthorized("Unauthorized".to_string()))?;
 
         if !token.access_claims.as_ref().unwrap().is_expired() {
-            Ok(builder.bearer_auth(&token.access))
+            Ok(token.access.clone())
         } else if !token.refresh_claims.as_ref().unwrap().is_expired() {
             let token = self.sign_refresh().await?;
 
             Store::dispatch(Action::SetToken(Some(token.clone())));
 
-            Ok(builder.bearer_auth(&token.access))
+            Ok(token.access)
         } else {
             Err(ApiError::TokenExpired)
         }
     }
 
+    async fn try_auth(&self, builder: RequestBuilder) -> ApiResult<RequestBuilder> {
+        Ok(builder.bearer_auth(self.access_token().await?))
+    }
+
+    /// Text of an error response, translated by its `code` when the backend
+    /// sent one.
+    async fn error_message(response: Response) -> ApiResult<String> {
+        let text = response.text().await?;
+
+        Ok(match serde_json::from_str::<FieldError>(&text) {
+            Ok(error) => i18n::field_error(&error),
+            Err(_) => text,
+        })
+    }
+
     async fn json<T>(response: Response) -> ApiResult<T>
     where
         T: DeserializeOwned,
     {
         match response.status() {
             StatusCode::OK | StatusCode::CREATED => Ok(response.json::<T>().await?),
-            StatusCode::BAD_REQUEST => Err(ApiError::BadRequest(response.text().await?)),
-            StatusCode::UNAUTHORIZED => Err(ApiError::Unauthorized(response.text().await?)),
-            StatusCode::FORBIDDEN => Err(ApiError::Forbidden(response.text().await?)),
+            StatusCode::BAD_REQUEST => {
+                Err(ApiError::BadRequest(Api::error_message(response).await?))
+            }
+            StatusCode::UNAUTHORIZED => {
+                Err(ApiError::Unauthorized(Api::error_message(response).await?))
+            }
+            StatusCode::FORBIDDEN => Err(ApiError::Forbidden(Api::error_message(response).await?)),
+            StatusCode::NOT_FOUND => Err(ApiError::NotFound(Api::error_message(response).await?)),
             StatusCode::UNPROCESSABLE_ENTITY => Err(ApiError::UnprocessableEntity(
                 response.json::<Value>().await?,
             )),
-            _ => Err(ApiError::Reqwest(response.text().await?)),
+            _ => Err(ApiError::Reqwest(Api::error_message(response).await?)),
         }
     }
 
@@ -90,22 +121,110 @@ impl Api {
             StatusCode::OK | StatusCode::CREATED | StatusCode::NO_CONTENT => {
                 Ok(response.text().await?)
             }
-            StatusCode::BAD_REQUEST => Err(ApiError::BadRequest(response.text().await?)),
-            StatusCode::UNAUTHORIZED => Err(ApiError::Unauthorized(response.text().await?)),
-            StatusCode::FORBIDDEN => Err(ApiError::Forbidden(response.text().await?)),
+            StatusCode::BAD_REQUEST => {
+                Err(ApiError::BadRequest(Api::error_message(response).await?))
+            }
+            StatusCode::UNAUTHORIZED => {
+                Err(ApiError::Unauthorized(Api::error_message(response).await?))
+            }
+            StatusCode::FORBIDDEN => Err(ApiError::Forbidden(Api::error_message(response).await?)),
+            StatusCode::NOT_FOUND => Err(ApiError::NotFound(Api::error_message(response).await?)),
             StatusCode::UNPROCESSABLE_ENTITY => Err(ApiError::UnprocessableEntity(
                 response.json::<Value>().await?,
             )),
-            _ => Err(ApiError::Reqwest(response.text().await?)),
+            _ => Err(ApiError::Reqwest(Api::error_message(response).await?)),
         }
     }
 
+    /// URL of the event stream with a single-use ticket, since `EventSource`
+    /// can't send headers.
+    pub async fn events_url(&self) -> ApiResult<String> {
+        let response = self.post_with_auth("/events/tickets").await?.send().await?;
+
+        let StreamTicket { ticket } = Api::json(response).await?;
+
+        Ok(format!("{BASE_URL}/events?ticket={ticket}"))
+    }
+
+    /// Download URL of an export with a single-use ticket, so it can be
+    /// opened by the browser directly.
+    pub async fn export_url(&self, query: ExportQuery) -> ApiResult<String> {
+        let response = self.post_with_auth("/events/tickets").await?.send().await?;
+
+        let StreamTicket { ticket } = Api::json(response).await?;
+
+        let request = self
+            .get("/export")
+            .query(&query)
+            .query(&[("ticket", ticket)])
+            .build()?;
+
+        Ok(request.url().to_string())
+    }
+
+    /// Download URL of the account archive with a single-use ticket like
+    /// exports.
+    pub async fn archive_url(&self) -> ApiResult<String> {
+        let response = self.post_with_auth("/events/tickets").await?.send().await?;
+
+        let StreamTicket { ticket } = Api::json(response).await?;
+
+        Ok(format!("{BASE_URL}/profile/archive?ticket={ticket}"))
+    }
+
     pub async fn profile(&self) -> ApiResult<User> {
         let response = self.get_with_auth("/profile").await?.send().await?;
 
         Api::json(response).await
     }
 
+    pub async fn delete_account(&self, payload: AccountDeletion) -> ApiResult<()> {
+        let response = self
+            .delete_with_auth("/profile")
+            .await?
+            .json(&payload)
+            .send()
+            .await?;
+
+        Api::text(response).await?;
+
+        Ok(())
+    }
+
+    pub async fn change_password(&self, payload: PasswordChange) -> ApiResult<()> {
+        let response = self
+            .post_with_auth("/change-password")
+            .await?
+            .json(&payload)
+            .send()
+            .await?;
+
+        Api::text(response).await?;
+
+        Ok(())
+    }
+
+    pub async fn preferences(&self) -> ApiResult<Preferences> {
+        let response = self
+            .get_with_auth("/profile/preferences")
+            .await?
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn update_preferences(&self, preferences: Preferences) -> ApiResult<Preferences> {
+        let response = self
+            .put_with_auth("/profile/preferences")
+            .await?
+            .json(&preferences)
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
     pub async fn sign_in(&self, credentials: Credentials) -> ApiResult<Token> {
         let response = self.post("/sign-in").json(&credentials).send().await?;
 
@@ -186,6 +305,39 @@ impl Api {
         Api::json(response).await
     }
 
+    pub async fn update_todo_fields(&self, id: Uuid, change: TodoFieldsChange) -> ApiResult<Todo> {
+        let response = self
+            .patch_with_auth(&format!("/todos/{id}"))
+            .await?
+            .json(&change)
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn update_todo_due_date(&self, id: Uuid, change: DueDateChange) -> ApiResult<Todo> {
+        let response = self
+            .patch_with_auth(&format!("/todos/{id}"))
+            .await?
+            .json(&change)
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn calendar_todos(&self, query: CalendarQuery) -> ApiResult<Vec<Todo>> {
+        let response = self
+            .get_with_auth("/todos/calendar")
+            .await?
+            .query(&query)
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
     pub async fn delete_todo(&self, id: Uuid) -> ApiResult<()> {
         self.delete_with_auth(&format!("/todos/{id}"))
             .await?
@@ -214,6 +366,183 @@ impl Api {
 
         Api::json(response).await
     }
+
+    pub async fn projects(&self) -> ApiResult<Vec<Project>> {
+        let response = self.get_with_auth("/projects").await?.send().await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn board(&self, project_id: Uuid) -> ApiResult<Board> {
+        let response = self
+            .get_with_auth(&format!("/projects/{project_id}/board"))
+            .await?
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn create_status(&self, project_id: Uuid, status: NewStatus) -> ApiResult<Status> {
+        let response = self
+            .post_with_auth(&format!("/projects/{project_id}/statuses"))
+            .await?
+            .json(&status)
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn update_status(&self, id: Uuid, changes: UpdateStatus) -> ApiResult<Status> {
+        let response = self
+            .patch_with_auth(&format!("/statuses/{id}"))
+            .await?
+            .json(&changes)
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn delete_status(&self, id: Uuid) -> ApiResult<String> {
+        let response = self
+            .delete_with_auth(&format!("/statuses/{id}"))
+            .await?
+            .send()
+            .await?;
+
+        Api::text(response).await
+    }
+
+    pub async fn transition_todo(&self, id: Uuid, transition: Transition) -> ApiResult<Todo> {
+        let response = self
+            .post_with_auth(&format!("/todos/{id}/status"))
+            .await?
+            .json(&transition)
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn project_fields(&self, project_id: Uuid) -> ApiResult<Vec<CustomField>> {
+        let response = self
+            .get_with_auth(&format!("/projects/{project_id}/fields"))
+            .await?
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn templates(&self) -> ApiResult<Vec<Template>> {
+        let response = self.get_with_auth("/templates").await?.send().await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn instantiate_template(
+        &self,
+        id: Uuid,
+        body: InstantiateTemplate,
+    ) -> ApiResult<Vec<Todo>> {
+        let response = self
+            .post_with_auth(&format!("/templates/{id}/instantiate"))
+            .await?
+            .json(&body)
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn timer(&self) -> ApiResult<Option<TimeEntry>> {
+        let response = self.get_with_auth("/timer").await?.send().await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn start_timer(&self, todo_id: Uuid) -> ApiResult<TimeEntry> {
+        let response = self
+            .post_with_auth(&format!("/todos/{todo_id}/timer/start"))
+            .await?
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn stop_timer(&self) -> ApiResult<TimeEntry> {
+        let response = self.post_with_auth("/timer/stop").await?.send().await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn notifications(&self) -> ApiResult<Notifications> {
+        let response = self.get_with_auth("/notifications").await?.send().await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn mark_notification_read(&self, id: Uuid) -> ApiResult<Notification> {
+        let response = self
+            .post_with_auth(&format!("/notifications/{id}/read"))
+            .await?
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn mark_all_notifications_read(&self) -> ApiResult<String> {
+        let response = self
+            .post_with_auth("/notifications/read")
+            .await?
+            .send()
+            .await?;
+
+        Api::text(response).await
+    }
+
+    pub async fn preview_import(&self, import: NewImport) -> ApiResult<ImportPreview> {
+        let response = self
+            .post_with_auth("/import")
+            .await?
+            .json(&NewImport {
+                dry_run: true,
+                ..import
+            })
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn import(&self, import: NewImport) -> ApiResult<Imported> {
+        let response = self
+            .post_with_auth("/import")
+            .await?
+            .json(&NewImport {
+                dry_run: false,
+                ..import
+            })
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
+
+    pub async fn stats(&self, query: StatsQuery) -> ApiResult<Stats> {
+        let response = self
+            .get_with_auth("/stats")
+            .await?
+            .query(&query)
+            .send()
+            .await?;
+
+        Api::json(response).await
+    }
 }
 
 #[hook]
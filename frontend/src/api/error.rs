@@ -1,12 +1,17 @@
+use std::collections::HashMap;
+
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::Value;
 use thiserror::Error;
 
-use crate::store::{Action, Store};
+use crate::{
+    i18n::{self, t},
+    store::{Action, Store},
+};
 
 #[derive(Debug, Clone, Error)]
 pub enum ApiError {
-    #[error("TokenExpired")]
+    #[error("{}", t("errors.token_expired"))]
     TokenExpired,
     #[error("{0}")]
     BadRequest(String),
@@ -14,10 +19,15 @@ pub enum ApiError {
     Unauthorized(String),
     #[error("{0}")]
     Forbidden(String),
-    #[error("UnprocessableEntity")]
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{}", t("errors.unprocessable_entity"))]
     UnprocessableEntity(Value),
     #[error("{0}")]
     Reqwest(String),
+    /// The request didn't reach the server.
+    #[error("{}", i18n::t_args("errors.network", &[("message", .0)]))]
+    Network(String),
 }
 
 impl ApiError {
@@ -41,11 +51,21 @@ impl ApiError {
 
 impl From<reqwest::Error> for ApiError {
     fn from(value: reqwest::Error) -> Self {
-        ApiError::Reqwest(value.to_string())
+        if value.is_request() {
+            ApiError::Network(value.to_string())
+        } else {
+            ApiError::Reqwest(value.to_string())
+        }
     }
 }
 
+/// A validation or response error of the backend, `code` is stable and
+/// translated with `params`, `message` is the English text.
 #[derive(Debug, Clone, Deserialize)]
 pub struct FieldError {
+    #[serde(default)]
+    pub code: String,
     pub message: String,
+    #[serde(default)]
+    pub params: HashMap<String, Value>,
 }
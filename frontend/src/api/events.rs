@@ -0,0 +1,120 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use gloo_timers::callback::Timeout;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{EventSource, MessageEvent};
+use yew::{platform::spawn_local, prelude::*};
+
+use super::{types::TodoEvent, use_api};
+
+const RECONNECT_MILLIS: u32 = 5_000;
+
+struct Subscription {
+    source: EventSource,
+    _on_todo: Closure<dyn FnMut(MessageEvent)>,
+    _on_error: Closure<dyn FnMut(Event)>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.source.close();
+    }
+}
+
+/// Subscribes to the todo events of the signed in user.
+///
+/// Tickets work once, so the browser's own retries of a dropped connection
+/// are refused and close the stream. It is then reopened with a fresh ticket.
+#[hook]
+pub fn use_events(on_event: Callback<TodoEvent>) {
+    let api = use_api();
+
+    let attempt_handle = use_state(|| 0_u32);
+
+    let is_signed_in = api.token.is_some();
+
+    let attempt = *attempt_handle;
+
+    use_effect_with_deps(
+        move |(_, is_signed_in)| {
+            let subscription = Rc::new(RefCell::new(None::<Subscription>));
+
+            let reconnect = Rc::new(RefCell::new(None::<Timeout>));
+
+            let is_cancelled = Rc::new(Cell::new(false));
+
+            if *is_signed_in {
+                let subscription = subscription.clone();
+
+                let reconnect = reconnect.clone();
+
+                let is_cancelled = is_cancelled.clone();
+
+                spawn_local(async move {
+                    let Ok(url) = api.events_url().await else {
+                        return;
+                    };
+
+                    let Ok(source) = EventSource::new(&url) else {
+                        return;
+                    };
+
+                    let on_todo =
+                        Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
+                            let Some(data) = e.data().as_string() else {
+                                return;
+                            };
+
+                            match serde_json::from_str::<TodoEvent>(&data) {
+                                Ok(event) => on_event.emit(event),
+                                Err(err) => log::error!("{err}"),
+                            }
+                        });
+
+                    source
+                        .add_event_listener_with_callback("todo", on_todo.as_ref().unchecked_ref())
+                        .ok();
+
+                    let on_error = {
+                        let source = source.clone();
+
+                        Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+                            if source.ready_state() == EventSource::CLOSED {
+                                let attempt_handle = attempt_handle.clone();
+
+                                *reconnect.borrow_mut() =
+                                    Some(Timeout::new(RECONNECT_MILLIS, move || {
+                                        attempt_handle.set(*attempt_handle + 1)
+                                    }));
+                            }
+                        })
+                    };
+
+                    source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+                    let new_subscription = Subscription {
+                        source,
+                        _on_todo: on_todo,
+                        _on_error: on_error,
+                    };
+
+                    if !is_cancelled.get() {
+                        *subscription.borrow_mut() = Some(new_subscription);
+                    }
+                });
+            }
+
+            move || {
+                is_cancelled.set(true);
+
+                subscription.borrow_mut().take();
+
+                reconnect.borrow_mut().take();
+            }
+        },
+        (attempt, is_signed_in),
+    );
+}
@@ -1,6 +1,7 @@
 use serde::de::DeserializeOwned;
 
 use super::error::{ApiError, FieldError};
+use crate::i18n;
 
 pub trait FieldErrorsMessagesExt {
     fn messages(&self) -> Vec<String>;
@@ -8,7 +9,7 @@ pub trait FieldErrorsMessagesExt {
 
 impl FieldErrorsMessagesExt for Vec<FieldError> {
     fn messages(&self) -> Vec<String> {
-        self.iter().map(|err| err.message.clone()).collect()
+        self.iter().map(i18n::field_error).collect()
     }
 }
 
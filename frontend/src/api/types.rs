@@ -1,16 +1,206 @@
-use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use uuid::Uuid;
 
+use crate::i18n::{self, t};
+
+#[derive(Debug, Serialize)]
+pub struct AccountDeletion {
+    pub password: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct Credentials {
     pub username: String,
     pub password: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct PasswordChange {
+    pub password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn label(&self) -> String {
+        t(match self {
+            Theme::System => "theme.system",
+            Theme::Light => "theme.light",
+            Theme::Dark => "theme.dark",
+        })
+    }
+
+    /// Theme after this one in the `Navbar` toggle.
+    pub fn next(&self) -> Theme {
+        match self {
+            Theme::System => Theme::Light,
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::System,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateFormat {
+    /// The way of the preferred language.
+    #[default]
+    Locale,
+    Iso,
+    European,
+    Us,
+}
+
+impl DateFormat {
+    pub const ALL: [DateFormat; 4] = [
+        DateFormat::Locale,
+        DateFormat::Iso,
+        DateFormat::European,
+        DateFormat::Us,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            DateFormat::Locale => {
+                let example = NaiveDate::from_ymd_opt(2023, 3, 31).unwrap_or_default();
+
+                i18n::t_args(
+                    "date_format.locale",
+                    &[(
+                        "example",
+                        &i18n::format_date_with(&example, &[("dateStyle", "medium")], "%Y-%m-%d"),
+                    )],
+                )
+            }
+            DateFormat::Iso => "2023-03-31".to_string(),
+            DateFormat::European => "31.03.2023".to_string(),
+            DateFormat::Us => "03/31/2023".to_string(),
+        }
+    }
+
+    /// `chrono` format of a date without time, `Locale` falls back to ISO
+    /// where the browser doesn't know the language.
+    pub fn date(&self) -> &'static str {
+        match self {
+            DateFormat::Locale | DateFormat::Iso => "%Y-%m-%d",
+            DateFormat::European => "%d.%m.%Y",
+            DateFormat::Us => "%m/%d/%Y",
+        }
+    }
+
+    /// `chrono` format of a date with time.
+    pub fn date_time(&self) -> &'static str {
+        match self {
+            DateFormat::Locale | DateFormat::Iso => "%Y-%m-%d %H:%M",
+            DateFormat::European => "%d.%m.%Y %H:%M",
+            DateFormat::Us => "%m/%d/%Y %H:%M",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DefaultView {
+    #[default]
+    Active,
+    All,
+    Completed,
+    Board,
+    Calendar,
+}
+
+impl DefaultView {
+    pub const ALL: [DefaultView; 5] = [
+        DefaultView::Active,
+        DefaultView::All,
+        DefaultView::Completed,
+        DefaultView::Board,
+        DefaultView::Calendar,
+    ];
+
+    pub fn label(&self) -> String {
+        t(match self {
+            DefaultView::Active => "nav.active",
+            DefaultView::All => "nav.all",
+            DefaultView::Completed => "nav.completed",
+            DefaultView::Board => "nav.board",
+            DefaultView::Calendar => "nav.calendar",
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeekStart {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl WeekStart {
+    pub const ALL: [WeekStart; 2] = [WeekStart::Monday, WeekStart::Sunday];
+
+    pub fn label(&self) -> String {
+        t(match self {
+            WeekStart::Monday => "week_start.monday",
+            WeekStart::Sunday => "week_start.sunday",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preferences {
+    pub time_zone: Option<String>,
+    pub page_size: i32,
+    pub theme: Theme,
+    pub date_format: DateFormat,
+    pub default_view: DefaultView,
+    pub week_start: WeekStart,
+    pub locale: String,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            time_zone: None,
+            page_size: 10,
+            theme: Theme::default(),
+            date_format: DateFormat::default(),
+            default_view: DefaultView::default(),
+            week_start: WeekStart::default(),
+            locale: "en".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct TodosQuery {
     pub is_completed: Option<bool>,
+    pub project_id: Option<Uuid>,
+    pub sort_field_id: Option<Uuid>,
+    pub sort_order: Option<SortOrder>,
+    pub search: Option<String>,
     pub limit: usize,
     pub offset: usize,
 }
@@ -18,6 +208,7 @@ pub struct TodosQuery {
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct TodosDeleteQuery {
     pub is_completed: Option<bool>,
+    pub project_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -26,22 +217,368 @@ pub struct Paginated<T> {
     pub count: usize,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Todo {
     pub id: Uuid,
+    pub project_id: Option<Uuid>,
     pub name: String,
     pub is_completed: bool,
+    pub estimate_minutes: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub parent_id: Option<Uuid>,
+    #[serde(default)]
+    pub status_id: Option<Uuid>,
+    #[serde(default)]
+    pub fields: HashMap<Uuid, Value>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewTodo {
     pub name: String,
+    pub project_id: Option<Uuid>,
+    pub fields: HashMap<Uuid, Value>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct RenameTodo {
     pub name: String,
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DueDateChange {
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CalendarQuery {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TodoFieldsChange {
+    pub fields: HashMap<Uuid, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Project {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusCategory {
+    Todo,
+    InProgress,
+    Done,
+}
+
+impl StatusCategory {
+    pub const ALL: [StatusCategory; 3] = [
+        StatusCategory::Todo,
+        StatusCategory::InProgress,
+        StatusCategory::Done,
+    ];
+
+    pub fn label(&self) -> String {
+        t(match self {
+            StatusCategory::Todo => "status_category.todo",
+            StatusCategory::InProgress => "status_category.in_progress",
+            StatusCategory::Done => "status_category.done",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Status {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub category: StatusCategory,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewStatus {
+    pub name: String,
+    pub category: StatusCategory,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpdateStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<StatusCategory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Transition {
+    pub status_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BoardColumn {
+    pub status: Status,
+    pub todos: Vec<Todo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Board {
+    pub project: Project,
+    pub columns: Vec<BoardColumn>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    Text,
+    Number,
+    Date,
+    Select,
+    Checkbox,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CustomField {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub kind: FieldKind,
+    pub options: Vec<String>,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Template {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstantiateTemplate {
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoEventKind {
+    Created,
+    Updated,
+    Completed,
+    Reverted,
+    Deleted,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TodoEvent {
+    pub kind: TodoEventKind,
+    pub todo_id: Uuid,
+    pub todo: Option<Todo>,
+}
+
+/// Opens one event stream, shortly after it's issued.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StreamTicket {
+    pub ticket: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    Reminder,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub todo_id: Option<Uuid>,
+    pub kind: NotificationKind,
+    pub message: String,
+    pub is_read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Notifications {
+    pub data: Vec<Notification>,
+    pub count: usize,
+    pub unread: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    pub note: String,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    Csv,
+    TodoTxt,
+    Todoist,
+    Trello,
+}
+
+impl ImportFormat {
+    pub const ALL: [ImportFormat; 4] = [
+        ImportFormat::Csv,
+        ImportFormat::TodoTxt,
+        ImportFormat::Todoist,
+        ImportFormat::Trello,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImportFormat::Csv => "CSV",
+            ImportFormat::TodoTxt => "todo.txt",
+            ImportFormat::Todoist => "Todoist JSON",
+            ImportFormat::Trello => "Trello JSON",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ImportMapping {
+    pub name: Option<String>,
+    pub project: Option<String>,
+    pub is_completed: Option<String>,
+    pub created_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub due_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewImport {
+    pub format: ImportFormat,
+    pub content: String,
+    pub mapping: ImportMapping,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ImportedTodo {
+    pub name: String,
+    pub project: Option<String>,
+    pub is_completed: bool,
+    pub due_at: Option<DateTime<Utc>>,
+    pub parent: Option<usize>,
+    pub priority: Option<char>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ImportError {
+    pub line: usize,
+    #[serde(default)]
+    pub code: String,
+    pub message: String,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ImportPreview {
+    pub headers: Vec<String>,
+    pub todos: Vec<ImportedTodo>,
+    pub errors: Vec<ImportError>,
+    pub new_projects: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Imported {
+    pub todos: Vec<Todo>,
+    pub projects: Vec<Project>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+    Todotxt,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [
+        ExportFormat::Json,
+        ExportFormat::Csv,
+        ExportFormat::Markdown,
+        ExportFormat::Todotxt,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Todotxt => "todo.txt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportQuery {
+    pub format: ExportFormat,
+    pub is_completed: Option<bool>,
+    pub project_id: Option<Uuid>,
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+}
+
+impl Period {
+    pub const ALL: [Period; 3] = [Period::Day, Period::Week, Period::Month];
+
+    pub fn label(&self) -> String {
+        t(match self {
+            Period::Day => "period.day",
+            Period::Week => "period.week",
+            Period::Month => "period.month",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsQuery {
+    pub period: Period,
+    pub tz: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StatsBucket {
+    pub start: NaiveDate,
+    pub created: i64,
+    pub completed: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Stats {
+    pub period: Period,
+    pub time_zone: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub buckets: Vec<StatsBucket>,
+    pub open: i64,
+    pub done: i64,
+    pub average_completion_seconds: Option<i64>,
+    pub current_streak: i64,
+    pub longest_streak: i64,
+}
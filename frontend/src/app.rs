@@ -1,13 +1,24 @@
 use yew::prelude::*;
+use yewdux::prelude::*;
 
-use crate::{components::alerts::Alerts, router::Router};
+use crate::{components::alerts::Alerts, i18n, router::Router, store::Store};
 
 #[function_component(App)]
 pub fn app() -> Html {
+    // Texts are looked up while rendering, a new language remounts the views.
+    let language = use_selector(|store: &Store| {
+        i18n::language_for(
+            store
+                .preferences
+                .as_ref()
+                .map(|preferences| preferences.locale.as_str()),
+        )
+    });
+
     html!(
         <>
-            <Router />
-            <Alerts />
+            <Router key={ *language } />
+            <Alerts key={ *language } />
         </>
     )
 }
@@ -1,11 +1,22 @@
+pub mod account_menu;
 pub mod alert;
 pub mod alerts;
+pub mod command_palette;
+pub mod custom_field_input;
+pub mod export_menu;
 pub mod header;
 pub mod modal;
 pub mod navbar;
+pub mod new_from_template;
 pub mod new_todo;
+pub mod notifications;
 pub mod pagination;
+pub mod shortcuts;
+pub mod shortcuts_help;
 pub mod spinner;
+pub mod sync_status;
 pub mod text_input;
+pub mod theme_toggle;
+pub mod timer;
 pub mod todo_row;
 pub mod todos_table;
@@ -0,0 +1,149 @@
+use web_sys::HtmlInputElement;
+use yew::{platform::spawn_local, prelude::*};
+use yew_hooks::prelude::*;
+use yew_router::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    api::{types::AccountDeletion, use_api},
+    components::modal::Modal,
+    i18n::t,
+    router::PrivateRoute,
+    store::{Action, Store},
+};
+
+#[function_component(DeleteAccount)]
+fn delete_account() -> Html {
+    let api = use_api();
+
+    let password_handle = use_state(String::new);
+
+    let delete_handle = {
+        let password = (*password_handle).clone();
+
+        use_async(async move {
+            let result = api.delete_account(AccountDeletion { password }).await;
+
+            if result.is_ok() {
+                Store::dispatch(Action::SignOut);
+            }
+
+            result
+        })
+    };
+
+    let set_password = {
+        let password_handle = password_handle.clone();
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            password_handle.set(input.value());
+        }
+    };
+
+    let onsubmit = {
+        let delete_handle = delete_handle.clone();
+
+        move |e: SubmitEvent| {
+            e.prevent_default();
+
+            delete_handle.run();
+        }
+    };
+
+    html!(
+        <form class="space-y-4" { onsubmit }>
+            <h3 class="font-bold text-lg">{ t("account.delete") }</h3>
+            <p>
+                { t("account.delete_warning") }
+            </p>
+            <input
+                type="password"
+                required={ true }
+                placeholder={ t("account.password") }
+                class={ classes!("input", "input-bordered", "w-full", delete_handle.error.is_some().then_some("input-error")) }
+                value={ (*password_handle).clone() }
+                onchange={ set_password }
+            />
+            if let Some(error) = &delete_handle.error {
+                <label class="label">
+                    <span class="label-text-alt text-error">{ error.to_string() }</span>
+                </label>
+            }
+            <button
+                type="submit"
+                class={ classes!("btn", "btn-error", "w-full", delete_handle.loading.then_some("loading")) }
+                disabled={ password_handle.is_empty() || delete_handle.loading }
+            >
+                { t("account.delete") }
+            </button>
+        </form>
+    )
+}
+
+#[function_component(AccountMenu)]
+pub fn account_menu() -> Html {
+    let api = use_api();
+
+    let user = use_selector(|store: &Store| store.user.clone());
+
+    let toggle = use_toggle(false, true);
+
+    let download = move |_| {
+        let api = api.clone();
+
+        spawn_local(async move {
+            match api.archive_url().await {
+                Ok(url) => {
+                    if let Some(window) = web_sys::window() {
+                        let _ = window.location().set_href(&url);
+                    }
+                }
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            }
+        });
+    };
+
+    let open = {
+        let toggle = toggle.clone();
+
+        move |_| toggle.toggle()
+    };
+
+    let sign_out = |_| Store::dispatch(Action::SignOut);
+
+    html!(
+        <>
+            <div class="dropdown dropdown-end">
+                <label tabindex="0" class="btn btn-ghost gap-2">
+                    if let Some(user) = user.as_ref() {
+                        { user.username.clone() }
+                    }
+                    <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" class="w-6 h-6">
+                        <path stroke-linecap="round" stroke-linejoin="round" d="M17.982 18.725A7.488 7.488 0 0012 15.75a7.488 7.488 0 00-5.982 2.975m11.963 0a9 9 0 10-11.963 0m11.963 0A8.966 8.966 0 0112 21a8.966 8.966 0 01-5.982-2.275M15 9.75a3 3 0 11-6 0 3 3 0 016 0z" />
+                    </svg>
+                </label>
+                <ul tabindex="0" class="dropdown-content menu p-2 shadow bg-base-100 rounded-box w-52 z-10">
+                    <li>
+                        <Link<PrivateRoute> to={ PrivateRoute::Settings }>{ t("nav.settings") }</Link<PrivateRoute>>
+                    </li>
+                    <li>
+                        <button onclick={ download }>{ t("account.download") }</button>
+                    </li>
+                    <li>
+                        <button onclick={ open } class="text-error">{ t("account.delete") }</button>
+                    </li>
+                    <li>
+                        <button onclick={ sign_out }>{ t("account.sign_out") }</button>
+                    </li>
+                </ul>
+            </div>
+            if *toggle {
+                <Modal toggle={ toggle.clone() }>
+                    <DeleteAccount />
+                </Modal>
+            }
+        </>
+    )
+}
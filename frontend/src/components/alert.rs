@@ -1,6 +1,9 @@
 use yew::prelude::*;
 
-use crate::store::{Alert as _Alert, AlertType};
+use crate::{
+    i18n::t,
+    store::{Action, Alert as _Alert, AlertType, Store},
+};
 
 #[derive(Debug, PartialEq, Properties)]
 pub struct AlertProps {
@@ -26,6 +29,16 @@ pub fn alert(props: &AlertProps) -> Html {
         ),
     };
 
+    let undo = _alert.undo.clone().map(|undo| {
+        let _alert = _alert.clone();
+
+        move |_| {
+            undo.emit(());
+
+            Store::dispatch(Action::RemoveAlert(_alert.clone()));
+        }
+    });
+
     html!(
         <div class={classes!("alert", class, "shadow-lg", "m-3")}>
             <div>
@@ -35,6 +48,11 @@ pub fn alert(props: &AlertProps) -> Html {
 
                 <span>{ _alert.text.clone() }</span>
             </div>
+            if let Some(undo) = undo {
+                <div class="flex-none">
+                    <button class="btn btn-sm btn-ghost" onclick={ undo }>{ t("alert.undo") }</button>
+                </div>
+            }
         </div>
     )
 }
@@ -4,6 +4,7 @@ use yewdux::prelude::*;
 
 use crate::{
     components::alert::Alert,
+    optimistic::UNDO_MILLIS,
     store::{Action, Store},
 };
 
@@ -11,7 +12,13 @@ use crate::{
 pub fn alerts() -> Html {
     let alerts = use_selector(|store: &Store| store.alerts.clone());
 
-    let timeout = use_timeout(|| Store::dispatch(Action::PopAlert), 1_500);
+    // Alerts with an undo stay as long as it's possible.
+    let millis = match alerts.front() {
+        Some(alert) if alert.undo.is_some() => UNDO_MILLIS,
+        _ => 1_500,
+    };
+
+    let timeout = use_timeout(|| Store::dispatch(Action::PopAlert), millis);
 
     if !alerts.is_empty() {
         timeout.reset();
@@ -0,0 +1,262 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_hooks::prelude::*;
+use yew_router::prelude::*;
+
+use crate::{
+    api::{
+        types::{Todo, TodosQuery},
+        use_api,
+    },
+    i18n::{self, t},
+    offline,
+    pages::todos::TodosLocation,
+    router::PrivateRoute,
+    shortcuts,
+    store::{Action, Store},
+};
+
+/// Todos found for the typed text, shown below the actions and views.
+const TODO_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    NewTodo,
+    Help,
+    SignOut,
+    View(&'static str, PrivateRoute),
+    Todo(Todo),
+}
+
+impl Command {
+    fn label(&self) -> String {
+        match self {
+            Command::NewTodo => t("palette.new_todo"),
+            Command::Help => t("shortcuts.title"),
+            Command::SignOut => t("account.sign_out"),
+            Command::View(name, _) => i18n::t_args("palette.go_to", &[("view", &t(name))]),
+            Command::Todo(todo) => todo.name.clone(),
+        }
+    }
+
+    fn group(&self) -> String {
+        t(match self {
+            Command::NewTodo | Command::Help | Command::SignOut => "palette.action",
+            Command::View(..) => "palette.view",
+            Command::Todo(_) => "palette.todo",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct CommandPaletteProps {
+    pub toggle: UseToggleHandle<bool>,
+    pub on_help: Callback<(), ()>,
+}
+
+#[function_component(CommandPalette)]
+pub fn command_palette(props: &CommandPaletteProps) -> Html {
+    let api = use_api();
+
+    let navigator = use_navigator().unwrap();
+
+    let query_handle = use_state(String::new);
+
+    let active_handle = use_state(|| 0_usize);
+
+    let input_ref = use_node_ref();
+
+    {
+        let input_ref = input_ref.clone();
+
+        use_mount(move || {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+            }
+        });
+    }
+
+    let todos_handle = {
+        let query_handle = query_handle.clone();
+
+        use_async(async move {
+            let query = TodosQuery {
+                is_completed: None,
+                project_id: None,
+                sort_field_id: None,
+                sort_order: None,
+                search: Some(query_handle.trim().to_string()),
+                limit: TODO_LIMIT,
+                offset: 0,
+            };
+
+            match offline::is_online() {
+                true => api.todos(query).await,
+                false => Ok(offline::cached_todos(&query).await),
+            }
+        })
+    };
+
+    let search_debounce = {
+        let todos_handle = todos_handle.clone();
+
+        let query_handle = query_handle.clone();
+
+        use_debounce(
+            move || {
+                if !query_handle.trim().is_empty() {
+                    todos_handle.run();
+                }
+            },
+            200,
+        )
+    };
+
+    let commands = {
+        let query = query_handle.trim().to_lowercase();
+
+        let mut commands = vec![Command::NewTodo, Command::Help, Command::SignOut];
+
+        commands.extend(
+            shortcuts::views()
+                .into_iter()
+                .map(|(name, route)| Command::View(name, route)),
+        );
+
+        commands.retain(|command| command.label().to_lowercase().contains(&query));
+
+        if !query.is_empty() {
+            commands.extend(
+                todos_handle
+                    .data
+                    .iter()
+                    .flat_map(|todos| todos.data.clone())
+                    .map(Command::Todo),
+            );
+        }
+
+        commands
+    };
+
+    let run = {
+        let toggle = props.toggle.clone();
+
+        let on_help = props.on_help.clone();
+
+        Callback::from(move |command: Command| {
+            toggle.set(false);
+
+            match command {
+                Command::NewTodo => {
+                    let query = TodosLocation {
+                        new: true,
+                        ..Default::default()
+                    };
+
+                    let _ = navigator.push_with_query(&PrivateRoute::Active, &query);
+                }
+                Command::Help => on_help.emit(()),
+                Command::SignOut => Store::dispatch(Action::SignOut),
+                Command::View(_, route) => navigator.push(&route),
+                Command::Todo(todo) => {
+                    let query = TodosLocation {
+                        search: Some(todo.name),
+                        ..Default::default()
+                    };
+
+                    let _ = navigator.push_with_query(&PrivateRoute::All, &query);
+                }
+            }
+        })
+    };
+
+    let set_query = {
+        let query_handle = query_handle.clone();
+
+        let active_handle = active_handle.clone();
+
+        move |e: InputEvent| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            query_handle.set(input.value());
+
+            active_handle.set(0);
+
+            search_debounce.run();
+        }
+    };
+
+    let on_keydown = {
+        let active_handle = active_handle.clone();
+
+        let commands = commands.clone();
+
+        let run = run.clone();
+
+        move |e: KeyboardEvent| {
+            let active = (*active_handle).min(commands.len().saturating_sub(1));
+
+            match e.key().as_str() {
+                "ArrowDown" => {
+                    active_handle.set((active + 1).min(commands.len().saturating_sub(1)))
+                }
+                "ArrowUp" => active_handle.set(active.saturating_sub(1)),
+                "Enter" => {
+                    if let Some(command) = commands.get(active) {
+                        run.emit(command.clone());
+                    }
+                }
+                _ => return,
+            }
+
+            e.prevent_default();
+        }
+    };
+
+    let active = (*active_handle).min(commands.len().saturating_sub(1));
+
+    html!(
+        <div class="space-y-2">
+            <input
+                ref={ input_ref }
+                type="text"
+                class="input input-bordered w-full mt-6"
+                placeholder={ t("palette.placeholder") }
+                value={ (*query_handle).clone() }
+                oninput={ set_query }
+                onkeydown={ on_keydown }
+            />
+            if commands.is_empty() {
+                <p class="p-4 text-center opacity-60">{ t("palette.empty") }</p>
+            } else {
+                <ul class="menu w-full">
+                    {
+                        commands
+                            .into_iter()
+                            .enumerate()
+                            .map(|(index, command)| {
+                                let label = command.label();
+
+                                let group = command.group();
+
+                                let run = run.clone();
+
+                                html!(
+                                    <li>
+                                        <a
+                                            class={ classes!((index == active).then_some("active")) }
+                                            onclick={ move |_| run.emit(command.clone()) }
+                                        >
+                                            <span class="badge badge-ghost badge-sm">{ group }</span>
+                                            { label }
+                                        </a>
+                                    </li>
+                                )
+                            })
+                            .collect::<Html>()
+                    }
+                </ul>
+            }
+        </div>
+    )
+}
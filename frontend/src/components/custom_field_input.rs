@@ -0,0 +1,140 @@
+use serde_json::{Number, Value};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::api::types::{CustomField, FieldKind};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct CustomFieldInputProps {
+    pub field: CustomField,
+    pub value: Option<Value>,
+    #[prop_or_default]
+    pub errors: Vec<String>,
+    #[prop_or_default]
+    pub compact: bool,
+    pub onchange: Callback<Value, ()>,
+}
+
+#[function_component(CustomFieldInput)]
+pub fn custom_field_input(props: &CustomFieldInputProps) -> Html {
+    let field = &props.field;
+
+    let value = props.value.clone().unwrap_or(Value::Null);
+
+    let size = if props.compact { "input-sm" } else { "" };
+
+    let error = if props.errors.is_empty() {
+        ""
+    } else {
+        "input-error"
+    };
+
+    let on_input_change = {
+        let onchange = props.onchange.clone();
+
+        let kind = field.kind;
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            let text = input.value();
+
+            let value = match kind {
+                FieldKind::Checkbox => Value::Bool(input.checked()),
+                _ if text.is_empty() => Value::Null,
+                FieldKind::Number => match text.parse::<f64>().ok().and_then(Number::from_f64) {
+                    Some(number) => Value::Number(number),
+                    None => Value::Null,
+                },
+                _ => Value::String(text),
+            };
+
+            onchange.emit(value);
+        }
+    };
+
+    let on_select_change = {
+        let onchange = props.onchange.clone();
+
+        move |e: Event| {
+            let select = e.target_dyn_into::<HtmlSelectElement>().unwrap();
+
+            let value = match select.value() {
+                text if text.is_empty() => Value::Null,
+                text => Value::String(text),
+            };
+
+            onchange.emit(value);
+        }
+    };
+
+    let text = match &value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        _ => String::new(),
+    };
+
+    let input = match field.kind {
+        FieldKind::Checkbox => html!(
+            <input
+                type="checkbox"
+                class={ classes!("checkbox", if props.compact { "checkbox-sm" } else { "" }) }
+                checked={ value.as_bool().unwrap_or_default() }
+                onchange={ on_input_change }
+            />
+        ),
+        FieldKind::Select => html!(
+            <select
+                class={ classes!("select", "select-bordered", "w-full", if props.compact { "select-sm" } else { "" }, error) }
+                onchange={ on_select_change }
+            >
+                <option value="" selected={ text.is_empty() }>{ "—" }</option>
+                {
+                    field
+                        .options
+                        .iter()
+                        .map(|option| html!(
+                            <option value={ option.clone() } selected={ *option == text }>{ option.clone() }</option>
+                        ))
+                        .collect::<Html>()
+                }
+            </select>
+        ),
+        kind => html!(
+            <input
+                type={ match kind {
+                    FieldKind::Number => "number",
+                    FieldKind::Date => "date",
+                    _ => "text",
+                } }
+                placeholder={ field.name.clone() }
+                class={ classes!("input", "input-bordered", "w-full", size, error) }
+                value={ text }
+                onchange={ on_input_change }
+            />
+        ),
+    };
+
+    html!(
+        <>
+            if !props.compact {
+                <label class="label">
+                    <span class="label-text">{ field.name.clone() }</span>
+                </label>
+            }
+
+            { input }
+
+            if !props.errors.is_empty() {
+                <label class="label">
+                    {
+                        props.errors
+                            .iter()
+                            .map(|text| html!(<span class="label-text-alt text-error">{ text.clone() }</span>))
+                            .collect::<Html>()
+                    }
+                </label>
+            }
+        </>
+    )
+}
@@ -0,0 +1,77 @@
+use uuid::Uuid;
+use yew::{platform::spawn_local, prelude::*};
+
+use crate::{
+    api::{
+        types::{ExportFormat, ExportQuery},
+        use_api,
+    },
+    i18n::t,
+    store::{Action, Store},
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ExportMenuProps {
+    pub is_completed: Option<bool>,
+    pub project_id: Option<Uuid>,
+    pub search: Option<String>,
+}
+
+#[function_component(ExportMenu)]
+pub fn export_menu(props: &ExportMenuProps) -> Html {
+    let api = use_api();
+
+    let download = {
+        let is_completed = props.is_completed;
+
+        let project_id = props.project_id;
+
+        let search = props.search.clone();
+
+        move |format: ExportFormat| {
+            let api = api.clone();
+
+            let search = search.clone();
+
+            move |_| {
+                let api = api.clone();
+
+                let query = ExportQuery {
+                    format,
+                    is_completed,
+                    project_id,
+                    search: search.clone(),
+                };
+
+                spawn_local(async move {
+                    match api.export_url(query).await {
+                        Ok(url) => {
+                            if let Some(window) = web_sys::window() {
+                                let _ = window.location().set_href(&url);
+                            }
+                        }
+                        Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+                    }
+                });
+            }
+        }
+    };
+
+    html!(
+        <div class="dropdown dropdown-end mr-2">
+            <label tabindex="0" class="btn btn-ghost">{ t("export") }</label>
+            <ul tabindex="0" class="dropdown-content menu p-2 shadow bg-base-100 rounded-box w-40 z-10">
+                {
+                    ExportFormat::ALL
+                        .iter()
+                        .map(|format| html!(
+                            <li>
+                                <button onclick={ download(*format) }>{ format.label() }</button>
+                            </li>
+                        ))
+                        .collect::<Html>()
+                }
+            </ul>
+        </div>
+    )
+}
@@ -1,12 +1,15 @@
 use yew::prelude::*;
 
-use crate::components::navbar::Navbar;
+use crate::components::{navbar::Navbar, sync_status::SyncStatus};
 
 #[function_component(Header)]
 pub fn header() -> Html {
     html!(
         <header class="max-w-screen-md mx-auto">
             <Navbar />
+            <div class="flex justify-end px-2 pt-1">
+                <SyncStatus />
+            </div>
         </header>
     )
 }
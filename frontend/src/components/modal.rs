@@ -17,6 +17,16 @@ pub fn modal(props: &ModalProps) -> Html {
         }
     };
 
+    {
+        let toggle = props.toggle.clone();
+
+        use_event_with_window("keydown", move |e: KeyboardEvent| {
+            if e.key() == "Escape" {
+                toggle.set(false);
+            }
+        });
+    }
+
     html!(
         <div class="modal modal-open ">
             <div class="modal-box relative">
@@ -1,10 +1,12 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
-use yewdux::prelude::*;
 
 use crate::{
+    components::{
+        account_menu::AccountMenu, notifications::Notifications, theme_toggle::ThemeToggle,
+    },
+    i18n::t,
     router::PrivateRoute,
-    store::{Action, Store},
 };
 
 #[derive(Debug, Clone, PartialEq, Properties)]
@@ -18,7 +20,7 @@ pub fn nav_link(props: &NavLinkProps) -> Html {
     let route = use_route::<PrivateRoute>().unwrap();
 
     let classes = if route == props.to {
-        classes!("bg-primary", "text-white")
+        classes!("bg-primary", "text-primary-content")
     } else {
         classes!()
     };
@@ -32,42 +34,45 @@ pub fn nav_link(props: &NavLinkProps) -> Html {
 
 #[function_component(Navbar)]
 pub fn navbar() -> Html {
-    let user = use_selector(|store: &Store| store.user.clone());
-
-    let sign_out = |_| Store::dispatch(Action::SignOut);
-
     html!(
         <div class="navbar rounded-lg bg-base-300">
             <div class="navbar-start">
                 <Link<PrivateRoute>
-                    to={ PrivateRoute::Active }
+                    to={ PrivateRoute::Home }
                     classes={ classes!("btn", "btn-ghost", "normal-case", "text-xl") }
                 >
-                    { "Todos" }
+                    { t("nav.todos") }
                 </Link<PrivateRoute>>
             </div>
             <div class="navbar-center">
                 <ul class="menu menu-horizontal px-1 space-x-4">
                     <li>
-                        <NavLink to={ PrivateRoute::Active }>{ "Active" }</NavLink>
+                        <NavLink to={ PrivateRoute::Active }>{ t("nav.active") }</NavLink>
+                    </li>
+                    <li>
+                        <NavLink to={ PrivateRoute::All }>{ t("nav.all") }</NavLink>
+                    </li>
+                    <li>
+                        <NavLink to={ PrivateRoute::Completed }>{ t("nav.completed") }</NavLink>
+                    </li>
+                    <li>
+                        <NavLink to={ PrivateRoute::Board }>{ t("nav.board") }</NavLink>
+                    </li>
+                    <li>
+                        <NavLink to={ PrivateRoute::CalendarMonth }>{ t("nav.calendar") }</NavLink>
                     </li>
                     <li>
-                        <NavLink to={ PrivateRoute::All }>{ "All" }</NavLink>
+                        <NavLink to={ PrivateRoute::Import }>{ t("nav.import") }</NavLink>
                     </li>
                     <li>
-                        <NavLink to={ PrivateRoute::Completed }>{ "Completed" }</NavLink>
+                        <NavLink to={ PrivateRoute::Stats }>{ t("nav.stats") }</NavLink>
                     </li>
                 </ul>
             </div>
             <div class="navbar-end">
-                <button onclick={ sign_out } class="btn btn-ghost gap-2">
-                    if let Some(user) = user.as_ref() {
-                        { user.username.clone() }
-                    }
-                    <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" class="w-6 h-6">
-                        <path stroke-linecap="round" stroke-linejoin="round" d="M15.75 9V5.25A2.25 2.25 0 0013.5 3h-6a2.25 2.25 0 00-2.25 2.25v13.5A2.25 2.25 0 007.5 21h6a2.25 2.25 0 002.25-2.25V15m3 0l3-3m0 0l-3-3m3 3H9" />
-                    </svg>
-                </button>
+                <ThemeToggle />
+                <Notifications />
+                <AccountMenu />
             </div>
         </div>
     )
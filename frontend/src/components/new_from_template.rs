@@ -0,0 +1,140 @@
+use uuid::Uuid;
+use yew::prelude::*;
+use yew_hooks::prelude::*;
+
+use crate::{
+    api::{types::InstantiateTemplate, use_api},
+    components::spinner::SpinnerMedium,
+    i18n::t,
+    store::{Action, Store},
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct NewFromTemplateProps {
+    pub on_add: Callback<(), ()>,
+    #[prop_or_default]
+    pub project_id: Option<Uuid>,
+}
+
+#[function_component(NewFromTemplate)]
+pub fn new_from_template(props: &NewFromTemplateProps) -> Html {
+    let api = use_api();
+
+    let selected_handle = use_state(|| None::<Uuid>);
+
+    let templates_handle = {
+        let api = api.clone();
+
+        use_async_with_options(
+            async move {
+                let result = api.templates().await;
+
+                if let Err(err) = &result {
+                    Store::dispatch(Action::AlertError(err.to_string()));
+                }
+
+                result
+            },
+            UseAsyncOptions::enable_auto(),
+        )
+    };
+
+    let instantiate_handle = {
+        let selected_handle = selected_handle.clone();
+
+        let on_add = props.on_add.clone();
+
+        let project_id = props.project_id;
+
+        use_async(async move {
+            let Some(id) = *selected_handle else {
+                return Ok(Vec::new());
+            };
+
+            let result = api
+                .instantiate_template(id, InstantiateTemplate { project_id })
+                .await;
+
+            match &result {
+                Ok(_) => on_add.emit(()),
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            };
+
+            result
+        })
+    };
+
+    {
+        let instantiate_handle = instantiate_handle.clone();
+
+        use_effect_with_deps(
+            move |selected| {
+                if selected.is_some() {
+                    instantiate_handle.run();
+                }
+            },
+            *selected_handle,
+        );
+    }
+
+    let select = {
+        let selected_handle = selected_handle.clone();
+
+        let instantiate_handle = instantiate_handle.clone();
+
+        move |id: Uuid| {
+            if *selected_handle == Some(id) {
+                instantiate_handle.run();
+            } else {
+                selected_handle.set(Some(id));
+            }
+        }
+    };
+
+    html!(
+        <div class="space-y-6 p-3">
+            <div>
+                <h2 class="text-xl font-bold tracking-tight">
+                    { t("templates.new_from") }
+                </h2>
+            </div>
+
+            if let Some(templates) = &templates_handle.data {
+                if templates.is_empty() {
+                    <p class="text-center">{ t("templates.empty") }</p>
+                } else {
+                    <ul class="menu bg-base-100 rounded-box">
+                        {
+                            templates
+                                .iter()
+                                .map(|template| {
+                                    let select = select.clone();
+
+                                    let id = template.id;
+
+                                    let loading = instantiate_handle.loading && *selected_handle == Some(id);
+
+                                    html!(
+                                        <li>
+                                            <button
+                                                class={ classes!(loading.then_some("loading")) }
+                                                disabled={ instantiate_handle.loading }
+                                                onclick={ move |_| select(id) }
+                                            >
+                                                { template.name.clone() }
+                                            </button>
+                                        </li>
+                                    )
+                                })
+                                .collect::<Html>()
+                        }
+                    </ul>
+                }
+            }
+
+            if templates_handle.loading {
+                <SpinnerMedium />
+            }
+        </div>
+    )
+}
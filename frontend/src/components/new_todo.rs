@@ -1,4 +1,8 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 use yew_hooks::prelude::*;
@@ -7,26 +11,36 @@ use crate::{
     api::{
         error::FieldError,
         ext::{ApiErrorOptionExt, FieldErrorsMessagesExt},
-        types::NewTodo as _NewTodo,
+        types::{CustomField, NewTodo as _NewTodo},
         use_api,
     },
-    components::text_input::TextInput,
+    components::{custom_field_input::CustomFieldInput, text_input::TextInput},
+    i18n::t,
+    offline::{self, Mutation},
     store::{Action, Store},
 };
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Form {
     pub name: String,
+    pub fields: HashMap<Uuid, Value>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize)]
 pub struct Errors {
+    #[serde(default = "Vec::new")]
     pub name: Vec<FieldError>,
+    #[serde(default = "HashMap::new")]
+    pub fields: HashMap<Uuid, Vec<FieldError>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct NewTodoProps {
     pub on_add: Callback<(), ()>,
+    #[prop_or_default]
+    pub project_id: Option<Uuid>,
+    #[prop_or_default]
+    pub fields: Vec<CustomField>,
 }
 
 #[function_component(NewTodo)]
@@ -40,12 +54,23 @@ pub fn new_todo(props: &NewTodoProps) -> Html {
 
         let on_add = props.on_add.clone();
 
+        let project_id = props.project_id;
+
         use_async(async move {
             let new_todo = _NewTodo {
                 name: form_handle.name.clone(),
+                project_id,
+                fields: form_handle.fields.clone(),
             };
 
-            let result = api.new_todo(new_todo).await;
+            let result = offline::send(
+                Mutation::Create {
+                    id: Uuid::new_v4(),
+                    todo: new_todo.clone(),
+                },
+                api.new_todo(new_todo),
+            )
+            .await;
 
             match &result {
                 Ok(_) => on_add.emit(()),
@@ -66,6 +91,22 @@ pub fn new_todo(props: &NewTodoProps) -> Html {
 
             form_handle.set(Form {
                 name: input.value(),
+                ..(*form_handle).clone()
+            });
+        }
+    };
+
+    let set_field = {
+        let form_handle = form_handle.clone();
+
+        move |(field_id, value): (Uuid, Value)| {
+            let mut fields = form_handle.fields.clone();
+
+            fields.insert(field_id, value);
+
+            form_handle.set(Form {
+                fields,
+                ..(*form_handle).clone()
             });
         }
     };
@@ -84,7 +125,7 @@ pub fn new_todo(props: &NewTodoProps) -> Html {
         <form class="space-y-6 p-3" onsubmit={ submit }>
             <div>
                 <h2 class="text-xl font-bold tracking-tight">
-                    { "Add todo" }
+                    { t("todos.add_title") }
                 </h2>
             </div>
 
@@ -95,12 +136,35 @@ pub fn new_todo(props: &NewTodoProps) -> Html {
                         name="name"
                         r#type="text"
                         onchange={ set_name }
-                        placeholder="Name"
+                        placeholder={ t("todos.name") }
                         required={ true }
                         value={ form_handle.name.clone() }
                         errors={ errors.name.clone().messages() }
                     />
                 </div>
+
+                {
+                    props
+                        .fields
+                        .iter()
+                        .map(|field| {
+                            let set_field = set_field.clone();
+
+                            let field_id = field.id;
+
+                            html!(
+                                <div class="mb-3">
+                                    <CustomFieldInput
+                                        field={ field.clone() }
+                                        value={ form_handle.fields.get(&field.id).cloned() }
+                                        errors={ errors.fields.get(&field.id).cloned().unwrap_or_default().messages() }
+                                        onchange={ move |value| set_field((field_id, value)) }
+                                    />
+                                </div>
+                            )
+                        })
+                        .collect::<Html>()
+                }
             </div>
 
             <div>
@@ -108,7 +172,7 @@ pub fn new_todo(props: &NewTodoProps) -> Html {
                     <button class="btn loading"></button>
                 } else {
                     <button class="btn btn-primary">
-                        { "Add" }
+                        { t("todos.add") }
                     </button>
                 }
             </div>
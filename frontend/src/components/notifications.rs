@@ -0,0 +1,204 @@
+use chrono::{DateTime, Utc};
+use yew::prelude::*;
+use yew_hooks::prelude::*;
+
+use crate::{
+    api::{types::Notification, use_api},
+    components::todo_row::DateTimeHumanizeExt,
+    i18n::t,
+    store::{Action, Alert, AlertType, Store},
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct NotificationItemProps {
+    pub notification: Notification,
+    pub on_read: Callback<(), ()>,
+}
+
+#[function_component(NotificationItem)]
+pub fn notification_item(props: &NotificationItemProps) -> Html {
+    let api = use_api();
+
+    let mark_read_handle = {
+        let id = props.notification.id;
+
+        let on_read = props.on_read.clone();
+
+        use_async(async move {
+            let result = api.mark_notification_read(id).await;
+
+            match &result {
+                Ok(_) => on_read.emit(()),
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            };
+
+            result
+        })
+    };
+
+    let mark_read = {
+        let mark_read_handle = mark_read_handle.clone();
+
+        move |_| {
+            mark_read_handle.run();
+        }
+    };
+
+    let notification = &props.notification;
+
+    html!(
+        <li>
+            <button
+                class={ classes!("flex", "flex-col", "items-start", (!notification.is_read).then_some("font-bold")) }
+                disabled={ notification.is_read || mark_read_handle.loading }
+                onclick={ mark_read }
+            >
+                <span>{ notification.message.clone() }</span>
+                <span class="text-xs opacity-60">
+                    { notification.created_at.humanize() }
+                </span>
+            </button>
+        </li>
+    )
+}
+
+#[function_component(Notifications)]
+pub fn notifications() -> Html {
+    let api = use_api();
+
+    // Newest notification already seen, so only fresh ones pop up as toasts.
+    let last_seen = use_mut_ref(|| None::<DateTime<Utc>>);
+
+    let notifications_handle = {
+        let api = api.clone();
+
+        use_async_with_options(
+            async move {
+                let result = api.notifications().await;
+
+                if let Ok(notifications) = &result {
+                    let mut last_seen = last_seen.borrow_mut();
+
+                    if let Some(seen) = *last_seen {
+                        notifications
+                            .data
+                            .iter()
+                            .filter(|notification| {
+                                !notification.is_read && notification.created_at > seen
+                            })
+                            .for_each(|notification| {
+                                Store::dispatch(Action::Alert(Alert::new(
+                                    AlertType::Warning,
+                                    &notification.message,
+                                )))
+                            });
+                    }
+
+                    *last_seen = notifications
+                        .data
+                        .iter()
+                        .map(|notification| notification.created_at)
+                        .max()
+                        .max(*last_seen)
+                        .or_else(|| Some(Utc::now()));
+                }
+
+                result
+            },
+            UseAsyncOptions::enable_auto(),
+        )
+    };
+
+    {
+        let notifications_handle = notifications_handle.clone();
+
+        use_interval(move || notifications_handle.run(), 30_000);
+    }
+
+    let mark_all_read_handle = {
+        let notifications_handle = notifications_handle.clone();
+
+        use_async(async move {
+            let result = api.mark_all_notifications_read().await;
+
+            match &result {
+                Ok(_) => notifications_handle.run(),
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            };
+
+            result
+        })
+    };
+
+    let mark_all_read = {
+        let mark_all_read_handle = mark_all_read_handle.clone();
+
+        move |_| {
+            mark_all_read_handle.run();
+        }
+    };
+
+    let on_read = {
+        let notifications_handle = notifications_handle.clone();
+
+        move |()| {
+            notifications_handle.run();
+        }
+    };
+
+    let unread = notifications_handle
+        .data
+        .as_ref()
+        .map(|notifications| notifications.unread)
+        .unwrap_or_default();
+
+    html!(
+        <div class="dropdown dropdown-end">
+            <label tabindex="0" class="btn btn-ghost btn-circle">
+                <div class="indicator">
+                    <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" class="w-6 h-6">
+                        <path stroke-linecap="round" stroke-linejoin="round" d="M14.857 17.082a23.848 23.848 0 005.454-1.31A8.967 8.967 0 0118 9.75v-.7V9A6 6 0 006 9v.75a8.967 8.967 0 01-2.312 6.022c1.733.64 3.56 1.085 5.455 1.31m5.714 0a24.255 24.255 0 01-5.714 0m5.714 0a3 3 0 11-5.714 0" />
+                    </svg>
+                    if unread > 0 {
+                        <span class="badge badge-sm badge-primary indicator-item">{ unread }</span>
+                    }
+                </div>
+            </label>
+            <div tabindex="0" class="dropdown-content card card-compact w-80 mt-3 shadow bg-base-100 z-10">
+                <div class="card-body">
+                    <div class="flex justify-between items-center">
+                        <span class="font-bold">{ t("notifications.title") }</span>
+                        <button
+                            class="btn btn-ghost btn-xs"
+                            disabled={ unread == 0 || mark_all_read_handle.loading }
+                            onclick={ mark_all_read }
+                        >
+                            { t("notifications.mark_all_read") }
+                        </button>
+                    </div>
+                    if let Some(notifications) = &notifications_handle.data {
+                        if notifications.data.is_empty() {
+                            <p class="text-center">{ t("notifications.empty") }</p>
+                        } else {
+                            <ul class="menu menu-compact">
+                                {
+                                    notifications
+                                        .data
+                                        .iter()
+                                        .map(|notification| html!(
+                                            <NotificationItem
+                                                key={ notification.id.to_string() }
+                                                notification={ notification.clone() }
+                                                on_read={ on_read.clone() }
+                                            />
+                                        ))
+                                        .collect::<Html>()
+                                }
+                            </ul>
+                        }
+                    }
+                </div>
+            </div>
+        </div>
+    )
+}
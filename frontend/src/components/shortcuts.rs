@@ -0,0 +1,89 @@
+use yew::prelude::*;
+use yew_hooks::prelude::*;
+use yew_router::prelude::*;
+
+use crate::{
+    components::{command_palette::CommandPalette, modal::Modal, shortcuts_help::ShortcutsHelp},
+    shortcuts,
+};
+
+/// Shortcuts available on every page, the command palette, the help and `g`
+/// sequences.
+#[function_component(Shortcuts)]
+pub fn shortcuts() -> Html {
+    let navigator = use_navigator().unwrap();
+
+    let palette_toggle = use_toggle(false, true);
+
+    let help_toggle = use_toggle(false, true);
+
+    // Set by `g`, the next key picks a view.
+    let is_go_pending = use_mut_ref(|| false);
+
+    {
+        let palette_toggle = palette_toggle.clone();
+
+        let help_toggle = help_toggle.clone();
+
+        use_event_with_window("keydown", move |e: KeyboardEvent| {
+            if shortcuts::is_palette(&e) {
+                e.prevent_default();
+
+                help_toggle.set(false);
+
+                palette_toggle.toggle();
+
+                return;
+            }
+
+            let Some(key) = shortcuts::key(&e) else {
+                return;
+            };
+
+            if is_go_pending.replace(false) {
+                if let Some(route) = shortcuts::go_to(&key) {
+                    e.prevent_default();
+
+                    navigator.push(&route);
+                }
+
+                return;
+            }
+
+            match key.as_str() {
+                "g" => *is_go_pending.borrow_mut() = true,
+                "?" => help_toggle.toggle(),
+                _ => return,
+            }
+
+            e.prevent_default();
+        });
+    }
+
+    let on_help = {
+        let palette_toggle = palette_toggle.clone();
+
+        let help_toggle = help_toggle.clone();
+
+        move |()| {
+            palette_toggle.set(false);
+
+            help_toggle.set(true);
+        }
+    };
+
+    html!(
+        <>
+            if *palette_toggle {
+                <Modal toggle={ palette_toggle.clone() }>
+                    <CommandPalette toggle={ palette_toggle.clone() } { on_help } />
+                </Modal>
+            }
+            if *help_toggle {
+                <Modal toggle={ help_toggle.clone() }>
+                    <ShortcutsHelp />
+                </Modal>
+            }
+        </>
+    )
+}
@@ -0,0 +1,27 @@
+use yew::prelude::*;
+
+use crate::{i18n::t, shortcuts::BINDINGS};
+
+#[function_component(ShortcutsHelp)]
+pub fn shortcuts_help() -> Html {
+    html!(
+        <div class="space-y-4">
+            <h3 class="font-bold text-lg">{ t("shortcuts.title") }</h3>
+            <table class="table table-compact w-full">
+                <tbody>
+                    {
+                        BINDINGS
+                            .iter()
+                            .map(|(keys, description)| html!(
+                                <tr>
+                                    <td class="whitespace-nowrap"><kbd class="kbd kbd-sm">{ *keys }</kbd></td>
+                                    <td>{ t(description) }</td>
+                                </tr>
+                            ))
+                            .collect::<Html>()
+                    }
+                </tbody>
+            </table>
+        </div>
+    )
+}
@@ -1,12 +1,14 @@
 use yew::prelude::*;
 
+use crate::i18n::t;
+
 #[function_component(SpinnerLarge)]
 pub fn spinner_large() -> Html {
     html!(
         <div class="absolute inset-0 m-auto w-full h-full text-center bg-base-100 z-40">
             <div class="absolute w-min h-min inset-0 m-auto">
                 <div class="w-20 h-20 rounded-full animate-spin border-8 border-solid border-primary border-t-transparent shadow-md"></div>
-                <span class="sr-only">{ "Loading..." }</span>
+                <span class="sr-only">{ t("loading") }</span>
             </div>
         </div>
     )
@@ -18,7 +20,7 @@ pub fn spinner_medium() -> Html {
         <div class="absolute inset-0 m-auto w-full h-full text-center bg-base-100 z-40">
             <div class="absolute w-min h-min inset-0 m-auto">
                 <div class="w-16 h-16 rounded-full animate-spin border-4 border-solid border-primary border-t-transparent shadow-md"></div>
-                <span class="sr-only">{ "Loading..." }</span>
+                <span class="sr-only">{ t("loading") }</span>
             </div>
         </div>
     )
@@ -0,0 +1,95 @@
+use wasm_bindgen::{closure::Closure, JsCast};
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+use crate::{
+    api::use_api,
+    i18n::{self, t},
+    offline,
+    store::{Action, Store, SyncStatus as Status},
+};
+
+/// Connection state with the number of changes waiting for it. Queued
+/// changes are sent on mount and whenever the browser gets back online.
+#[function_component(SyncStatus)]
+pub fn sync_status() -> Html {
+    let api = use_api();
+
+    let status = use_selector(|store: &Store| store.sync_status);
+
+    let pending = use_selector(|store: &Store| store.pending_mutations);
+
+    use_effect_with_deps(
+        move |_| {
+            let sync = move || {
+                let api = api.clone();
+
+                spawn_local(async move { offline::sync(&api).await });
+            };
+
+            if offline::is_online() {
+                sync();
+            } else {
+                Store::dispatch(Action::SetSyncStatus(Status::Offline));
+
+                spawn_local(offline::refresh_pending());
+            }
+
+            let on_online = Closure::<dyn FnMut(Event)>::new(move |_: Event| sync());
+
+            let on_offline = Closure::<dyn FnMut(Event)>::new(|_: Event| {
+                Store::dispatch(Action::SetSyncStatus(Status::Offline))
+            });
+
+            let window = web_sys::window();
+
+            if let Some(window) = &window {
+                window
+                    .add_event_listener_with_callback("online", on_online.as_ref().unchecked_ref())
+                    .ok();
+                window
+                    .add_event_listener_with_callback(
+                        "offline",
+                        on_offline.as_ref().unchecked_ref(),
+                    )
+                    .ok();
+            }
+
+            move || {
+                if let Some(window) = &window {
+                    window
+                        .remove_event_listener_with_callback(
+                            "online",
+                            on_online.as_ref().unchecked_ref(),
+                        )
+                        .ok();
+                    window
+                        .remove_event_listener_with_callback(
+                            "offline",
+                            on_offline.as_ref().unchecked_ref(),
+                        )
+                        .ok();
+                }
+            }
+        },
+        (),
+    );
+
+    let (class, label) = match *status {
+        Status::Online => ("badge-success", t("sync.online")),
+        Status::Offline => ("badge-warning", t("sync.offline")),
+        Status::Syncing => ("badge-info", t("sync.syncing")),
+    };
+
+    let label = match *pending {
+        0 => label,
+        pending => i18n::t_args(
+            "sync.pending",
+            &[("status", &label), ("count", &pending.to_string())],
+        ),
+    };
+
+    html!(
+        <span class={ classes!("badge", "badge-sm", class) }>{ label }</span>
+    )
+}
@@ -0,0 +1,73 @@
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+use crate::{
+    api::{
+        types::{Preferences, Theme},
+        use_api,
+    },
+    i18n,
+    store::{Action, Store},
+};
+
+/// Switches to the next theme right away and saves it, going back when the
+/// server refuses.
+#[function_component(ThemeToggle)]
+pub fn theme_toggle() -> Html {
+    let api = use_api();
+
+    let preferences = use_selector(|store: &Store| store.preferences.clone());
+
+    // Saving would overwrite the other preferences with the defaults.
+    let Some(preferences) = (*preferences).clone() else {
+        return html!();
+    };
+
+    let theme = preferences.theme;
+
+    let toggle = {
+        let preferences = preferences.clone();
+
+        move |_| {
+            let api = api.clone();
+
+            let previous = preferences.clone();
+
+            let preferences = Preferences {
+                theme: theme.next(),
+                ..preferences.clone()
+            };
+
+            Store::dispatch(Action::SetPreferences(Some(preferences.clone())));
+
+            spawn_local(async move {
+                match api.update_preferences(preferences).await {
+                    Ok(preferences) => Store::dispatch(Action::SetPreferences(Some(preferences))),
+                    Err(err) => {
+                        Store::dispatch(Action::SetPreferences(Some(previous)));
+
+                        Store::dispatch(Action::AlertError(err.to_string()));
+                    }
+                }
+            });
+        }
+    };
+
+    let d = match theme {
+        Theme::System => "M9 17.25v1.007a3 3 0 01-.879 2.122L7.5 21h9l-.621-.621A3 3 0 0115 18.257V17.25m6-12V15a2.25 2.25 0 01-2.25 2.25H5.25A2.25 2.25 0 013 15V5.25m18 0A2.25 2.25 0 0018.75 3H5.25A2.25 2.25 0 003 5.25m18 0V12a2.25 2.25 0 01-2.25 2.25H5.25A2.25 2.25 0 013 12V5.25",
+        Theme::Light => "M12 3v2.25m6.364.386l-1.591 1.591M21 12h-2.25m-.386 6.364l-1.591-1.591M12 18.75V21m-4.773-4.227l-1.591 1.591M5.25 12H3m4.227-4.773L5.636 5.636M15.75 12a3.75 3.75 0 11-7.5 0 3.75 3.75 0 017.5 0z",
+        Theme::Dark => "M21.752 15.002A9.718 9.718 0 0118 15.75c-5.385 0-9.75-4.365-9.75-9.75 0-1.33.266-2.597.748-3.752A9.753 9.753 0 003 11.25C3 16.635 7.365 21 12.75 21a9.753 9.753 0 009.002-5.998z",
+    };
+
+    html!(
+        <button
+            class="btn btn-ghost btn-circle"
+            title={ i18n::t_args("theme.toggle", &[("theme", &theme.label())]) }
+            onclick={ toggle }
+        >
+            <svg xmlns="http://www.w3.org/2000/svg" fill="none" viewBox="0 0 24 24" stroke-width="1.5" stroke="currentColor" class="w-6 h-6">
+                <path stroke-linecap="round" stroke-linejoin="round" { d } />
+            </svg>
+        </button>
+    )
+}
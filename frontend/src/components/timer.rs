@@ -0,0 +1,102 @@
+use chrono::Utc;
+use uuid::Uuid;
+use yew::prelude::*;
+use yew_hooks::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    api::use_api,
+    i18n::t,
+    store::{Action, Store},
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct TimerProps {
+    pub todo_id: Uuid,
+}
+
+#[function_component(Timer)]
+pub fn timer(props: &TimerProps) -> Html {
+    let api = use_api();
+
+    let timer = use_selector(|store: &Store| store.timer.clone());
+
+    let update = use_update();
+
+    let is_running = matches!(timer.as_ref(), Some(entry) if entry.todo_id == props.todo_id);
+
+    use_interval(
+        move || {
+            if is_running {
+                update();
+            }
+        },
+        1_000,
+    );
+
+    let toggle_handle = {
+        let todo_id = props.todo_id;
+
+        use_async(async move {
+            let result = if is_running {
+                api.stop_timer().await.map(|_| None)
+            } else {
+                api.start_timer(todo_id).await.map(Some)
+            };
+
+            match &result {
+                Ok(timer) => Store::dispatch(Action::SetTimer(timer.clone())),
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            };
+
+            result
+        })
+    };
+
+    let toggle = {
+        let toggle_handle = toggle_handle.clone();
+
+        move |_| {
+            toggle_handle.run();
+        }
+    };
+
+    let elapsed = match timer.as_ref() {
+        Some(entry) if is_running => {
+            let seconds = (Utc::now() - entry.started_at).num_seconds().max(0);
+
+            format!(
+                "{:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds % 3600 / 60,
+                seconds % 60
+            )
+        }
+        _ => String::new(),
+    };
+
+    html!(
+        <div class="flex items-center gap-1">
+            <button
+                class={classes!(
+                    "btn",
+                    "btn-sm",
+                    "btn-circle",
+                    if is_running { "btn-error" } else { "btn-ghost" },
+                    toggle_handle.loading.then_some("loading"),
+                )}
+                onclick={ toggle }
+                title={ if is_running { t("timer.stop") } else { t("timer.start") } }
+            >
+                if !toggle_handle.loading {
+                    if is_running {
+                        { "■" }
+                    } else {
+                        { "▶" }
+                    }
+                }
+            </button>
+            <span class="font-mono text-sm">{ elapsed }</span>
+        </div>
+    )
+}
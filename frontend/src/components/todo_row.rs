@@ -1,15 +1,41 @@
-use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
-use yew::{html, prelude::function_component, Callback, Html, Properties};
-use yew_hooks::use_async;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use uuid::Uuid;
+use web_sys::{Element, HtmlInputElement};
+use yew::prelude::*;
+use yew_hooks::{use_async, use_event_with_window};
 
 use crate::{
-    api::{types::Todo, use_api},
+    api::{
+        error::{ApiError, FieldError},
+        ext::FieldErrorsMessagesExt,
+        types::{CustomField, RenameTodo, Todo, TodoFieldsChange},
+        use_api,
+    },
+    components::{custom_field_input::CustomFieldInput, timer::Timer},
+    i18n::{self, t},
+    offline::{self, Mutation},
+    optimistic, shortcuts,
     store::{Action, Store},
 };
 
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Errors {
+    #[serde(default = "Vec::new")]
+    pub name: Vec<FieldError>,
+}
+
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct TodoRowProps {
     pub todo: Todo,
+    #[prop_or_default]
+    pub fields: Vec<CustomField>,
+    /// Whether keyboard shortcuts act on this todo.
+    #[prop_or_default]
+    pub is_selected: bool,
     pub on_todo_action: Callback<(), ()>,
 }
 
@@ -22,15 +48,35 @@ pub fn todo_row(props: &TodoRowProps) -> Html {
 
         let todo = props.todo.clone();
 
+        use_async(async move {
+            let result = optimistic::toggle_completed(&api, &todo).await;
+
+            if let Err(err) = &result {
+                Store::dispatch(Action::AlertError(err.to_string()));
+            }
+
+            result
+        })
+    };
+
+    let field_change_handle = use_state(HashMap::<Uuid, Value>::new);
+
+    let update_fields_handle = {
+        let api = api.clone();
+
+        let id = props.todo.id;
+
+        let field_change_handle = field_change_handle.clone();
+
         let on_todo_action = props.on_todo_action.clone();
 
         use_async(async move {
-            let result = if todo.is_completed {
-                api.revert_todo(todo.id).await
-            } else {
-                api.complete_todo(todo.id).await
+            let change = TodoFieldsChange {
+                fields: (*field_change_handle).clone(),
             };
 
+            let result = api.update_todo_fields(id, change).await;
+
             match &result {
                 Ok(_) => on_todo_action.emit(()),
                 Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
@@ -40,18 +86,49 @@ pub fn todo_row(props: &TodoRowProps) -> Html {
         })
     };
 
-    let delete_handle = {
-        let api = api;
+    let editing_handle = use_state(|| false);
 
-        let id = props.todo.id;
+    let name_handle = use_state(String::new);
+
+    let errors_handle = use_state(Errors::default);
+
+    let input_ref = use_node_ref();
+
+    let row_ref = use_node_ref();
+
+    let rename_handle = {
+        let api = api.clone();
+
+        let (id, updated_at) = (props.todo.id, props.todo.updated_at);
+
+        let name_handle = name_handle.clone();
+
+        let editing_handle = editing_handle.clone();
+
+        let errors_handle = errors_handle.clone();
 
         let on_todo_action = props.on_todo_action.clone();
 
         use_async(async move {
-            let result = api.delete_todo(id).await;
+            let name = (*name_handle).clone();
+
+            let result = offline::send(
+                Mutation::Rename {
+                    id,
+                    name: name.clone(),
+                    updated_at,
+                },
+                api.update_todo(id, RenameTodo { name }),
+            )
+            .await;
 
             match &result {
-                Ok(_) => on_todo_action.emit(()),
+                Ok(_) => {
+                    editing_handle.set(false);
+
+                    on_todo_action.emit(());
+                }
+                Err(err @ ApiError::UnprocessableEntity(_)) => errors_handle.set(err.json()),
                 Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
             };
 
@@ -59,8 +136,38 @@ pub fn todo_row(props: &TodoRowProps) -> Html {
         })
     };
 
+    {
+        let input_ref = input_ref.clone();
+
+        use_effect_with_deps(
+            move |editing| {
+                if *editing {
+                    if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                        input.select();
+                    }
+                }
+            },
+            *editing_handle,
+        );
+    }
+
+    {
+        let row_ref = row_ref.clone();
+
+        use_effect_with_deps(
+            move |is_selected| {
+                if *is_selected {
+                    if let Some(row) = row_ref.cast::<Element>() {
+                        row.scroll_into_view_with_bool(false);
+                    }
+                }
+            },
+            props.is_selected,
+        );
+    }
+
     let toggle = {
-        let toggle_completed_handle = toggle_completed_handle;
+        let toggle_completed_handle = toggle_completed_handle.clone();
 
         move |_| {
             toggle_completed_handle.run();
@@ -68,15 +175,102 @@ pub fn todo_row(props: &TodoRowProps) -> Html {
     };
 
     let delete = {
-        let delete_handle = delete_handle;
+        let todo = props.todo.clone();
 
-        move |_| {
-            delete_handle.run();
+        Callback::from(move |()| {
+            optimistic::delete(&api, &todo);
+        })
+    };
+
+    let start_editing = {
+        let editing_handle = editing_handle.clone();
+
+        let name_handle = name_handle.clone();
+
+        let errors_handle = errors_handle.clone();
+
+        let name = props.todo.name.clone();
+
+        Callback::from(move |()| {
+            name_handle.set(name.clone());
+
+            errors_handle.set(Errors::default());
+
+            editing_handle.set(true);
+        })
+    };
+
+    {
+        let is_selected = props.is_selected;
+
+        let is_editing = *editing_handle;
+
+        let start_editing = start_editing.clone();
+
+        let delete = delete.clone();
+
+        use_event_with_window("keydown", move |e: KeyboardEvent| {
+            if !is_selected || is_editing {
+                return;
+            }
+
+            match shortcuts::key(&e).as_deref() {
+                Some("x") => toggle_completed_handle.run(),
+                Some("e") => start_editing.emit(()),
+                Some("#") => delete.emit(()),
+                _ => return,
+            }
+
+            e.prevent_default();
+        });
+    }
+
+    let set_name = {
+        let name_handle = name_handle.clone();
+
+        move |e: InputEvent| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            name_handle.set(input.value());
+        }
+    };
+
+    let on_name_keydown = {
+        let editing_handle = editing_handle.clone();
+
+        let name = props.todo.name.clone();
+
+        let name_handle = name_handle.clone();
+
+        let rename_handle = rename_handle.clone();
+
+        move |e: KeyboardEvent| match e.key().as_str() {
+            "Enter" => {
+                e.prevent_default();
+
+                if *name_handle == name {
+                    editing_handle.set(false);
+                } else {
+                    rename_handle.run();
+                }
+            }
+            "Escape" => editing_handle.set(false),
+            _ => {}
+        }
+    };
+
+    let set_field = {
+        let update_fields_handle = update_fields_handle;
+
+        move |(field_id, value): (Uuid, Value)| {
+            field_change_handle.set(HashMap::from([(field_id, value)]));
+
+            update_fields_handle.run();
         }
     };
 
     html!(
-        <tr>
+        <tr ref={ row_ref } class={ classes!(props.is_selected.then_some("active")) }>
             <td>
                 <div class="form-control">
                     <label class="label cursor-pointer">
@@ -89,7 +283,85 @@ pub fn todo_row(props: &TodoRowProps) -> Html {
                     </label>
                 </div>
             </td>
-            <td class="whitespace-pre-wrap">{ props.todo.name.clone() }</td>
+            if *editing_handle {
+                <td>
+                    <input
+                        ref={ input_ref }
+                        type="text"
+                        class={ classes!(
+                            "input",
+                            "input-bordered",
+                            "input-sm",
+                            "w-full",
+                            (!errors_handle.name.is_empty()).then_some("input-error"),
+                        ) }
+                        value={ (*name_handle).clone() }
+                        disabled={ rename_handle.loading }
+                        oninput={ set_name }
+                        onkeydown={ on_name_keydown }
+                    />
+                    if !errors_handle.name.is_empty() {
+                        <label class="label">
+                            {
+                                errors_handle.name
+                                    .messages()
+                                    .into_iter()
+                                    .map(|text| html!(<span class="label-text-alt text-error">{ text }</span>))
+                                    .collect::<Html>()
+                            }
+                        </label>
+                    }
+                </td>
+            } else {
+                <td class="whitespace-pre-wrap" ondblclick={ start_editing.reform(|_| ()) }>
+                    { props.todo.name.clone() }
+                    <button
+                        class="btn btn-ghost btn-xs ml-1 opacity-60"
+                        title={ t("todos.rename") }
+                        onclick={ start_editing.reform(|_| ()) }
+                    >
+                        { "✎" }
+                    </button>
+                </td>
+            }
+            <td>
+                <Timer todo_id={ props.todo.id } />
+                if let Some(estimate_minutes) = props.todo.estimate_minutes {
+                    <span class="text-xs opacity-60">
+                        {
+                            i18n::t_args(
+                                "todos.estimate",
+                                &[
+                                    ("hours", &(estimate_minutes / 60).to_string()),
+                                    ("minutes", &format!("{:02}", estimate_minutes % 60)),
+                                ],
+                            )
+                        }
+                    </span>
+                }
+            </td>
+            {
+                props
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let set_field = set_field.clone();
+
+                        let field_id = field.id;
+
+                        html!(
+                            <td>
+                                <CustomFieldInput
+                                    field={ field.clone() }
+                                    value={ props.todo.fields.get(&field.id).cloned() }
+                                    compact={ true }
+                                    onchange={ move |value| set_field((field_id, value)) }
+                                />
+                            </td>
+                        )
+                    })
+                    .collect::<Html>()
+            }
             <td>{ props.todo.created_at.humanize() }</td>
             <td>{ props.todo.updated_at.humanize() }</td>
             <td>
@@ -100,7 +372,7 @@ pub fn todo_row(props: &TodoRowProps) -> Html {
             <td>
                 <button
                     class="btn btn-ghost btn-sm btn-circle"
-                    onclick={ delete }
+                    onclick={ delete.reform(|_| ()) }
                 >
                     { "✕" }
                 </button>
@@ -115,12 +387,6 @@ pub trait DateTimeHumanizeExt {
 
 impl DateTimeHumanizeExt for DateTime<Utc> {
     fn humanize(&self) -> String {
-        thread_local! {
-            static TZ_OFFSET: FixedOffset = FixedOffset::east_opt(Local::now().offset().local_minus_utc()).unwrap();
-        };
-
-        let dt = TZ_OFFSET.with(|tz_offset| tz_offset.from_utc_datetime(&self.naive_utc()));
-
-        dt.format("%Y-%m-%d %H:%M").to_string()
+        i18n::format_date_time(self)
     }
 }
@@ -1,10 +1,24 @@
+use uuid::Uuid;
 use yew::prelude::*;
 
-use crate::{api::types::Todo, components::todo_row::TodoRow};
+use crate::{
+    api::types::{CustomField, SortOrder, Todo},
+    components::todo_row::TodoRow,
+    i18n::t,
+};
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct TodosTableProps {
     pub todos: Vec<Todo>,
+    #[prop_or_default]
+    pub fields: Vec<CustomField>,
+    #[prop_or_default]
+    pub sort: Option<(Uuid, SortOrder)>,
+    #[prop_or_default]
+    pub on_sort: Callback<Uuid, ()>,
+    /// Todo the keyboard shortcuts act on.
+    #[prop_or_default]
+    pub selected: Option<Uuid>,
     pub on_todo_action: Callback<(), ()>,
 }
 
@@ -16,10 +30,34 @@ pub fn todos_table(props: &TodosTableProps) -> Html {
                 <thead>
                     <tr>
                         <th></th>
-                        <th>{ "Name" }</th>
-                        <th>{ "Created at" }</th>
-                        <th>{ "Updated at" }</th>
-                        <th>{ "Completed at" }</th>
+                        <th>{ t("todos.name") }</th>
+                        <th>{ t("todos.time") }</th>
+                        {
+                            props
+                                .fields
+                                .iter()
+                                .map(|field| {
+                                    let on_sort = props.on_sort.clone();
+
+                                    let field_id = field.id;
+
+                                    let arrow = match props.sort {
+                                        Some((id, SortOrder::Asc)) if id == field.id => " ↑",
+                                        Some((id, SortOrder::Desc)) if id == field.id => " ↓",
+                                        _ => "",
+                                    };
+
+                                    html!(
+                                        <th class="cursor-pointer" onclick={ move |_| on_sort.emit(field_id) }>
+                                            { field.name.clone() }{ arrow }
+                                        </th>
+                                    )
+                                })
+                                .collect::<Html>()
+                        }
+                        <th>{ t("todos.created_at") }</th>
+                        <th>{ t("todos.updated_at") }</th>
+                        <th>{ t("todos.completed_at") }</th>
                         <th></th>
                     </tr>
                 </thead>
@@ -31,6 +69,8 @@ pub fn todos_table(props: &TodosTableProps) -> Html {
                             .map(|todo| html!(
                                 <TodoRow
                                     todo={ todo.clone() }
+                                    fields={ props.fields.clone() }
+                                    is_selected={ props.selected == Some(todo.id) }
                                     on_todo_action={ props.on_todo_action.clone() }
                                 />
                             ))
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use js_sys::{Array, Date, Function, Object, Reflect};
+use serde_json::Value;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::{
+    api::{error::FieldError, types::DateFormat},
+    preferences,
+};
+
+/// Languages with a translation, the first is the fallback.
+pub const LANGUAGES: [(&str, &str); 2] = [("en", "English"), ("de", "Deutsch")];
+
+fn source(language: &str) -> &'static str {
+    match language {
+        "de" => include_str!("../locales/de.json"),
+        _ => include_str!("../locales/en.json"),
+    }
+}
+
+thread_local! {
+    static CATALOGS: HashMap<&'static str, HashMap<String, String>> = LANGUAGES
+        .iter()
+        .map(|(language, _)| {
+            let catalog = serde_json::from_str(source(language)).unwrap_or_else(|err| {
+                log::error!("{language}: {err}");
+
+                HashMap::new()
+            });
+
+            (*language, catalog)
+        })
+        .collect();
+}
+
+fn browser_locale() -> Option<String> {
+    web_sys::window()?.navigator().language()
+}
+
+/// Language with a translation for `locale`, matched by its primary subtag.
+pub fn language_for(locale: Option<&str>) -> &'static str {
+    let locale = locale.map(str::to_string).or_else(browser_locale);
+
+    let primary = locale
+        .as_deref()
+        .and_then(|locale| locale.split('-').next())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    LANGUAGES
+        .iter()
+        .map(|(language, _)| *language)
+        .find(|language| *language == primary)
+        .unwrap_or(LANGUAGES[0].0)
+}
+
+/// Preferred locale, the browser one until the preferences are loaded.
+pub fn locale() -> String {
+    preferences::loaded()
+        .map(|preferences| preferences.locale)
+        .or_else(browser_locale)
+        .unwrap_or_else(|| LANGUAGES[0].0.to_string())
+}
+
+pub fn language() -> &'static str {
+    language_for(Some(&locale()))
+}
+
+/// Text of `key` in the preferred language, the English one when missing
+/// and `key` itself when that's missing too.
+pub fn t(key: &str) -> String {
+    CATALOGS.with(|catalogs| {
+        [language(), LANGUAGES[0].0]
+            .iter()
+            .find_map(|language| catalogs.get(language)?.get(key).cloned())
+            .unwrap_or_else(|| key.to_string())
+    })
+}
+
+/// Text of `key` with `{name}` placeholders filled in from `args`.
+pub fn t_args(key: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(t(key), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), value)
+    })
+}
+
+/// Message of a backend validation error translated by its code, the English
+/// message from the server for unknown codes.
+pub fn field_error(error: &FieldError) -> String {
+    let key = format!("errors.{}", error.code);
+
+    if t(&key) == key {
+        return error.message.clone();
+    }
+
+    let params = error
+        .params
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+
+            (name.as_str(), value)
+        })
+        .collect::<Vec<_>>();
+
+    let args = params
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect::<Vec<_>>();
+
+    t_args(&key, &args)
+}
+
+/// `millis` written by an `Intl.DateTimeFormat` of the preferred locale with
+/// `options`, `None` when the browser doesn't know the locale or time zone.
+fn intl(millis: f64, options: &[(&str, &str)]) -> Option<String> {
+    let object = Object::new();
+
+    for (key, value) in options {
+        Reflect::set(&object, &(*key).into(), &(*value).into()).ok()?;
+    }
+
+    let constructor = Reflect::get(&js_sys::global(), &"Intl".into())
+        .and_then(|intl| Reflect::get(&intl, &"DateTimeFormat".into()))
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()?;
+
+    // Invalid tags and unknown zones throw a `RangeError`, construct catches it.
+    let format = Reflect::construct(&constructor, &Array::of2(&locale().into(), &object)).ok()?;
+
+    Reflect::get(&format, &"format".into())
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()?
+        .call1(&format, &Date::new(&JsValue::from_f64(millis)))
+        .ok()?
+        .as_string()
+}
+
+/// `date` written with `options`, days are formatted at midnight UTC so the
+/// time zone doesn't move them.
+fn intl_date(date: &NaiveDate, options: &[(&str, &str)]) -> Option<String> {
+    let millis = Date::utc(date.year() as f64, date.month0() as f64);
+
+    let millis = millis + (date.day0() as f64) * 86_400_000.0;
+
+    let options = [options, &[("timeZone", "UTC")]].concat();
+
+    intl(millis, &options)
+}
+
+/// `instant` in the preferred time zone, written the preferred way.
+pub fn format_date_time(instant: &DateTime<Utc>) -> String {
+    let date_format = preferences::current().date_format;
+
+    if date_format == DateFormat::Locale {
+        let time_zone = preferences::time_zone();
+
+        let options = [
+            ("dateStyle", "medium"),
+            ("timeStyle", "short"),
+            ("timeZone", time_zone.as_str()),
+        ];
+
+        if let Some(text) = intl(instant.timestamp_millis() as f64, &options) {
+            return text;
+        }
+    }
+
+    preferences::to_local(instant)
+        .format(date_format.date_time())
+        .to_string()
+}
+
+/// `date` written the preferred way.
+pub fn format_date(date: &NaiveDate) -> String {
+    let date_format = preferences::current().date_format;
+
+    if date_format == DateFormat::Locale {
+        if let Some(text) = intl_date(date, &[("dateStyle", "medium")]) {
+            return text;
+        }
+    }
+
+    date.format(date_format.date()).to_string()
+}
+
+/// `date` with names in the preferred language, `fallback` is a `chrono`
+/// format for browsers without the locale.
+pub fn format_date_with(date: &NaiveDate, options: &[(&str, &str)], fallback: &str) -> String {
+    intl_date(date, options).unwrap_or_else(|| date.format(fallback).to_string())
+}
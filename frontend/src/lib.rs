@@ -1,7 +1,12 @@
 pub mod api;
 pub mod app;
 pub mod components;
+pub mod i18n;
+pub mod offline;
+pub mod optimistic;
 pub mod pages;
+pub mod preferences;
 pub mod providers;
 pub mod router;
+pub mod shortcuts;
 pub mod store;
@@ -1,7 +1,9 @@
-use frontend::app::App;
+use frontend::{app::App, offline};
 
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
 
+    offline::register_service_worker();
+
     yew::Renderer::<App>::new().render();
 }
@@ -0,0 +1,421 @@
+use std::{cell::Cell, cmp::Reverse, collections::HashMap, future::Future};
+
+use chrono::{DateTime, Utc};
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use wasm_bindgen::JsValue;
+use yew::platform::spawn_local;
+
+use crate::{
+    api::{
+        error::ApiError,
+        types::{NewTodo, Paginated, Preferences, RenameTodo, Todo, TodosQuery},
+        Api, ApiResult,
+    },
+    i18n::{self, t},
+    store::{Action, Store, SyncStatus, User},
+};
+
+mod idb;
+
+/// A change to a todo, queued while offline and replayed on reconnect.
+///
+/// `updated_at` is the version of the todo the change was made on. When the
+/// todo changed on the server since, the server version wins and the change
+/// is dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Mutation {
+    /// `id` is a local one, later changes to the todo refer to it until the
+    /// server assigned its own.
+    Create {
+        id: Uuid,
+        todo: NewTodo,
+    },
+    Rename {
+        id: Uuid,
+        name: String,
+        updated_at: DateTime<Utc>,
+    },
+    Complete {
+        id: Uuid,
+        updated_at: DateTime<Utc>,
+    },
+    Revert {
+        id: Uuid,
+        updated_at: DateTime<Utc>,
+    },
+    Delete {
+        id: Uuid,
+        updated_at: DateTime<Utc>,
+    },
+}
+
+impl Mutation {
+    fn id(&self) -> Uuid {
+        match self {
+            Mutation::Create { id, .. }
+            | Mutation::Rename { id, .. }
+            | Mutation::Complete { id, .. }
+            | Mutation::Revert { id, .. }
+            | Mutation::Delete { id, .. } => *id,
+        }
+    }
+
+    fn updated_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Mutation::Create { .. } => None,
+            Mutation::Rename { updated_at, .. }
+            | Mutation::Complete { updated_at, .. }
+            | Mutation::Revert { updated_at, .. }
+            | Mutation::Delete { updated_at, .. } => Some(*updated_at),
+        }
+    }
+}
+
+fn log_error(err: JsValue) {
+    log::error!("{err:?}");
+}
+
+pub fn is_online() -> bool {
+    web_sys::window()
+        .map(|window| window.navigator().on_line())
+        .unwrap_or(true)
+}
+
+/// Lets `sw.js` cache the app shell so the app starts without a connection.
+pub fn register_service_worker() {
+    if let Some(window) = web_sys::window() {
+        let _ = window.navigator().service_worker().register("/sw.js");
+    }
+}
+
+/// Keeps the profile to sign in with while offline.
+pub fn save_profile(user: &User, preferences: &Preferences) {
+    let _ = LocalStorage::set("profile", (user, preferences));
+}
+
+pub fn saved_profile() -> Option<(User, Preferences)> {
+    LocalStorage::get("profile").ok()
+}
+
+/// Forgets everything kept for offline use, on sign out.
+pub fn clear() {
+    LocalStorage::delete("profile");
+
+    spawn_local(async {
+        for name in [idb::TODOS, idb::OUTBOX] {
+            if let Err(err) = idb::clear(name).await {
+                log_error(err);
+            }
+        }
+    });
+}
+
+async fn cache_put(todo: &Todo) -> Result<(), JsValue> {
+    let value = serde_json::to_string(todo).map_err(|err| err.to_string())?;
+
+    idb::put(idb::TODOS, Some(&todo.id.to_string()), &value).await?;
+
+    Ok(())
+}
+
+async fn cache_delete(id: Uuid) -> Result<(), JsValue> {
+    idb::delete(idb::TODOS, &id.to_string().into()).await
+}
+
+async fn cached() -> Vec<Todo> {
+    match idb::entries(idb::TODOS).await {
+        Ok(entries) => entries
+            .into_iter()
+            .filter_map(|(_, value)| serde_json::from_str(&value).ok())
+            .collect(),
+        Err(err) => {
+            log_error(err);
+
+            Vec::new()
+        }
+    }
+}
+
+/// Keeps `todos` for offline use.
+pub async fn cache_todos(todos: &[Todo]) {
+    for todo in todos {
+        if let Err(err) = cache_put(todo).await {
+            return log_error(err);
+        }
+    }
+}
+
+/// Page of the cached todos matching `query`, newest first like the API.
+/// Sorting by custom fields isn't available offline.
+pub async fn cached_todos(query: &TodosQuery) -> Paginated<Todo> {
+    let search = query.search.as_ref().map(|search| search.to_lowercase());
+
+    let mut todos = cached()
+        .await
+        .into_iter()
+        .filter(|todo| {
+            query
+                .is_completed
+                .is_none_or(|is_completed| todo.is_completed == is_completed)
+                && query
+                    .project_id
+                    .is_none_or(|project_id| todo.project_id == Some(project_id))
+                && search
+                    .as_ref()
+                    .is_none_or(|search| todo.name.to_lowercase().contains(search))
+        })
+        .collect::<Vec<_>>();
+
+    todos.sort_by_key(|todo| Reverse(todo.created_at));
+
+    Paginated {
+        count: todos.len(),
+        data: todos
+            .into_iter()
+            .skip(query.offset)
+            .take(query.limit)
+            .collect(),
+    }
+}
+
+/// Shows `mutation` in the cached todos until the server has it.
+async fn apply_locally(mutation: &Mutation) -> Result<(), JsValue> {
+    let now = Utc::now();
+
+    if let Mutation::Create { id, todo } = mutation {
+        return cache_put(&Todo {
+            id: *id,
+            project_id: todo.project_id,
+            name: todo.name.clone(),
+            is_completed: false,
+            estimate_minutes: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+            due_at: None,
+            parent_id: None,
+            status_id: None,
+            fields: todo.fields.clone(),
+        })
+        .await;
+    }
+
+    let Some(mut todo) = cached()
+        .await
+        .into_iter()
+        .find(|todo| todo.id == mutation.id())
+    else {
+        return Ok(());
+    };
+
+    match mutation {
+        Mutation::Rename { name, .. } => todo.name = name.clone(),
+        Mutation::Complete { .. } => {
+            todo.is_completed = true;
+            todo.completed_at = Some(now);
+        }
+        Mutation::Revert { .. } => {
+            todo.is_completed = false;
+            todo.completed_at = None;
+        }
+        Mutation::Delete { .. } => return cache_delete(todo.id).await,
+        Mutation::Create { .. } => {}
+    }
+
+    cache_put(&todo).await
+}
+
+/// Publishes the number of queued changes to the store.
+pub async fn refresh_pending() {
+    match idb::entries(idb::OUTBOX).await {
+        Ok(entries) => Store::dispatch(Action::SetPendingMutations(entries.len())),
+        Err(err) => log_error(err),
+    }
+}
+
+/// Applies `mutation` to the cached todos and queues it for the server.
+pub async fn queue(mutation: Mutation) {
+    if let Err(err) = apply_locally(&mutation).await {
+        log_error(err);
+    }
+
+    match serde_json::to_string(&mutation) {
+        Ok(value) => {
+            if let Err(err) = idb::put(idb::OUTBOX, None, &value).await {
+                log_error(err);
+            }
+        }
+        Err(err) => log::error!("{err}"),
+    }
+
+    refresh_pending().await;
+}
+
+/// Sends a change with `request`, or queues `mutation` instead when there's
+/// no connection. The response is `None` for a queued change.
+pub async fn send<T, F>(mutation: Mutation, request: F) -> ApiResult<Option<T>>
+where
+    F: Future<Output = ApiResult<T>>,
+{
+    if is_online() {
+        match request.await {
+            Err(ApiError::Network(_)) => {
+                Store::dispatch(Action::SetSyncStatus(SyncStatus::Offline))
+            }
+            result => return result.map(Some),
+        }
+    }
+
+    queue(mutation).await;
+
+    Ok(None)
+}
+
+/// Server ids of todos created offline and the versions left by replayed
+/// changes, so later changes to the same todo don't count as conflicts.
+#[derive(Default)]
+struct Replay {
+    ids: HashMap<Uuid, Uuid>,
+    versions: HashMap<Uuid, DateTime<Utc>>,
+}
+
+impl Replay {
+    async fn run(&mut self, api: &Api, mutation: &Mutation) -> ApiResult<()> {
+        if let Mutation::Create { id, todo } = mutation {
+            let created = api.new_todo(todo.clone()).await?;
+
+            cache_delete(*id).await.map_err(log_error).ok();
+            cache_put(&created).await.map_err(log_error).ok();
+
+            self.ids.insert(*id, created.id);
+            self.versions.insert(created.id, created.updated_at);
+
+            return Ok(());
+        }
+
+        let id = self
+            .ids
+            .get(&mutation.id())
+            .copied()
+            .unwrap_or(mutation.id());
+
+        let current = match api.get_todo(id).await {
+            Ok(todo) => todo,
+            Err(ApiError::NotFound(_) | ApiError::Forbidden(_)) => {
+                cache_delete(id).await.map_err(log_error).ok();
+
+                Store::dispatch(Action::AlertWarning(t("offline.deleted_elsewhere")));
+
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        };
+
+        let version = self.versions.get(&id).copied().or(mutation.updated_at());
+
+        if version.is_some_and(|version| current.updated_at > version) {
+            cache_put(&current).await.map_err(log_error).ok();
+
+            Store::dispatch(Action::AlertWarning(i18n::t_args(
+                "offline.changed_elsewhere",
+                &[("name", &current.name)],
+            )));
+
+            return Ok(());
+        }
+
+        let todo = match mutation {
+            Mutation::Rename { name, .. } => Some(
+                api.update_todo(id, RenameTodo { name: name.clone() })
+                    .await?,
+            ),
+            Mutation::Complete { .. } => Some(api.complete_todo(id).await?),
+            Mutation::Revert { .. } => Some(api.revert_todo(id).await?),
+            Mutation::Delete { .. } => {
+                api.delete_todo(id).await?;
+
+                None
+            }
+            Mutation::Create { .. } => None,
+        };
+
+        match todo {
+            Some(todo) => {
+                self.versions.insert(id, todo.updated_at);
+
+                cache_put(&todo).await.map_err(log_error).ok();
+            }
+            None => {
+                cache_delete(id).await.map_err(log_error).ok();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Replays the queued changes in order, stops at the first one that doesn't
+/// reach the server or fails for the session or the server rather than the
+/// change, those are kept for the next sync.
+async fn replay(api: &Api) -> SyncStatus {
+    let entries = match idb::entries(idb::OUTBOX).await {
+        Ok(entries) => entries,
+        Err(err) => {
+            log_error(err);
+
+            return SyncStatus::Online;
+        }
+    };
+
+    let mut replay = Replay::default();
+
+    for (key, value) in entries {
+        match serde_json::from_str::<Mutation>(&value) {
+            Ok(mutation) => match replay.run(api, &mutation).await {
+                Ok(()) => {}
+                Err(ApiError::Network(_)) => return SyncStatus::Offline,
+                Err(
+                    err @ (ApiError::TokenExpired
+                    | ApiError::Unauthorized(_)
+                    | ApiError::Reqwest(_)),
+                ) => {
+                    Store::dispatch(Action::AlertError(err.to_string()));
+
+                    return SyncStatus::Online;
+                }
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            },
+            Err(err) => log::error!("{err}"),
+        }
+
+        if let Err(err) = idb::delete(idb::OUTBOX, &key).await {
+            log_error(err);
+        }
+    }
+
+    SyncStatus::Online
+}
+
+/// Sends the changes queued while offline, one sync runs at a time.
+pub async fn sync(api: &Api) {
+    thread_local! {
+        static IS_SYNCING: Cell<bool> = const { Cell::new(false) };
+    };
+
+    if IS_SYNCING.with(|is_syncing| is_syncing.replace(true)) {
+        return;
+    }
+
+    Store::dispatch(Action::SetSyncStatus(SyncStatus::Syncing));
+
+    let status = replay(api).await;
+
+    IS_SYNCING.with(|is_syncing| is_syncing.set(false));
+
+    refresh_pending().await;
+
+    Store::dispatch(Action::SetSyncStatus(status));
+}
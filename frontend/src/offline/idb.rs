@@ -0,0 +1,121 @@
+use js_sys::{Array, Promise};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    IdbDatabase, IdbObjectStore, IdbObjectStoreParameters, IdbOpenDbRequest, IdbRequest,
+    IdbTransactionMode, IdbVersionChangeEvent,
+};
+
+const NAME: &str = "todos";
+const VERSION: u32 = 1;
+
+/// Todos as JSON keyed by their id.
+pub const TODOS: &str = "todos";
+/// Pending mutations as JSON keyed by an increasing number.
+pub const OUTBOX: &str = "outbox";
+
+/// Waits for `request` and returns its result.
+async fn wait(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+
+    JsFuture::from(promise).await?;
+
+    request.result()
+}
+
+async fn open() -> Result<IdbDatabase, JsValue> {
+    let factory = web_sys::window()
+        .ok_or("No window")?
+        .indexed_db()?
+        .ok_or("IndexedDB is unavailable")?;
+
+    let request = factory.open_with_u32(NAME, VERSION)?;
+
+    let on_upgrade =
+        Closure::<dyn FnMut(IdbVersionChangeEvent)>::new(move |e: IdbVersionChangeEvent| {
+            let Some(db) = e
+                .target()
+                .and_then(|target| target.dyn_into::<IdbOpenDbRequest>().ok())
+                .and_then(|request| request.result().ok())
+            else {
+                return;
+            };
+
+            let db = db.unchecked_into::<IdbDatabase>();
+
+            db.create_object_store(TODOS).ok();
+
+            db.create_object_store_with_optional_parameters(
+                OUTBOX,
+                IdbObjectStoreParameters::new().auto_increment(true),
+            )
+            .ok();
+        });
+
+    request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+
+    let db = wait(&request).await?;
+
+    request.set_onupgradeneeded(None);
+
+    Ok(db.unchecked_into())
+}
+
+async fn store(name: &str, mode: IdbTransactionMode) -> Result<IdbObjectStore, JsValue> {
+    open()
+        .await?
+        .transaction_with_str_and_mode(name, mode)?
+        .object_store(name)
+}
+
+/// Stores `value` under `key`, or under the next number without one.
+/// Returns the key.
+pub async fn put(name: &str, key: Option<&str>, value: &str) -> Result<JsValue, JsValue> {
+    let store = store(name, IdbTransactionMode::Readwrite).await?;
+
+    let request = match key {
+        Some(key) => store.put_with_key(&value.into(), &key.into())?,
+        None => store.add(&value.into())?,
+    };
+
+    wait(&request).await
+}
+
+pub async fn delete(name: &str, key: &JsValue) -> Result<(), JsValue> {
+    let store = store(name, IdbTransactionMode::Readwrite).await?;
+
+    wait(&store.delete(key)?).await?;
+
+    Ok(())
+}
+
+pub async fn clear(name: &str) -> Result<(), JsValue> {
+    let store = store(name, IdbTransactionMode::Readwrite).await?;
+
+    wait(&store.clear()?).await?;
+
+    Ok(())
+}
+
+/// Keys and values of the store in key order.
+pub async fn entries(name: &str) -> Result<Vec<(JsValue, String)>, JsValue> {
+    let store = store(name, IdbTransactionMode::Readonly).await?;
+
+    // Both requests are made up front, the transaction ends once it's idle.
+    let keys = store.get_all_keys()?;
+
+    let values = store.get_all()?;
+
+    let keys = wait(&keys).await?;
+
+    let values = wait(&values).await?;
+
+    Ok(Array::from(&keys)
+        .iter()
+        .zip(Array::from(&values).iter())
+        .filter_map(|(key, value)| Some((key, value.as_string()?)))
+        .collect())
+}
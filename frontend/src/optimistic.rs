@@ -0,0 +1,155 @@
+use std::{cell::RefCell, collections::HashMap, future::Future};
+
+use chrono::Utc;
+use gloo_timers::callback::Timeout;
+use uuid::Uuid;
+use yew::{platform::spawn_local, Callback};
+use yewdux::prelude::*;
+
+use crate::{
+    api::{types::Todo, Api, ApiResult},
+    i18n::t,
+    offline::{self, Mutation},
+    store::{Action, Alert, Store},
+};
+
+/// How long deletes and completes can be undone.
+pub const UNDO_MILLIS: u32 = 5_000;
+
+thread_local! {
+    /// Deletes waiting for the grace period, dropping one cancels it.
+    static DELETES: RefCell<HashMap<Uuid, Timeout>> = RefCell::new(HashMap::new());
+}
+
+/// Shows `todo` changed by `change` right away, then settles it with the
+/// server: its version on success, `todo` as it was on an error. Changes
+/// queued offline stay as they are.
+async fn apply<F>(todo: &Todo, change: impl FnOnce(&mut Todo), request: F) -> ApiResult<()>
+where
+    F: Future<Output = ApiResult<Option<Todo>>>,
+{
+    let mut changed = todo.clone();
+
+    change(&mut changed);
+
+    Store::dispatch(Action::SetTodo(changed));
+
+    match request.await {
+        Ok(Some(todo)) => {
+            Store::dispatch(Action::SetTodo(todo));
+
+            Ok(())
+        }
+        Ok(None) => Ok(()),
+        Err(err) => {
+            Store::dispatch(Action::SetTodo(todo.clone()));
+
+            Err(err)
+        }
+    }
+}
+
+async fn set_completed(api: &Api, todo: &Todo, is_completed: bool) -> ApiResult<()> {
+    let (id, updated_at) = (todo.id, todo.updated_at);
+
+    let change = |todo: &mut Todo| {
+        todo.is_completed = is_completed;
+        todo.completed_at = is_completed.then(Utc::now);
+    };
+
+    match is_completed {
+        true => {
+            let request =
+                offline::send(Mutation::Complete { id, updated_at }, api.complete_todo(id));
+
+            apply(todo, change, request).await
+        }
+        false => {
+            let request = offline::send(Mutation::Revert { id, updated_at }, api.revert_todo(id));
+
+            apply(todo, change, request).await
+        }
+    }
+}
+
+/// Completes or reverts `todo`, completing it can be undone for a while.
+pub async fn toggle_completed(api: &Api, todo: &Todo) -> ApiResult<()> {
+    let is_completed = !todo.is_completed;
+
+    set_completed(api, todo, is_completed).await?;
+
+    if is_completed {
+        let api = api.clone();
+
+        let id = todo.id;
+
+        let undo = Callback::from(move |()| {
+            let api = api.clone();
+
+            let todo = Dispatch::<Store>::new().get().todos.get(&id).cloned();
+
+            spawn_local(async move {
+                let Some(todo) = todo else {
+                    return;
+                };
+
+                if let Err(err) = set_completed(&api, &todo, false).await {
+                    Store::dispatch(Action::AlertError(err.to_string()));
+                }
+            });
+        });
+
+        Store::dispatch(Action::Alert(
+            Alert::new_success(&t("todos.completed")).with_undo(undo),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Hides `todo` and deletes it once the grace period passed without an undo.
+/// It shows up again when the delete fails.
+pub fn delete(api: &Api, todo: &Todo) {
+    let (id, updated_at) = (todo.id, todo.updated_at);
+
+    Store::dispatch(Action::HideTodo(id));
+
+    let timeout = {
+        let api = api.clone();
+
+        Timeout::new(UNDO_MILLIS, move || {
+            spawn_local(async move {
+                // Dropped here rather than in its own callback.
+                DELETES.with(|deletes| deletes.borrow_mut().remove(&id));
+
+                let result =
+                    offline::send(Mutation::Delete { id, updated_at }, api.delete_todo(id)).await;
+
+                match result {
+                    Ok(_) => Store::dispatch(Action::RemoveTodo(id)),
+                    Err(err) => {
+                        Store::dispatch(Action::ShowTodo(id));
+
+                        Store::dispatch(Action::AlertError(err.to_string()));
+                    }
+                }
+            });
+        })
+    };
+
+    DELETES.with(|deletes| deletes.borrow_mut().insert(id, timeout));
+
+    let undo = Callback::from(move |()| {
+        let timeout = DELETES.with(|deletes| deletes.borrow_mut().remove(&id));
+
+        if let Some(timeout) = timeout {
+            timeout.cancel();
+
+            Store::dispatch(Action::ShowTodo(id));
+        }
+    });
+
+    Store::dispatch(Action::Alert(
+        Alert::new_success(&t("todos.deleted")).with_undo(undo),
+    ));
+}
@@ -1,3 +1,8 @@
+pub mod board;
+pub mod calendar;
+pub mod import;
+pub mod settings;
 pub mod sign_in;
 pub mod sign_up;
+pub mod stats;
 pub mod todos;
@@ -0,0 +1,499 @@
+use uuid::Uuid;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::{platform::spawn_local, prelude::*};
+use yew_hooks::prelude::*;
+
+use crate::{
+    api::{
+        events::use_events,
+        types::{BoardColumn, NewStatus, StatusCategory, Todo, Transition, UpdateStatus},
+        use_api, ApiResult,
+    },
+    i18n::{self, t},
+    store::{Action, Store},
+};
+
+/// Runs a board change and reloads the board once it's stored.
+fn change<T, F>(request: F, on_change: Callback<()>)
+where
+    T: 'static,
+    F: std::future::Future<Output = ApiResult<T>> + 'static,
+{
+    spawn_local(async move {
+        match request.await {
+            Ok(_) => on_change.emit(()),
+            Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+        }
+    });
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+struct CardProps {
+    todo: Todo,
+    ondragstart: Callback<Uuid>,
+}
+
+#[function_component(Card)]
+fn card(props: &CardProps) -> Html {
+    let ondragstart = {
+        let ondragstart = props.ondragstart.clone();
+
+        let id = props.todo.id;
+
+        move |_: DragEvent| ondragstart.emit(id)
+    };
+
+    html!(
+        <div class="card card-compact bg-base-100 shadow cursor-move" draggable="true" { ondragstart }>
+            <div class="card-body">
+                <span class={ classes!(props.todo.is_completed.then_some("line-through")) }>
+                    { props.todo.name.clone() }
+                </span>
+            </div>
+        </div>
+    )
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct ColumnProps {
+    column: BoardColumn,
+    /// Neighbour statuses the column can swap places with.
+    previous: Option<(Uuid, i32)>,
+    next: Option<(Uuid, i32)>,
+    ondragstart: Callback<Uuid>,
+    ondrop: Callback<Uuid>,
+    on_change: Callback<()>,
+}
+
+#[function_component(Column)]
+fn column(props: &ColumnProps) -> Html {
+    let api = use_api();
+
+    let status = &props.column.status;
+
+    let swap = {
+        let api = api.clone();
+
+        let on_change = props.on_change.clone();
+
+        let (id, position) = (status.id, status.position);
+
+        move |neighbour: Option<(Uuid, i32)>| {
+            let api = api.clone();
+
+            let on_change = on_change.clone();
+
+            move |_: MouseEvent| {
+                let Some((neighbour_id, neighbour_position)) = neighbour else {
+                    return;
+                };
+
+                let api = api.clone();
+
+                change(
+                    async move {
+                        api.update_status(
+                            id,
+                            UpdateStatus {
+                                position: Some(neighbour_position),
+                                ..Default::default()
+                            },
+                        )
+                        .await?;
+
+                        api.update_status(
+                            neighbour_id,
+                            UpdateStatus {
+                                position: Some(position),
+                                ..Default::default()
+                            },
+                        )
+                        .await
+                    },
+                    on_change.clone(),
+                );
+            }
+        }
+    };
+
+    let set_category = {
+        let api = api.clone();
+
+        let on_change = props.on_change.clone();
+
+        let id = status.id;
+
+        move |e: Event| {
+            let select = e.target_dyn_into::<HtmlSelectElement>().unwrap();
+
+            let Some(category) = StatusCategory::ALL.get(select.selected_index() as usize) else {
+                return;
+            };
+
+            let changes = UpdateStatus {
+                category: Some(*category),
+                ..Default::default()
+            };
+
+            let api = api.clone();
+
+            change(
+                async move { api.update_status(id, changes).await },
+                on_change.clone(),
+            );
+        }
+    };
+
+    let rename = {
+        let api = api.clone();
+
+        let on_change = props.on_change.clone();
+
+        let id = status.id;
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            let changes = UpdateStatus {
+                name: Some(input.value()).filter(|name| !name.is_empty()),
+                ..Default::default()
+            };
+
+            let api = api.clone();
+
+            change(
+                async move { api.update_status(id, changes).await },
+                on_change.clone(),
+            );
+        }
+    };
+
+    let delete = {
+        let on_change = props.on_change.clone();
+
+        let id = status.id;
+
+        move |_| {
+            let api = api.clone();
+
+            change(
+                async move { api.delete_status(id).await },
+                on_change.clone(),
+            );
+        }
+    };
+
+    let ondrop = {
+        let ondrop = props.ondrop.clone();
+
+        let id = status.id;
+
+        move |e: DragEvent| {
+            e.prevent_default();
+
+            ondrop.emit(id);
+        }
+    };
+
+    html!(
+        <div
+            class="flex flex-col gap-2 w-72 shrink-0 rounded-lg bg-base-200 p-2"
+            ondragover={ |e: DragEvent| e.prevent_default() }
+            { ondrop }
+        >
+            <div class="flex items-center gap-2">
+                <input
+                    class="input input-ghost input-sm font-bold flex-1 min-w-0"
+                    value={ status.name.clone() }
+                    onchange={ rename }
+                />
+                <span class="badge">{ props.column.todos.len() }</span>
+                <div class="dropdown dropdown-end">
+                    <label tabindex="0" class="btn btn-ghost btn-xs">{ "⋯" }</label>
+                    <ul tabindex="0" class="dropdown-content menu p-2 shadow bg-base-100 rounded-box w-52 z-10">
+                        <li>
+                            <select class="select select-bordered select-sm" onchange={ set_category }>
+                                {
+                                    StatusCategory::ALL
+                                        .iter()
+                                        .map(|category| html!(
+                                            <option selected={ *category == status.category }>
+                                                { category.label() }
+                                            </option>
+                                        ))
+                                        .collect::<Html>()
+                                }
+                            </select>
+                        </li>
+                        if props.previous.is_some() {
+                            <li><button onclick={ swap(props.previous) }>{ t("board.move_left") }</button></li>
+                        }
+                        if props.next.is_some() {
+                            <li><button onclick={ swap(props.next) }>{ t("board.move_right") }</button></li>
+                        }
+                        <li><button class="text-error" onclick={ delete }>{ t("board.delete_column") }</button></li>
+                    </ul>
+                </div>
+            </div>
+            {
+                props.column.todos
+                    .iter()
+                    .map(|todo| html!(
+                        <Card todo={ todo.clone() } ondragstart={ props.ondragstart.clone() } />
+                    ))
+                    .collect::<Html>()
+            }
+        </div>
+    )
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct NewColumnProps {
+    project_id: Uuid,
+    on_change: Callback<()>,
+}
+
+#[function_component(NewColumn)]
+fn new_column(props: &NewColumnProps) -> Html {
+    let api = use_api();
+
+    let name_handle = use_state(String::new);
+
+    let category_handle = use_state(|| StatusCategory::InProgress);
+
+    let set_name = {
+        let name_handle = name_handle.clone();
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            name_handle.set(input.value());
+        }
+    };
+
+    let set_category = {
+        let category_handle = category_handle.clone();
+
+        move |e: Event| {
+            let select = e.target_dyn_into::<HtmlSelectElement>().unwrap();
+
+            if let Some(category) = StatusCategory::ALL.get(select.selected_index() as usize) {
+                category_handle.set(*category);
+            }
+        }
+    };
+
+    let onsubmit = {
+        let name_handle = name_handle.clone();
+
+        let category_handle = category_handle.clone();
+
+        let project_id = props.project_id;
+
+        let on_change = props.on_change.clone();
+
+        move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let status = NewStatus {
+                name: (*name_handle).clone(),
+                category: *category_handle,
+            };
+
+            let api = api.clone();
+
+            name_handle.set(String::new());
+
+            change(
+                async move { api.create_status(project_id, status).await },
+                on_change.clone(),
+            );
+        }
+    };
+
+    html!(
+        <form class="flex flex-col gap-2 w-72 shrink-0 rounded-lg border-2 border-dashed border-base-300 p-2" { onsubmit }>
+            <input
+                class="input input-bordered input-sm"
+                placeholder={ t("board.new_column") }
+                required={ true }
+                value={ (*name_handle).clone() }
+                onchange={ set_name }
+            />
+            <select class="select select-bordered select-sm" onchange={ set_category }>
+                {
+                    StatusCategory::ALL
+                        .iter()
+                        .map(|category| html!(
+                            <option selected={ *category == *category_handle }>{ category.label() }</option>
+                        ))
+                        .collect::<Html>()
+                }
+            </select>
+            <button type="submit" class="btn btn-sm">{ t("board.add_column") }</button>
+        </form>
+    )
+}
+
+#[function_component(Kanban)]
+pub fn kanban() -> Html {
+    let api = use_api();
+
+    use_title(i18n::t_args("title", &[("page", &t("nav.board"))]));
+
+    let project_handle = use_state(|| None::<Uuid>);
+
+    let dragged_handle = use_state(|| None::<Uuid>);
+
+    let projects_handle = {
+        let api = api.clone();
+
+        let project_handle = project_handle.clone();
+
+        use_async_with_options(
+            async move {
+                let result = api.projects().await;
+
+                if let Ok(projects) = &result {
+                    if project_handle.is_none() {
+                        project_handle.set(projects.first().map(|project| project.id));
+                    }
+                }
+
+                result
+            },
+            UseAsyncOptions::enable_auto(),
+        )
+    };
+
+    let board_handle = {
+        let api = api.clone();
+
+        let project_id = *project_handle;
+
+        use_async(async move {
+            let Some(project_id) = project_id else {
+                return Ok(None);
+            };
+
+            let result = api.board(project_id).await;
+
+            if let Err(err) = &result {
+                Store::dispatch(Action::AlertError(err.to_string()));
+            }
+
+            result.map(Some)
+        })
+    };
+
+    {
+        let board_handle = board_handle.clone();
+
+        use_effect_with_deps(move |_| board_handle.run(), *project_handle);
+    }
+
+    {
+        let board_handle = board_handle.clone();
+
+        use_events(Callback::from(move |_| board_handle.run()));
+    }
+
+    let on_change = {
+        let board_handle = board_handle.clone();
+
+        Callback::from(move |_| board_handle.run())
+    };
+
+    let set_project = {
+        let project_handle = project_handle.clone();
+
+        move |e: Event| {
+            let select = e.target_dyn_into::<HtmlSelectElement>().unwrap();
+
+            project_handle.set(select.value().parse().ok());
+        }
+    };
+
+    let ondragstart = {
+        let dragged_handle = dragged_handle.clone();
+
+        Callback::from(move |id: Uuid| dragged_handle.set(Some(id)))
+    };
+
+    let ondrop = {
+        let on_change = on_change.clone();
+
+        Callback::from(move |status_id: Uuid| {
+            let Some(id) = *dragged_handle else {
+                return;
+            };
+
+            dragged_handle.set(None);
+
+            let api = api.clone();
+
+            change(
+                async move { api.transition_todo(id, Transition { status_id }).await },
+                on_change.clone(),
+            );
+        })
+    };
+
+    let columns = board_handle
+        .data
+        .iter()
+        .flatten()
+        .flat_map(|board| board.columns.iter())
+        .collect::<Vec<_>>();
+
+    let neighbour = |index: Option<usize>| {
+        index
+            .and_then(|index| columns.get(index))
+            .map(|column| (column.status.id, column.status.position))
+    };
+
+    html!(
+        <main class="relative mx-auto space-y-4 py-2">
+            <select class="select select-bordered" onchange={ set_project }>
+                {
+                    projects_handle
+                        .data
+                        .iter()
+                        .flatten()
+                        .map(|project| html!(
+                            <option value={ project.id.to_string() } selected={ *project_handle == Some(project.id) }>
+                                { project.name.clone() }
+                            </option>
+                        ))
+                        .collect::<Html>()
+                }
+            </select>
+
+            if projects_handle.data.as_ref().is_some_and(|projects| projects.is_empty()) {
+                <h2 class="w-full p-10 text-center">{ t("board.no_projects") }</h2>
+            }
+
+            <div class="flex gap-4 overflow-x-auto items-start pb-4">
+                {
+                    columns
+                        .iter()
+                        .enumerate()
+                        .map(|(index, column)| html!(
+                            <Column
+                                column={ (*column).clone() }
+                                previous={ neighbour(index.checked_sub(1)) }
+                                next={ neighbour(Some(index + 1)) }
+                                ondragstart={ ondragstart.clone() }
+                                ondrop={ ondrop.clone() }
+                                on_change={ on_change.clone() }
+                            />
+                        ))
+                        .collect::<Html>()
+                }
+                if let Some(project_id) = *project_handle {
+                    <NewColumn { project_id } on_change={ on_change.clone() } />
+                }
+            </div>
+        </main>
+    )
+}
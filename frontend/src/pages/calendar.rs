@@ -0,0 +1,374 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use uuid::Uuid;
+use yew::{platform::spawn_local, prelude::*};
+use yew_hooks::prelude::*;
+use yew_router::prelude::*;
+
+use crate::{
+    api::{
+        events::use_events,
+        types::{CalendarQuery, DueDateChange, Todo, WeekStart},
+        use_api,
+    },
+    i18n::{self, t},
+    preferences,
+    router::PrivateRoute,
+    store::{Action, Store},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CalendarView {
+    Month,
+    Week,
+}
+
+impl CalendarView {
+    /// First day shown, month grids are padded to full weeks.
+    fn start(&self, anchor: NaiveDate, week_start: WeekStart) -> NaiveDate {
+        let date = match self {
+            CalendarView::Month => anchor.with_day(1).unwrap(),
+            CalendarView::Week => anchor,
+        };
+
+        let days = match week_start {
+            WeekStart::Monday => date.weekday().num_days_from_monday(),
+            WeekStart::Sunday => date.weekday().num_days_from_sunday(),
+        };
+
+        date - Duration::days(days.into())
+    }
+
+    fn days(&self) -> i64 {
+        match self {
+            CalendarView::Month => 42,
+            CalendarView::Week => 7,
+        }
+    }
+
+    fn step(&self, anchor: NaiveDate, forward: bool) -> NaiveDate {
+        match (self, forward) {
+            (CalendarView::Month, true) => (anchor.with_day(1).unwrap() + Duration::days(32))
+                .with_day(1)
+                .unwrap(),
+            (CalendarView::Month, false) => (anchor.with_day(1).unwrap() - Duration::days(1))
+                .with_day(1)
+                .unwrap(),
+            (CalendarView::Week, true) => anchor + Duration::weeks(1),
+            (CalendarView::Week, false) => anchor - Duration::weeks(1),
+        }
+    }
+}
+
+fn local_midnight(date: NaiveDate) -> DateTime<Utc> {
+    preferences::from_local(&date.and_hms_opt(0, 0, 0).unwrap())
+}
+
+/// Day a todo is placed on, its due date or else its completion date.
+fn todo_day(todo: &Todo) -> Option<NaiveDate> {
+    todo.due_at
+        .or(todo.completed_at)
+        .map(|date| preferences::to_local(&date).date_naive())
+}
+
+/// Moves the due date to another day keeping its time, todos without one
+/// become due at 9:00.
+fn moved_due_at(todo: &Todo, day: NaiveDate) -> DateTime<Utc> {
+    let time = todo
+        .due_at
+        .map(|due_at| preferences::to_local(&due_at).time())
+        .unwrap_or_else(|| NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+
+    preferences::from_local(&day.and_time(time))
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+struct CalendarTodoProps {
+    todo: Todo,
+    show_time: bool,
+    ondragstart: Callback<Uuid>,
+}
+
+#[function_component(CalendarTodo)]
+fn calendar_todo(props: &CalendarTodoProps) -> Html {
+    let todo = &props.todo;
+
+    let ondragstart = {
+        let ondragstart = props.ondragstart.clone();
+
+        let id = todo.id;
+
+        move |_: DragEvent| ondragstart.emit(id)
+    };
+
+    let time = todo
+        .due_at
+        .map(|due_at| preferences::to_local(&due_at).format("%H:%M").to_string());
+
+    html!(
+        <div
+            class={ classes!(
+                "badge",
+                "badge-sm",
+                "w-full",
+                "justify-start",
+                "truncate",
+                if todo.is_completed { "badge-ghost line-through" } else { "badge-primary cursor-move" },
+            ) }
+            title={ todo.name.clone() }
+            draggable={ (!todo.is_completed).to_string() }
+            { ondragstart }
+        >
+            if props.show_time {
+                if let Some(time) = time {
+                    <span class="mr-1 opacity-70">{ time }</span>
+                }
+            }
+            { todo.name.clone() }
+        </div>
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct CalendarProps {
+    pub view: CalendarView,
+}
+
+#[function_component(Calendar)]
+pub fn calendar(props: &CalendarProps) -> Html {
+    let api = use_api();
+
+    use_title(i18n::t_args(
+        "title",
+        &[(
+            "page",
+            &t(match props.view {
+                CalendarView::Month => "calendar.month",
+                CalendarView::Week => "calendar.week",
+            }),
+        )],
+    ));
+
+    let today = preferences::today();
+
+    let anchor_handle = use_state(|| today);
+
+    let dragged_handle = use_state(|| None::<Uuid>);
+
+    let start = props
+        .view
+        .start(*anchor_handle, preferences::current().week_start);
+
+    let days = (0..props.view.days())
+        .map(|i| start + Duration::days(i))
+        .collect::<Vec<_>>();
+
+    let todos_handle = {
+        let api = api.clone();
+
+        let query = CalendarQuery {
+            from: local_midnight(start),
+            to: local_midnight(start + Duration::days(props.view.days())),
+        };
+
+        use_async(async move {
+            let result = api.calendar_todos(query).await;
+
+            if let Err(err) = &result {
+                Store::dispatch(Action::AlertError(err.to_string()));
+            }
+
+            result
+        })
+    };
+
+    {
+        let todos_handle = todos_handle.clone();
+
+        use_effect_with_deps(move |_| todos_handle.run(), (start, props.view));
+    }
+
+    {
+        let todos_handle = todos_handle.clone();
+
+        use_events(Callback::from(move |_| todos_handle.run()));
+    }
+
+    let navigate = {
+        let anchor_handle = anchor_handle.clone();
+
+        let view = props.view;
+
+        move |forward: Option<bool>| {
+            let anchor_handle = anchor_handle.clone();
+
+            move |_| match forward {
+                Some(forward) => anchor_handle.set(view.step(*anchor_handle, forward)),
+                None => anchor_handle.set(today),
+            }
+        }
+    };
+
+    let ondragstart = {
+        let dragged_handle = dragged_handle.clone();
+
+        Callback::from(move |id: Uuid| dragged_handle.set(Some(id)))
+    };
+
+    let ondragover = |e: DragEvent| e.prevent_default();
+
+    let ondrop = {
+        let todos_handle = todos_handle.clone();
+
+        let dragged_handle = dragged_handle.clone();
+
+        move |day: NaiveDate| {
+            let api = api.clone();
+
+            let todos_handle = todos_handle.clone();
+
+            let dragged_handle = dragged_handle.clone();
+
+            move |e: DragEvent| {
+                e.prevent_default();
+
+                let Some(id) = *dragged_handle else {
+                    return;
+                };
+
+                dragged_handle.set(None);
+
+                let Some(todo) = todos_handle
+                    .data
+                    .iter()
+                    .flatten()
+                    .find(|todo| todo.id == id)
+                    .cloned()
+                else {
+                    return;
+                };
+
+                if todo_day(&todo) == Some(day) {
+                    return;
+                }
+
+                let api = api.clone();
+
+                let todos_handle = todos_handle.clone();
+
+                spawn_local(async move {
+                    let change = DueDateChange {
+                        due_at: Some(moved_due_at(&todo, day)),
+                    };
+
+                    match api.update_todo_due_date(todo.id, change).await {
+                        Ok(_) => todos_handle.run(),
+                        Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+                    }
+                });
+            }
+        }
+    };
+
+    let title = match props.view {
+        CalendarView::Month => i18n::format_date_with(
+            &anchor_handle,
+            &[("month", "long"), ("year", "numeric")],
+            "%B %Y",
+        ),
+        CalendarView::Week => format!(
+            "{} – {}",
+            i18n::format_date_with(&start, &[("day", "numeric"), ("month", "short")], "%d %b"),
+            i18n::format_date_with(
+                &(start + Duration::days(6)),
+                &[("day", "numeric"), ("month", "short"), ("year", "numeric")],
+                "%d %b %Y"
+            )
+        ),
+    };
+
+    let tab_classes =
+        |view: CalendarView| classes!("tab", (view == props.view).then_some("tab-active"));
+
+    html!(
+        <main class="relative max-w-screen-lg mx-auto space-y-4 py-2">
+            <div class="flex items-center gap-2">
+                <div class="tabs tabs-boxed">
+                    <Link<PrivateRoute> to={ PrivateRoute::CalendarMonth } classes={ tab_classes(CalendarView::Month) }>
+                        { t("calendar.month") }
+                    </Link<PrivateRoute>>
+                    <Link<PrivateRoute> to={ PrivateRoute::CalendarWeek } classes={ tab_classes(CalendarView::Week) }>
+                        { t("calendar.week") }
+                    </Link<PrivateRoute>>
+                </div>
+                <h2 class="text-xl font-bold mx-auto">{ title }</h2>
+                <div class="btn-group">
+                    <button class="btn btn-sm" onclick={ navigate(Some(false)) }>{ "‹" }</button>
+                    <button class="btn btn-sm" onclick={ navigate(None) }>{ t("calendar.today") }</button>
+                    <button class="btn btn-sm" onclick={ navigate(Some(true)) }>{ "›" }</button>
+                </div>
+            </div>
+
+            <div class="grid grid-cols-7 gap-1">
+                {
+                    days
+                        .iter()
+                        .take(7)
+                        .map(|day| html!(
+                            <div class="text-center text-sm font-bold">{ i18n::format_date_with(day, &[("weekday", "short")], "%a") }</div>
+                        ))
+                        .collect::<Html>()
+                }
+                {
+                    days
+                        .iter()
+                        .map(|day| {
+                            let todos = todos_handle
+                                .data
+                                .iter()
+                                .flatten()
+                                .filter(|todo| todo_day(todo) == Some(*day))
+                                .collect::<Vec<_>>();
+
+                            let outside = props.view == CalendarView::Month
+                                && day.month() != anchor_handle.month();
+
+                            html!(
+                                <div
+                                    class={ classes!(
+                                        "rounded-lg",
+                                        "bg-base-200",
+                                        "p-1",
+                                        "space-y-1",
+                                        "overflow-hidden",
+                                        match props.view {
+                                            CalendarView::Month => "h-28",
+                                            CalendarView::Week => "min-h-[24rem]",
+                                        },
+                                        outside.then_some("opacity-50"),
+                                        (*day == today).then_some("ring ring-primary"),
+                                    ) }
+                                    { ondragover }
+                                    ondrop={ ondrop(*day) }
+                                >
+                                    <div class="text-xs text-right">{ day.day() }</div>
+                                    {
+                                        todos
+                                            .into_iter()
+                                            .map(|todo| html!(
+                                                <CalendarTodo
+                                                    todo={ todo.clone() }
+                                                    show_time={ props.view == CalendarView::Week }
+                                                    ondragstart={ ondragstart.clone() }
+                                                />
+                                            ))
+                                            .collect::<Html>()
+                                    }
+                                </div>
+                            )
+                        })
+                        .collect::<Html>()
+                }
+            </div>
+        </main>
+    )
+}
@@ -0,0 +1,409 @@
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::{platform::spawn_local, prelude::*};
+use yew_hooks::prelude::*;
+use yew_router::prelude::*;
+
+use crate::{
+    api::{
+        types::{ImportError, ImportFormat, ImportMapping, NewImport},
+        use_api,
+    },
+    components::todo_row::DateTimeHumanizeExt,
+    i18n::{self, t},
+    router::PrivateRoute,
+    store::{Action, Store},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    Source,
+    Preview,
+    Done,
+}
+
+/// Mappable CSV fields with the translation keys of their labels.
+const FIELDS: [(&str, &str); 6] = [
+    ("name", "todos.name"),
+    ("project", "import.project"),
+    ("is_completed", "import.completed"),
+    ("created_at", "todos.created_at"),
+    ("completed_at", "todos.completed_at"),
+    ("due_at", "import.due_at"),
+];
+
+/// `error` translated by its code, the English message for unknown codes.
+fn error_message(error: &ImportError) -> String {
+    let key = format!("import.errors.{}", error.code);
+
+    if t(&key) == key {
+        return error.message.clone();
+    }
+
+    i18n::t_args(
+        &key,
+        &[
+            ("message", &error.message),
+            ("value", error.value.as_deref().unwrap_or_default()),
+        ],
+    )
+}
+
+fn mapping_field<'a>(mapping: &'a mut ImportMapping, field: &str) -> &'a mut Option<String> {
+    match field {
+        "project" => &mut mapping.project,
+        "is_completed" => &mut mapping.is_completed,
+        "created_at" => &mut mapping.created_at,
+        "completed_at" => &mut mapping.completed_at,
+        "due_at" => &mut mapping.due_at,
+        _ => &mut mapping.name,
+    }
+}
+
+fn mapping_value(mapping: &ImportMapping, field: &str) -> Option<String> {
+    match field {
+        "project" => mapping.project.clone(),
+        "is_completed" => mapping.is_completed.clone(),
+        "created_at" => mapping.created_at.clone(),
+        "completed_at" => mapping.completed_at.clone(),
+        "due_at" => mapping.due_at.clone(),
+        _ => mapping.name.clone(),
+    }
+}
+
+#[function_component(Import)]
+pub fn import() -> Html {
+    let api = use_api();
+
+    let step_handle = use_state(|| Step::Source);
+
+    let format_handle = use_state(|| ImportFormat::Csv);
+
+    let content_handle = use_state(String::new);
+
+    let mapping_handle = use_state(ImportMapping::default);
+
+    let new_import = NewImport {
+        format: *format_handle,
+        content: (*content_handle).clone(),
+        mapping: (*mapping_handle).clone(),
+        dry_run: true,
+    };
+
+    let preview_handle = {
+        let api = api.clone();
+
+        let step_handle = step_handle.clone();
+
+        let new_import = new_import.clone();
+
+        use_async(async move {
+            let result = api.preview_import(new_import).await;
+
+            match &result {
+                Ok(_) => step_handle.set(Step::Preview),
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            }
+
+            result
+        })
+    };
+
+    let import_handle = {
+        let step_handle = step_handle.clone();
+
+        use_async(async move {
+            let result = api.import(new_import).await;
+
+            match &result {
+                Ok(_) => step_handle.set(Step::Done),
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            }
+
+            result
+        })
+    };
+
+    {
+        let preview_handle = preview_handle.clone();
+
+        let step_handle = step_handle.clone();
+
+        use_effect_with_deps(
+            move |_| {
+                if *step_handle == Step::Preview {
+                    preview_handle.run();
+                }
+            },
+            (*mapping_handle).clone(),
+        );
+    }
+
+    let set_format = {
+        let format_handle = format_handle.clone();
+
+        move |e: Event| {
+            let select = e.target_dyn_into::<HtmlSelectElement>().unwrap();
+
+            if let Some(format) = ImportFormat::ALL.get(select.selected_index() as usize) {
+                format_handle.set(*format);
+            }
+        }
+    };
+
+    let set_file = {
+        let content_handle = content_handle.clone();
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+
+            let content_handle = content_handle.clone();
+
+            spawn_local(async move {
+                match JsFuture::from(file.text()).await {
+                    Ok(text) => content_handle.set(text.as_string().unwrap_or_default()),
+                    Err(_) => Store::dispatch(Action::AlertError(t("import.read_failed"))),
+                }
+            });
+        }
+    };
+
+    let set_content = {
+        let content_handle = content_handle.clone();
+
+        move |e: Event| {
+            let textarea = e.target_dyn_into::<HtmlTextAreaElement>().unwrap();
+
+            content_handle.set(textarea.value());
+        }
+    };
+
+    let set_mapping = {
+        let mapping_handle = mapping_handle.clone();
+
+        move |field: &'static str| {
+            let mapping_handle = mapping_handle.clone();
+
+            move |e: Event| {
+                let select = e.target_dyn_into::<HtmlSelectElement>().unwrap();
+
+                let mut mapping = (*mapping_handle).clone();
+
+                *mapping_field(&mut mapping, field) =
+                    Some(select.value()).filter(|value| !value.is_empty());
+
+                mapping_handle.set(mapping);
+            }
+        }
+    };
+
+    let preview = {
+        let preview_handle = preview_handle.clone();
+
+        move |_| preview_handle.run()
+    };
+
+    let back = {
+        let step_handle = step_handle.clone();
+
+        move |_| step_handle.set(Step::Source)
+    };
+
+    let run_import = {
+        let import_handle = import_handle.clone();
+
+        move |_| import_handle.run()
+    };
+
+    let step_classes = |step: Step| {
+        let active = match step {
+            Step::Source => true,
+            Step::Preview => *step_handle != Step::Source,
+            Step::Done => *step_handle == Step::Done,
+        };
+
+        classes!("step", active.then_some("step-primary"))
+    };
+
+    html!(
+        <div class="space-y-6">
+            <ul class="steps w-full">
+                <li class={ step_classes(Step::Source) }>{ t("import.step_source") }</li>
+                <li class={ step_classes(Step::Preview) }>{ t("import.step_preview") }</li>
+                <li class={ step_classes(Step::Done) }>{ t("import.step_import") }</li>
+            </ul>
+
+            if *step_handle == Step::Source {
+                <div class="space-y-4">
+                    <select class="select select-bordered w-full" onchange={ set_format }>
+                        {
+                            ImportFormat::ALL
+                                .iter()
+                                .map(|format| html!(
+                                    <option selected={ *format == *format_handle }>
+                                        { format.label() }
+                                    </option>
+                                ))
+                                .collect::<Html>()
+                        }
+                    </select>
+
+                    <input type="file" class="file-input file-input-bordered w-full" onchange={ set_file } />
+
+                    <textarea
+                        class="textarea textarea-bordered w-full h-64 font-mono"
+                        placeholder={ t("import.paste") }
+                        value={ (*content_handle).clone() }
+                        onchange={ set_content }
+                    />
+
+                    <button
+                        class={ classes!("btn", "btn-primary", "w-full", preview_handle.loading.then_some("loading")) }
+                        disabled={ content_handle.is_empty() || preview_handle.loading }
+                        onclick={ preview }
+                    >
+                        { t("import.preview") }
+                    </button>
+                </div>
+            }
+
+            if *step_handle == Step::Preview {
+                if let Some(data) = &preview_handle.data {
+                    <div class="space-y-4">
+                        if *format_handle == ImportFormat::Csv {
+                            <div class="grid grid-cols-2 md:grid-cols-3 gap-4">
+                                {
+                                    FIELDS
+                                        .iter()
+                                        .map(|(field, label)| {
+                                            let selected = mapping_value(&mapping_handle, field)
+                                                .or_else(|| data.headers.iter().find(|header| header.eq_ignore_ascii_case(field)).cloned());
+
+                                            html!(
+                                                <label class="form-control">
+                                                    <span class="label-text">{ t(label) }</span>
+                                                    <select class="select select-bordered select-sm" onchange={ set_mapping(field) }>
+                                                        <option value="" selected={ selected.is_none() }>{ "-" }</option>
+                                                        {
+                                                            data.headers
+                                                                .iter()
+                                                                .map(|header| html!(
+                                                                    <option value={ header.clone() } selected={ selected.as_ref() == Some(header) }>
+                                                                        { header.clone() }
+                                                                    </option>
+                                                                ))
+                                                                .collect::<Html>()
+                                                        }
+                                                    </select>
+                                                </label>
+                                            )
+                                        })
+                                        .collect::<Html>()
+                                }
+                            </div>
+                        }
+
+                        if !data.errors.is_empty() {
+                            <div class="alert alert-error flex-col items-start">
+                                {
+                                    data.errors
+                                        .iter()
+                                        .map(|error| html!(
+                                            <span>
+                                                {
+                                                    i18n::t_args(
+                                                        "import.line",
+                                                        &[("line", &error.line.to_string()), ("message", &error_message(error))],
+                                                    )
+                                                }
+                                            </span>
+                                        ))
+                                        .collect::<Html>()
+                                }
+                            </div>
+                        }
+
+                        if !data.new_projects.is_empty() {
+                            <div class="alert alert-info">
+                                { i18n::t_args("import.new_projects", &[("projects", &data.new_projects.join(", "))]) }
+                            </div>
+                        }
+
+                        <div class="overflow-x-auto">
+                            <table class="table table-compact w-full">
+                                <thead>
+                                    <tr>
+                                        <th>{ t("todos.name") }</th>
+                                        <th>{ t("import.project") }</th>
+                                        <th>{ t("import.completed") }</th>
+                                        <th>{ t("import.due_at") }</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {
+                                        data.todos
+                                            .iter()
+                                            .map(|todo| html!(
+                                                <tr>
+                                                    <td>
+                                                        if todo.parent.is_some() {
+                                                            { "↳ " }
+                                                        }
+                                                        if let Some(priority) = todo.priority {
+                                                            <span class="badge badge-ghost mr-2">{ priority }</span>
+                                                        }
+                                                        { todo.name.clone() }
+                                                    </td>
+                                                    <td>{ todo.project.clone().unwrap_or_default() }</td>
+                                                    <td>{ t(if todo.is_completed { "import.yes" } else { "import.no" }) }</td>
+                                                    <td>
+                                                        {
+                                                            todo.due_at
+                                                                .map(|due_at| due_at.humanize())
+                                                                .unwrap_or_default()
+                                                        }
+                                                    </td>
+                                                </tr>
+                                            ))
+                                            .collect::<Html>()
+                                    }
+                                </tbody>
+                            </table>
+                        </div>
+
+                        <div class="flex gap-4">
+                            <button class="btn flex-1" onclick={ back }>{ t("import.back") }</button>
+                            <button
+                                class={ classes!("btn", "btn-primary", "flex-1", import_handle.loading.then_some("loading")) }
+                                disabled={ !data.errors.is_empty() || data.todos.is_empty() || import_handle.loading }
+                                onclick={ run_import }
+                            >
+                                { i18n::t_args("import.import", &[("count", &data.todos.len().to_string())]) }
+                            </button>
+                        </div>
+                    </div>
+                }
+            }
+
+            if *step_handle == Step::Done {
+                if let Some(data) = &import_handle.data {
+                    <div class="space-y-4 text-center">
+                        <p>
+                            { i18n::t_args("import.imported", &[("count", &data.todos.len().to_string())]) }
+                            if !data.projects.is_empty() {
+                                { i18n::t_args("import.imported_projects", &[("count", &data.projects.len().to_string())]) }
+                            }
+                        </p>
+                        <Link<PrivateRoute> to={ PrivateRoute::All } classes={ classes!("btn", "btn-primary") }>
+                            { t("import.show_todos") }
+                        </Link<PrivateRoute>>
+                    </div>
+                }
+            }
+        </div>
+    )
+}
@@ -0,0 +1,457 @@
+use serde::Deserialize;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_hooks::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    api::{
+        error::FieldError,
+        ext::{ApiErrorOptionExt, FieldErrorsMessagesExt},
+        types::{DateFormat, DefaultView, PasswordChange, Preferences, Theme, WeekStart},
+        use_api,
+    },
+    components::{spinner::SpinnerMedium, text_input::TextInput, todo_row::DateTimeHumanizeExt},
+    i18n::{self, t},
+    preferences,
+    store::{Action, Store},
+};
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct PasswordErrors {
+    #[serde(default = "Vec::new")]
+    pub password: Vec<FieldError>,
+    #[serde(default = "Vec::new")]
+    pub new_password: Vec<FieldError>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct PreferencesErrors {
+    #[serde(default = "Vec::new")]
+    pub time_zone: Vec<FieldError>,
+    #[serde(default = "Vec::new")]
+    pub page_size: Vec<FieldError>,
+    #[serde(default = "Vec::new")]
+    pub locale: Vec<FieldError>,
+}
+
+#[function_component(ChangePassword)]
+fn change_password() -> Html {
+    let api = use_api();
+
+    let password_handle = use_state(String::new);
+
+    let new_password_handle = use_state(String::new);
+
+    let change_handle = {
+        let password_handle = password_handle.clone();
+
+        let new_password_handle = new_password_handle.clone();
+
+        use_async(async move {
+            let payload = PasswordChange {
+                password: (*password_handle).clone(),
+                new_password: (*new_password_handle).clone(),
+            };
+
+            let result = api.change_password(payload).await;
+
+            match &result {
+                Ok(_) => {
+                    password_handle.set(String::new());
+
+                    new_password_handle.set(String::new());
+
+                    Store::dispatch(Action::AlertSuccess(t("settings.password_changed")));
+                }
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            };
+
+            result
+        })
+    };
+
+    let errors = change_handle.error.json::<PasswordErrors>();
+
+    let set_password = {
+        let password_handle = password_handle.clone();
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            password_handle.set(input.value());
+        }
+    };
+
+    let set_new_password = {
+        let new_password_handle = new_password_handle.clone();
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            new_password_handle.set(input.value());
+        }
+    };
+
+    let submit = {
+        let change_handle = change_handle.clone();
+
+        move |e: SubmitEvent| {
+            e.prevent_default();
+
+            change_handle.run();
+        }
+    };
+
+    html!(
+        <form class="card bg-base-200" onsubmit={ submit }>
+            <div class="card-body space-y-3">
+                <h2 class="card-title">{ t("settings.change_password") }</h2>
+                <div>
+                    <TextInput
+                        id="password"
+                        name="password"
+                        r#type="password"
+                        required={ true }
+                        placeholder={ t("settings.current_password") }
+                        value={ (*password_handle).clone() }
+                        errors={ errors.password.messages() }
+                        onchange={ set_password }
+                    />
+                </div>
+                <div>
+                    <TextInput
+                        id="new_password"
+                        name="new_password"
+                        r#type="password"
+                        required={ true }
+                        placeholder={ t("settings.new_password") }
+                        value={ (*new_password_handle).clone() }
+                        errors={ errors.new_password.messages() }
+                        onchange={ set_new_password }
+                    />
+                </div>
+                <div class="card-actions justify-end">
+                    if change_handle.loading {
+                        <button class="btn loading"></button>
+                    } else {
+                        <button type="submit" class="btn btn-primary">{ t("settings.change_password") }</button>
+                    }
+                </div>
+            </div>
+        </form>
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+struct PreferencesFormProps {
+    preferences: Preferences,
+}
+
+#[function_component(PreferencesForm)]
+fn preferences_form(props: &PreferencesFormProps) -> Html {
+    let api = use_api();
+
+    let form_handle = use_state(|| props.preferences.clone());
+
+    // The `Navbar` toggle changes the theme without this form.
+    {
+        let form_handle = form_handle.clone();
+
+        let theme = use_selector(|store: &Store| {
+            store
+                .preferences
+                .as_ref()
+                .map(|preferences| preferences.theme)
+        });
+
+        use_effect_update_with_deps(
+            move |theme| {
+                if let Some(theme) = **theme {
+                    form_handle.set(Preferences {
+                        theme,
+                        ..(*form_handle).clone()
+                    });
+                }
+
+                || ()
+            },
+            theme,
+        );
+    }
+
+    let save_handle = {
+        let form_handle = form_handle.clone();
+
+        use_async(async move {
+            let result = api.update_preferences((*form_handle).clone()).await;
+
+            match &result {
+                Ok(preferences) => {
+                    form_handle.set(preferences.clone());
+
+                    Store::dispatch(Action::SetPreferences(Some(preferences.clone())));
+
+                    Store::dispatch(Action::AlertSuccess(t("settings.preferences_saved")));
+                }
+                Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+            };
+
+            result
+        })
+    };
+
+    let errors = save_handle.error.json::<PreferencesErrors>();
+
+    let set_time_zone = {
+        let form_handle = form_handle.clone();
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            let time_zone = input.value().trim().to_string();
+
+            form_handle.set(Preferences {
+                time_zone: (!time_zone.is_empty()).then_some(time_zone),
+                ..(*form_handle).clone()
+            });
+        }
+    };
+
+    let set_page_size = {
+        let form_handle = form_handle.clone();
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            form_handle.set(Preferences {
+                page_size: input.value_as_number() as i32,
+                ..(*form_handle).clone()
+            });
+        }
+    };
+
+    let set_locale = {
+        let form_handle = form_handle.clone();
+
+        move |e: Event| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            form_handle.set(Preferences {
+                locale: input.value().trim().to_string(),
+                ..(*form_handle).clone()
+            });
+        }
+    };
+
+    let set_option = |update: fn(&mut Preferences, usize)| {
+        let form_handle = form_handle.clone();
+
+        move |e: Event| {
+            let select = e.target_dyn_into::<HtmlSelectElement>().unwrap();
+
+            let mut preferences = (*form_handle).clone();
+
+            update(&mut preferences, select.selected_index() as usize);
+
+            form_handle.set(preferences);
+        }
+    };
+
+    let set_theme = set_option(|preferences, index| {
+        if let Some(theme) = Theme::ALL.get(index) {
+            preferences.theme = *theme;
+        }
+    });
+
+    let set_date_format = set_option(|preferences, index| {
+        if let Some(date_format) = DateFormat::ALL.get(index) {
+            preferences.date_format = *date_format;
+        }
+    });
+
+    let set_default_view = set_option(|preferences, index| {
+        if let Some(default_view) = DefaultView::ALL.get(index) {
+            preferences.default_view = *default_view;
+        }
+    });
+
+    let set_week_start = set_option(|preferences, index| {
+        if let Some(week_start) = WeekStart::ALL.get(index) {
+            preferences.week_start = *week_start;
+        }
+    });
+
+    let submit = {
+        let save_handle = save_handle.clone();
+
+        move |e: SubmitEvent| {
+            e.prevent_default();
+
+            save_handle.run();
+        }
+    };
+
+    html!(
+        <form class="card bg-base-200" onsubmit={ submit }>
+            <div class="card-body space-y-3">
+                <h2 class="card-title">{ t("settings.preferences") }</h2>
+                <div>
+                    <label class="label" for="time_zone">
+                        <span class="label-text">{ t("settings.time_zone") }</span>
+                    </label>
+                    <TextInput
+                        id="time_zone"
+                        name="time_zone"
+                        r#type="text"
+                        required={ false }
+                        placeholder={ i18n::t_args("settings.browser_time_zone", &[("zone", &preferences::browser_time_zone())]) }
+                        value={ form_handle.time_zone.clone().unwrap_or_default() }
+                        errors={ errors.time_zone.messages() }
+                        onchange={ set_time_zone }
+                    />
+                </div>
+                <div>
+                    <label class="label" for="page_size">
+                        <span class="label-text">{ t("settings.page_size") }</span>
+                    </label>
+                    <TextInput
+                        id="page_size"
+                        name="page_size"
+                        r#type="number"
+                        required={ true }
+                        placeholder={ t("settings.page_size") }
+                        value={ form_handle.page_size.to_string() }
+                        errors={ errors.page_size.messages() }
+                        onchange={ set_page_size }
+                    />
+                </div>
+                <div>
+                    <label class="label" for="theme">
+                        <span class="label-text">{ t("settings.theme") }</span>
+                    </label>
+                    <select id="theme" class="select select-bordered w-full" onchange={ set_theme }>
+                        {
+                            Theme::ALL
+                                .iter()
+                                .map(|theme| html!(
+                                    <option selected={ *theme == form_handle.theme }>{ theme.label() }</option>
+                                ))
+                                .collect::<Html>()
+                        }
+                    </select>
+                </div>
+                <div>
+                    <label class="label" for="date_format">
+                        <span class="label-text">{ t("settings.date_format") }</span>
+                    </label>
+                    <select id="date_format" class="select select-bordered w-full" onchange={ set_date_format }>
+                        {
+                            DateFormat::ALL
+                                .iter()
+                                .map(|date_format| html!(
+                                    <option selected={ *date_format == form_handle.date_format }>{ date_format.label() }</option>
+                                ))
+                                .collect::<Html>()
+                        }
+                    </select>
+                </div>
+                <div>
+                    <label class="label" for="default_view">
+                        <span class="label-text">{ t("settings.default_view") }</span>
+                    </label>
+                    <select id="default_view" class="select select-bordered w-full" onchange={ set_default_view }>
+                        {
+                            DefaultView::ALL
+                                .iter()
+                                .map(|default_view| html!(
+                                    <option selected={ *default_view == form_handle.default_view }>{ default_view.label() }</option>
+                                ))
+                                .collect::<Html>()
+                        }
+                    </select>
+                </div>
+                <div>
+                    <label class="label" for="week_start">
+                        <span class="label-text">{ t("settings.week_start") }</span>
+                    </label>
+                    <select id="week_start" class="select select-bordered w-full" onchange={ set_week_start }>
+                        {
+                            WeekStart::ALL
+                                .iter()
+                                .map(|week_start| html!(
+                                    <option selected={ *week_start == form_handle.week_start }>{ week_start.label() }</option>
+                                ))
+                                .collect::<Html>()
+                        }
+                    </select>
+                </div>
+                <div>
+                    <label class="label" for="locale">
+                        <span class="label-text">{ t("settings.locale") }</span>
+                    </label>
+                    <TextInput
+                        id="locale"
+                        name="locale"
+                        r#type="text"
+                        required={ true }
+                        placeholder={ t("settings.locale_placeholder") }
+                        value={ form_handle.locale.clone() }
+                        errors={ errors.locale.messages() }
+                        onchange={ set_locale }
+                    />
+                </div>
+                <div class="card-actions justify-end">
+                    if save_handle.loading {
+                        <button class="btn loading"></button>
+                    } else {
+                        <button type="submit" class="btn btn-primary">{ t("settings.save") }</button>
+                    }
+                </div>
+            </div>
+        </form>
+    )
+}
+
+#[function_component(Settings)]
+pub fn settings() -> Html {
+    let api = use_api();
+
+    use_title(i18n::t_args("title", &[("page", &t("nav.settings"))]));
+
+    let user = use_selector(|store: &Store| store.user.clone());
+
+    let preferences_handle = use_async_with_options(
+        async move {
+            let result = api.preferences().await;
+
+            if let Err(err) = &result {
+                Store::dispatch(Action::AlertError(err.to_string()));
+            }
+
+            result
+        },
+        UseAsyncOptions::enable_auto(),
+    );
+
+    html!(
+        <main class="relative max-w-screen-sm mx-auto space-y-6 py-2">
+            if let Some(user) = user.as_ref() {
+                <div class="card bg-base-200">
+                    <div class="card-body">
+                        <h2 class="card-title">{ user.username.clone() }</h2>
+                        <p class="opacity-60">{ i18n::t_args("settings.joined", &[("date", &user.joined_at.humanize())]) }</p>
+                    </div>
+                </div>
+            }
+
+            <ChangePassword />
+
+            if let Some(preferences) = &preferences_handle.data {
+                <PreferencesForm preferences={ preferences.clone() } />
+            } else if preferences_handle.loading {
+                <SpinnerMedium />
+            }
+        </main>
+    )
+}
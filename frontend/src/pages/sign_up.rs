@@ -12,6 +12,7 @@ use crate::{
         use_api,
     },
     components::text_input::TextInput,
+    i18n::{self, t},
     router::Route,
     store::{Action, Store},
 };
@@ -34,7 +35,7 @@ pub struct Errors {
 pub fn sign_up() -> Html {
     let api = use_api();
 
-    use_title("Sign in | ToDos".to_string());
+    use_title(i18n::t_args("title", &[("page", &t("auth.sign_up"))]));
 
     let form_handle = use_state(Form::default);
 
@@ -104,7 +105,7 @@ pub fn sign_up() -> Html {
         <div class="flex h-screen items-center justify-center py-12 px-4 sm:px-6 lg:px-8">
             <div class="relative w-full max-w-md space-y-8">
                 <div>
-                    <h2 class="text-center text-3xl font-bold tracking-tight">{ "Sign up" }</h2>
+                    <h2 class="text-center text-3xl font-bold tracking-tight">{ t("auth.sign_up") }</h2>
                 </div>
                 <form class="mt-8 space-y-6 p-3" action="#" method="POST" onsubmit={ submit }>
                     <div class="-space-y-px rounded-md shadow-sm">
@@ -114,7 +115,7 @@ pub fn sign_up() -> Html {
                                 name="username"
                                 r#type="text"
                                 required={ true }
-                                placeholder="Username"
+                                placeholder={ t("auth.username") }
                                 value={ form_handle.username.clone() }
                                 errors={ errors.username.messages() }
                                 onchange={ set_username.clone() }
@@ -127,7 +128,7 @@ pub fn sign_up() -> Html {
                                 name="password"
                                 r#type="password"
                                 required={ true }
-                                placeholder="Password"
+                                placeholder={ t("account.password") }
                                 value={ form_handle.password.clone() }
                                 errors={ errors.password.messages() }
                                 onchange={ set_password.clone() }
@@ -140,12 +141,12 @@ pub fn sign_up() -> Html {
                             <button class="btn w-full loading"></button>
                         } else {
                             <button type="submit" class="btn w-full btn-primary">
-                                { "Sign up" }
+                                { t("auth.sign_up") }
                             </button>
                         }
                         <p class="mt-2 text-center">
                             <Link<Route> to={ Route::SignIn } classes="link hover:text-primary">
-                                { "Sign in" }
+                                { t("auth.sign_in") }
                             </Link<Route>>
                         </p>
                     </div>
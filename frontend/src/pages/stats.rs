@@ -0,0 +1,269 @@
+use yew::prelude::*;
+use yew_hooks::prelude::*;
+
+use crate::{
+    api::{
+        types::{Period, Stats as StatsData, StatsBucket, StatsQuery},
+        use_api,
+    },
+    components::spinner::SpinnerMedium,
+    i18n::{self, t},
+    preferences,
+    store::{Action, Store},
+};
+
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 200.0;
+const LABEL_HEIGHT: f64 = 20.0;
+
+fn format_duration(seconds: i64) -> String {
+    let minutes = seconds / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+
+    match (days, hours, minutes) {
+        (0, 0, minutes) => i18n::t_args("duration.minutes", &[("minutes", &minutes.to_string())]),
+        (0, hours, minutes) => i18n::t_args(
+            "duration.hours",
+            &[
+                ("hours", &hours.to_string()),
+                ("minutes", &(minutes % 60).to_string()),
+            ],
+        ),
+        (days, hours, _) => i18n::t_args(
+            "duration.days",
+            &[
+                ("days", &days.to_string()),
+                ("hours", &(hours % 24).to_string()),
+            ],
+        ),
+    }
+}
+
+fn bucket_label(bucket: &StatsBucket, period: Period) -> String {
+    match period {
+        Period::Day | Period::Week => i18n::format_date_with(
+            &bucket.start,
+            &[("day", "numeric"), ("month", "short")],
+            "%m-%d",
+        ),
+        Period::Month => i18n::format_date_with(
+            &bucket.start,
+            &[("month", "short"), ("year", "numeric")],
+            "%Y-%m",
+        ),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+struct ThroughputChartProps {
+    stats: StatsData,
+}
+
+/// Created and completed todos per bucket as grouped bars.
+#[function_component(ThroughputChart)]
+fn throughput_chart(props: &ThroughputChartProps) -> Html {
+    let buckets = &props.stats.buckets;
+
+    let max = buckets
+        .iter()
+        .map(|bucket| bucket.created.max(bucket.completed))
+        .max()
+        .unwrap_or_default()
+        .max(1) as f64;
+
+    let slot = CHART_WIDTH / buckets.len().max(1) as f64;
+
+    let bar = slot * 0.4;
+
+    // Keeps about ten labels on the axis whatever the number of buckets.
+    let label_every = (buckets.len() / 10).max(1);
+
+    let height = |value: i64| value as f64 / max * (CHART_HEIGHT - LABEL_HEIGHT);
+
+    html!(
+        <svg viewBox={ format!("0 0 {CHART_WIDTH} {CHART_HEIGHT}") } class="w-full">
+            {
+                buckets
+                    .iter()
+                    .enumerate()
+                    .map(|(index, bucket)| {
+                        let x = index as f64 * slot + slot * 0.1;
+
+                        let label = bucket_label(bucket, props.stats.period);
+
+                        html!(
+                            <g>
+                                <title>
+                                    {
+                                        i18n::t_args(
+                                            "stats.bucket",
+                                            &[
+                                                ("label", &label),
+                                                ("created", &bucket.created.to_string()),
+                                                ("completed", &bucket.completed.to_string()),
+                                            ],
+                                        )
+                                    }
+                                </title>
+                                <rect
+                                    class="fill-primary"
+                                    x={ x.to_string() }
+                                    y={ (CHART_HEIGHT - LABEL_HEIGHT - height(bucket.created)).to_string() }
+                                    width={ bar.to_string() }
+                                    height={ height(bucket.created).to_string() }
+                                />
+                                <rect
+                                    class="fill-secondary"
+                                    x={ (x + bar).to_string() }
+                                    y={ (CHART_HEIGHT - LABEL_HEIGHT - height(bucket.completed)).to_string() }
+                                    width={ bar.to_string() }
+                                    height={ height(bucket.completed).to_string() }
+                                />
+                                if index % label_every == 0 {
+                                    <text
+                                        class="fill-current text-[10px]"
+                                        x={ (x + bar).to_string() }
+                                        y={ (CHART_HEIGHT - 4.0).to_string() }
+                                        text-anchor="middle"
+                                    >
+                                        { label }
+                                    </text>
+                                }
+                            </g>
+                        )
+                    })
+                    .collect::<Html>()
+            }
+        </svg>
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+struct StatusChartProps {
+    open: i64,
+    done: i64,
+}
+
+/// Share of open and done todos as a single stacked bar.
+#[function_component(StatusChart)]
+fn status_chart(props: &StatusChartProps) -> Html {
+    let total = (props.open + props.done).max(1) as f64;
+
+    let done = props.done as f64 / total * CHART_WIDTH;
+
+    html!(
+        <svg viewBox={ format!("0 0 {CHART_WIDTH} 20") } class="w-full rounded-lg">
+            <rect class="fill-base-300" x="0" y="0" width={ CHART_WIDTH.to_string() } height="20">
+                <title>{ i18n::t_args("stats.open_count", &[("count", &props.open.to_string())]) }</title>
+            </rect>
+            <rect class="fill-secondary" x="0" y="0" width={ done.to_string() } height="20">
+                <title>{ i18n::t_args("stats.done_count", &[("count", &props.done.to_string())]) }</title>
+            </rect>
+        </svg>
+    )
+}
+
+#[function_component(Stats)]
+pub fn stats() -> Html {
+    let api = use_api();
+
+    let period_handle = use_state(|| Period::Day);
+
+    let stats_handle = {
+        let query = StatsQuery {
+            period: *period_handle,
+            tz: preferences::time_zone(),
+        };
+
+        use_async(async move {
+            let result = api.stats(query).await;
+
+            if let Err(err) = &result {
+                Store::dispatch(Action::AlertError(err.to_string()));
+            }
+
+            result
+        })
+    };
+
+    {
+        let stats_handle = stats_handle.clone();
+
+        use_effect_with_deps(move |_| stats_handle.run(), *period_handle);
+    }
+
+    let set_period = {
+        let period_handle = period_handle.clone();
+
+        move |period: Period| {
+            let period_handle = period_handle.clone();
+
+            move |_| period_handle.set(period)
+        }
+    };
+
+    html!(
+        <main class="relative max-w-screen-md mx-auto space-y-6 py-2">
+            <div class="tabs tabs-boxed">
+                {
+                    Period::ALL
+                        .iter()
+                        .map(|period| html!(
+                            <a
+                                class={ classes!("tab", (*period == *period_handle).then_some("tab-active")) }
+                                onclick={ set_period(*period) }
+                            >
+                                { period.label() }
+                            </a>
+                        ))
+                        .collect::<Html>()
+                }
+            </div>
+
+            if let Some(stats) = &stats_handle.data {
+                <div class="stats stats-vertical md:stats-horizontal shadow w-full">
+                    <div class="stat">
+                        <div class="stat-title">{ t("stats.open") }</div>
+                        <div class="stat-value">{ stats.open }</div>
+                    </div>
+                    <div class="stat">
+                        <div class="stat-title">{ t("stats.done") }</div>
+                        <div class="stat-value">{ stats.done }</div>
+                    </div>
+                    <div class="stat">
+                        <div class="stat-title">{ t("stats.time_to_complete") }</div>
+                        <div class="stat-value">
+                            { stats.average_completion_seconds.map(format_duration).unwrap_or_else(|| "-".to_string()) }
+                        </div>
+                        <div class="stat-desc">{ t("stats.on_average") }</div>
+                    </div>
+                    <div class="stat">
+                        <div class="stat-title">{ t("stats.streak") }</div>
+                        <div class="stat-value">{ stats.current_streak }</div>
+                        <div class="stat-desc">{ i18n::t_args("stats.longest", &[("days", &stats.longest_streak.to_string())]) }</div>
+                    </div>
+                </div>
+
+                <StatusChart open={ stats.open } done={ stats.done } />
+
+                <div class="space-y-2">
+                    <div class="flex gap-4 text-sm">
+                        <span class="flex items-center gap-1">
+                            <span class="w-3 h-3 rounded-sm bg-primary" />{ t("stats.created") }
+                        </span>
+                        <span class="flex items-center gap-1">
+                            <span class="w-3 h-3 rounded-sm bg-secondary" />{ t("stats.completed") }
+                        </span>
+                        <span class="ml-auto opacity-60">
+                            { format!("{} – {}", i18n::format_date(&stats.from), i18n::format_date(&stats.to)) }
+                        </span>
+                    </div>
+                    <ThroughputChart stats={ stats.clone() } />
+                </div>
+            } else if stats_handle.loading {
+                <SpinnerMedium />
+            }
+        </main>
+    )
+}
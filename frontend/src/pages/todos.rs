@@ -1,18 +1,40 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 use yew_hooks::prelude::*;
+use yew_router::{hooks::use_location, prelude::*};
+use yewdux::prelude::*;
 
 use crate::{
     api::{
-        types::{TodosDeleteQuery, TodosQuery},
+        error::ApiError,
+        events::use_events,
+        types::{SortOrder, TodosDeleteQuery, TodosQuery},
         use_api,
     },
     components::{
-        modal::Modal, new_todo::NewTodo, pagination::Pagination, spinner::SpinnerMedium,
-        todos_table::TodosTable,
+        export_menu::ExportMenu, modal::Modal, new_from_template::NewFromTemplate,
+        new_todo::NewTodo, pagination::Pagination, spinner::SpinnerMedium, todos_table::TodosTable,
     },
+    i18n::{self, t},
+    offline, preferences,
+    router::PrivateRoute,
+    shortcuts,
     store::{Action, Store},
 };
 
+/// Query of the todos pages, lets the command palette link to a search or
+/// to a new todo.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TodosLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub new: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct TodosProps {
     pub is_completed: Option<bool>,
@@ -22,18 +44,39 @@ pub struct TodosProps {
 pub fn todos(props: &TodosProps) -> Html {
     let api = use_api();
 
-    use_title(
-        match &props.is_completed {
-            Some(true) => "Completed | ToDos",
-            Some(false) => "Active | ToDos",
-            None => "All | ToDos",
-        }
-        .to_string(),
-    );
+    use_title(i18n::t_args(
+        "title",
+        &[(
+            "page",
+            &t(match &props.is_completed {
+                Some(true) => "nav.completed",
+                Some(false) => "nav.active",
+                None => "nav.all",
+            }),
+        )],
+    ));
 
     let is_completed_handle = use_state(|| props.is_completed);
 
-    let pagination_handle = use_state(|| (10, 0));
+    let pagination_handle = use_state(|| (preferences::current().page_size as usize, 0));
+
+    let project_handle = use_state(|| None::<Uuid>);
+
+    let sort_handle = use_state(|| None::<(Uuid, SortOrder)>);
+
+    let location = use_location()
+        .and_then(|location| location.query::<TodosLocation>().ok())
+        .unwrap_or_default();
+
+    // What's typed into the search field, and the search applied to the list
+    // once typing paused.
+    let search_input_handle = use_state(|| location.search.clone().unwrap_or_default());
+
+    let search_handle = use_state(|| location.search.clone());
+
+    let selected_handle = use_state(|| None::<usize>);
+
+    let search_ref = use_node_ref();
 
     let todos_handle = {
         let api = api.clone();
@@ -42,29 +85,160 @@ pub fn todos(props: &TodosProps) -> Html {
 
         let pagination_handle = pagination_handle.clone();
 
+        let project_handle = project_handle.clone();
+
+        let sort_handle = sort_handle.clone();
+
+        let search_handle = search_handle.clone();
+
         use_async_with_options(
             async move {
                 let query = TodosQuery {
                     is_completed: *is_completed_handle,
+                    project_id: *project_handle,
+                    sort_field_id: sort_handle.map(|(field_id, _)| field_id),
+                    sort_order: sort_handle.map(|(_, order)| order),
+                    search: (*search_handle).clone(),
                     limit: pagination_handle.0,
                     offset: pagination_handle.1,
                 };
 
-                api.todos(query).await
+                let result = match offline::is_online() {
+                    true => match api.todos(query.clone()).await {
+                        Ok(todos) => {
+                            offline::cache_todos(&todos.data).await;
+
+                            Ok(todos)
+                        }
+                        Err(ApiError::Network(_)) => Ok(offline::cached_todos(&query).await),
+                        Err(err) => Err(err),
+                    },
+                    false => Ok(offline::cached_todos(&query).await),
+                };
+
+                if let Ok(todos) = &result {
+                    Store::dispatch(Action::SetTodos(todos.data.clone()));
+                }
+
+                result
             },
             UseAsyncOptions::enable_auto(),
         )
     };
 
+    let sync_status = use_selector(|store: &Store| store.sync_status);
+
+    // Rows show the store versions, changed before the server confirms them.
+    let cached = use_selector(|store: &Store| (store.todos.clone(), store.deleting.clone()));
+
+    {
+        let todos_handle = todos_handle.clone();
+
+        // Reloads once a sync finished or the connection dropped.
+        use_effect_update_with_deps(
+            move |_| {
+                todos_handle.run();
+
+                || ()
+            },
+            *sync_status,
+        );
+    }
+
+    {
+        let todos_handle = todos_handle.clone();
+
+        let selected_handle = selected_handle.clone();
+
+        use_effect_update_with_deps(
+            move |_| {
+                selected_handle.set(None);
+
+                todos_handle.run();
+
+                || ()
+            },
+            (*search_handle).clone(),
+        );
+    }
+
+    let search_debounce = {
+        let search_input_handle = search_input_handle.clone();
+
+        let search_handle = search_handle.clone();
+
+        let pagination_handle = pagination_handle.clone();
+
+        use_debounce(
+            move || {
+                let search = search_input_handle.trim().to_string();
+
+                pagination_handle.set((pagination_handle.0, 0));
+
+                search_handle.set((!search.is_empty()).then_some(search));
+            },
+            300,
+        )
+    };
+
+    let projects_handle = {
+        let api = api.clone();
+
+        use_async_with_options(
+            async move { api.projects().await },
+            UseAsyncOptions::enable_auto(),
+        )
+    };
+
+    let fields_handle = {
+        let api = api.clone();
+
+        let project_handle = project_handle.clone();
+
+        use_async(async move {
+            match *project_handle {
+                Some(project_id) => api.project_fields(project_id).await,
+                None => Ok(Vec::new()),
+            }
+        })
+    };
+
+    {
+        let api = api.clone();
+
+        use_async_with_options(
+            async move {
+                let result = api.timer().await;
+
+                match &result {
+                    Ok(timer) => Store::dispatch(Action::SetTimer(timer.clone())),
+                    Err(err) => Store::dispatch(Action::AlertError(err.to_string())),
+                };
+
+                result
+            },
+            UseAsyncOptions::enable_auto(),
+        );
+    }
+
+    {
+        let todos_handle = todos_handle.clone();
+
+        use_events(Callback::from(move |_| todos_handle.run()));
+    }
+
     let delete_completed_handle = {
         let api = api;
 
         let todos_handle = todos_handle.clone();
 
+        let project_handle = project_handle.clone();
+
         use_async(async move {
             let result = api
                 .delete_todos(TodosDeleteQuery {
                     is_completed: Some(true),
+                    project_id: *project_handle,
                 })
                 .await;
 
@@ -110,6 +284,48 @@ pub fn todos(props: &TodosProps) -> Html {
         }
     };
 
+    let set_project = {
+        let project_handle = project_handle.clone();
+
+        let pagination_handle = pagination_handle.clone();
+
+        let sort_handle = sort_handle.clone();
+
+        let todos_handle = todos_handle.clone();
+
+        let fields_handle = fields_handle.clone();
+
+        move |e: Event| {
+            let select = e.target_dyn_into::<HtmlSelectElement>().unwrap();
+
+            project_handle.set(select.value().parse::<Uuid>().ok());
+
+            pagination_handle.set((pagination_handle.0, 0));
+
+            sort_handle.set(None);
+
+            todos_handle.run();
+
+            fields_handle.run();
+        }
+    };
+
+    let set_sort = {
+        let sort_handle = sort_handle.clone();
+
+        let todos_handle = todos_handle.clone();
+
+        move |field_id: Uuid| {
+            sort_handle.set(match *sort_handle {
+                Some((id, SortOrder::Asc)) if id == field_id => Some((field_id, SortOrder::Desc)),
+                Some((id, SortOrder::Desc)) if id == field_id => None,
+                _ => Some((field_id, SortOrder::Asc)),
+            });
+
+            todos_handle.run();
+        }
+    };
+
     let toggle = use_toggle(false, true);
 
     let open = {
@@ -132,6 +348,67 @@ pub fn todos(props: &TodosProps) -> Html {
         }
     };
 
+    {
+        let navigator = use_navigator().unwrap();
+
+        let route = use_route::<PrivateRoute>().unwrap_or(PrivateRoute::Active);
+
+        let toggle = toggle.clone();
+
+        let search_input_handle = search_input_handle.clone();
+
+        let search_handle = search_handle.clone();
+
+        let pagination_handle = pagination_handle.clone();
+
+        // Follows links from the command palette while already on the page.
+        use_effect_with_deps(
+            move |location: &TodosLocation| {
+                if location.new {
+                    toggle.set(true);
+
+                    let query = TodosLocation {
+                        new: false,
+                        ..location.clone()
+                    };
+
+                    let _ = navigator.replace_with_query(&route, &query);
+                }
+
+                if location.search != *search_handle {
+                    search_input_handle.set(location.search.clone().unwrap_or_default());
+
+                    pagination_handle.set((pagination_handle.0, 0));
+
+                    search_handle.set(location.search.clone());
+                }
+            },
+            location,
+        );
+    }
+
+    let template_toggle = use_toggle(false, true);
+
+    let open_template = {
+        let template_toggle = template_toggle.clone();
+
+        move |_| {
+            template_toggle.toggle();
+        }
+    };
+
+    let on_template_add = {
+        let todos_handle = todos_handle.clone();
+
+        let template_toggle = template_toggle.clone();
+
+        move |()| {
+            todos_handle.run();
+
+            template_toggle.toggle();
+        }
+    };
+
     let on_todo_action = {
         let todos_handle = todos_handle.clone();
 
@@ -148,24 +425,139 @@ pub fn todos(props: &TodosProps) -> Html {
         }
     };
 
+    let set_search = {
+        let search_input_handle = search_input_handle.clone();
+
+        move |e: InputEvent| {
+            let input = e.target_dyn_into::<HtmlInputElement>().unwrap();
+
+            search_input_handle.set(input.value());
+
+            search_debounce.run();
+        }
+    };
+
+    let on_search_keydown = {
+        let search_ref = search_ref.clone();
+
+        move |e: KeyboardEvent| {
+            if e.key() == "Escape" {
+                if let Some(input) = search_ref.cast::<HtmlInputElement>() {
+                    let _ = input.blur();
+                }
+            }
+        }
+    };
+
+    let fields = fields_handle.data.clone().unwrap_or_default();
+
+    let todos = {
+        let (todos, deleting) = &*cached;
+
+        todos_handle
+            .data
+            .iter()
+            .flat_map(|data| &data.data)
+            .filter(|todo| !deleting.contains(&todo.id))
+            .map(|todo| todos.get(&todo.id).unwrap_or(todo).clone())
+            .collect::<Vec<_>>()
+    };
+
+    {
+        let toggle = toggle.clone();
+
+        let search_ref = search_ref.clone();
+
+        let selected_handle = selected_handle.clone();
+
+        let count = todos.len();
+
+        use_event_with_window("keydown", move |e: KeyboardEvent| {
+            let selected = selected_handle.filter(|index| *index < count);
+
+            match shortcuts::key(&e).as_deref() {
+                Some("n") => toggle.set(true),
+                Some("/") => {
+                    if let Some(input) = search_ref.cast::<HtmlInputElement>() {
+                        let _ = input.focus();
+                    }
+                }
+                Some("j") if count > 0 => selected_handle
+                    .set(Some(selected.map_or(0, |index| (index + 1).min(count - 1)))),
+                Some("k") if count > 0 => {
+                    selected_handle.set(Some(selected.map_or(0, |index| index.saturating_sub(1))))
+                }
+                _ => return,
+            }
+
+            e.prevent_default();
+        });
+    }
+
+    let selected = selected_handle
+        .and_then(|index| todos.get(index))
+        .map(|todo| todo.id);
+
     html!(
         <main class="relative max-w-screen-md mx-auto">
             <div class="flex justify-end w-full py-2">
-                <button onclick={ delete_completed } class="btn btn-ghost mr-2">{ "Delete Completed" }</button>
-                <button onclick={ open } class="btn btn-primary">{ "Add" }</button>
+                <select class="select select-bordered mr-2" onchange={ set_project }>
+                    <option value="" selected={ project_handle.is_none() }>{ t("todos.all_projects") }</option>
+                    {
+                        projects_handle
+                            .data
+                            .iter()
+                            .flatten()
+                            .map(|project| html!(
+                                <option
+                                    value={ project.id.to_string() }
+                                    selected={ *project_handle == Some(project.id) }
+                                >
+                                    { project.name.clone() }
+                                </option>
+                            ))
+                            .collect::<Html>()
+                    }
+                </select>
+                <input
+                    ref={ search_ref }
+                    type="search"
+                    class="input input-bordered mr-auto"
+                    placeholder={ t("todos.search") }
+                    value={ (*search_input_handle).clone() }
+                    oninput={ set_search }
+                    onkeydown={ on_search_keydown }
+                />
+                <ExportMenu
+                    is_completed={ props.is_completed }
+                    project_id={ *project_handle }
+                    search={ (*search_handle).clone() }
+                />
+                <button onclick={ delete_completed } class="btn btn-ghost mr-2">{ t("todos.delete_completed") }</button>
+                <button onclick={ open_template } class="btn btn-ghost mr-2">{ t("templates.new_from") }</button>
+                <button onclick={ open } class="btn btn-primary">{ t("todos.add") }</button>
             </div>
+            if *template_toggle {
+                <Modal toggle={ template_toggle.clone() }>
+                    <NewFromTemplate on_add={ on_template_add } project_id={ *project_handle } />
+                </Modal>
+            }
             if *toggle {
                 <Modal toggle={ toggle.clone() }>
-                    <NewTodo { on_add } />
+                    <NewTodo { on_add } project_id={ *project_handle } fields={ fields.clone() } />
                 </Modal>
             }
             if let Some(data) = &todos_handle.data {
-                if data.data.is_empty() {
-                    <h2 class="w-full p-10 text-center">{ "Empty" }</h2>
+                if todos.is_empty() {
+                    <h2 class="w-full p-10 text-center">{ t("todos.empty") }</h2>
                 } else {
                     <div class="w-full grow">
                         <TodosTable
-                            todos={ data.data.clone() }
+                            todos={ todos }
+                            fields={ fields }
+                            sort={ *sort_handle }
+                            on_sort={ set_sort }
+                            selected={ selected }
                             on_todo_action={ on_todo_action }
                         />
                     </div>
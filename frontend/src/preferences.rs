@@ -0,0 +1,190 @@
+use std::cell::RefCell;
+
+use chrono::{
+    DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc,
+};
+use gloo_storage::{LocalStorage, Storage};
+use js_sys::{Array, Date, Function, Intl, Object, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use yewdux::prelude::*;
+
+use crate::{
+    api::types::{Preferences, Theme},
+    store::Store,
+};
+
+/// Preferences of the signed in user, `None` until they're loaded.
+pub fn loaded() -> Option<Preferences> {
+    Dispatch::<Store>::new().get().preferences.clone()
+}
+
+/// Preferences of the signed in user, the defaults until they're loaded.
+pub fn current() -> Preferences {
+    loaded().unwrap_or_default()
+}
+
+/// IANA name of the browser time zone.
+pub fn browser_time_zone() -> String {
+    let options = Intl::DateTimeFormat::new(&Array::new(), &Object::new()).resolved_options();
+
+    Reflect::get(&options, &"timeZone".into())
+        .ok()
+        .and_then(|value| value.as_string())
+        .unwrap_or_else(|| "UTC".to_string())
+}
+
+/// Preferred time zone, the browser one when unset.
+pub fn time_zone() -> String {
+    current().time_zone.unwrap_or_else(browser_time_zone)
+}
+
+/// Formatter writing the wall clock time of `time_zone` in numeric parts,
+/// `None` when the browser doesn't know the zone.
+fn wall_clock_format(time_zone: &str) -> Option<Intl::DateTimeFormat> {
+    let options = Object::new();
+
+    for (key, value) in [
+        ("timeZone", time_zone),
+        ("hourCycle", "h23"),
+        ("year", "numeric"),
+        ("month", "numeric"),
+        ("day", "numeric"),
+        ("hour", "numeric"),
+        ("minute", "numeric"),
+        ("second", "numeric"),
+    ] {
+        Reflect::set(&options, &key.into(), &value.into()).ok()?;
+    }
+
+    let constructor = Reflect::get(&js_sys::global(), &"Intl".into())
+        .and_then(|intl| Reflect::get(&intl, &"DateTimeFormat".into()))
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()?;
+
+    // Unknown zones throw a `RangeError`, construct catches it.
+    Reflect::construct(&constructor, &Array::of2(&"en-US".into(), &options))
+        .ok()
+        .map(JsCast::unchecked_into)
+}
+
+/// Offset of `time_zone` at `instant`, the browser offset when unset or
+/// unknown.
+fn offset(time_zone: Option<&str>, instant: &DateTime<Utc>) -> FixedOffset {
+    thread_local! {
+        static FORMAT: RefCell<Option<(String, Option<Intl::DateTimeFormat>)>> = const { RefCell::new(None) };
+    };
+
+    let browser = || Local.offset_from_utc_datetime(&instant.naive_utc()).fix();
+
+    let Some(time_zone) = time_zone else {
+        return browser();
+    };
+
+    let parts = FORMAT.with(|format| {
+        let mut format = format.borrow_mut();
+
+        if format.as_ref().map(|(name, _)| name.as_str()) != Some(time_zone) {
+            *format = Some((time_zone.to_string(), wall_clock_format(time_zone)));
+        }
+
+        let (_, format) = format.as_ref().unwrap();
+
+        format.as_ref().map(|format| {
+            format.format_to_parts(&Date::new(&JsValue::from_f64(
+                instant.timestamp_millis() as f64
+            )))
+        })
+    });
+
+    let Some(parts) = parts else {
+        return browser();
+    };
+
+    let part = |name: &str| {
+        parts.iter().find_map(|part| {
+            let r#type = Reflect::get(&part, &"type".into()).ok()?.as_string()?;
+
+            if r#type != name {
+                return None;
+            }
+
+            Reflect::get(&part, &"value".into())
+                .ok()?
+                .as_string()?
+                .parse::<u32>()
+                .ok()
+        })
+    };
+
+    let wall_clock = NaiveDate::from_ymd_opt(
+        part("year").unwrap_or_default() as i32,
+        part("month").unwrap_or_default(),
+        part("day").unwrap_or_default(),
+    )
+    .and_then(|date| {
+        date.and_hms_opt(
+            part("hour").unwrap_or_default(),
+            part("minute").unwrap_or_default(),
+            part("second").unwrap_or_default(),
+        )
+    });
+
+    wall_clock
+        .and_then(|wall_clock| {
+            let seconds = (wall_clock - instant.naive_utc()).num_seconds();
+
+            FixedOffset::east_opt(seconds as i32)
+        })
+        .unwrap_or_else(browser)
+}
+
+/// `instant` in the preferred time zone.
+pub fn to_local(instant: &DateTime<Utc>) -> DateTime<FixedOffset> {
+    let time_zone = current().time_zone;
+
+    instant.with_timezone(&offset(time_zone.as_deref(), instant))
+}
+
+/// Instant of a wall clock time in the preferred time zone. The offset is
+/// looked up twice so times next to a DST change land on the right side.
+pub fn from_local(local: &NaiveDateTime) -> DateTime<Utc> {
+    let time_zone = current().time_zone;
+
+    let shift = |offset: FixedOffset| Duration::seconds(offset.local_minus_utc().into());
+
+    let guess = Utc.from_utc_datetime(local);
+
+    let guess = guess - shift(offset(time_zone.as_deref(), &guess));
+
+    Utc.from_utc_datetime(local) - shift(offset(time_zone.as_deref(), &guess))
+}
+
+/// Current date in the preferred time zone.
+pub fn today() -> NaiveDate {
+    to_local(&Utc::now()).date_naive()
+}
+
+/// Local storage key of the last theme, `index.html` reads it before the app
+/// loads so pages don't flash in the wrong theme.
+const THEME_KEY: &str = "theme";
+
+/// Puts the theme and the language on the document root.
+pub fn apply(preferences: &Preferences) {
+    let Some(root) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element())
+    else {
+        return;
+    };
+
+    let _ = match preferences.theme {
+        Theme::System => root.remove_attribute("data-theme"),
+        Theme::Light => root.set_attribute("data-theme", "light"),
+        Theme::Dark => root.set_attribute("data-theme", "dark"),
+    };
+
+    let _ = LocalStorage::set(THEME_KEY, preferences.theme);
+
+    let _ = root.set_attribute("lang", &preferences.locale);
+}
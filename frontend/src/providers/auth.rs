@@ -4,8 +4,9 @@ use yew_router::prelude::*;
 use yewdux::prelude::*;
 
 use crate::{
-    api::use_api,
+    api::{error::ApiError, types::Preferences, use_api},
     components::spinner::SpinnerLarge,
+    offline,
     router::Route,
     store::{Action, Store},
 };
@@ -23,21 +24,54 @@ pub fn auth_provider(props: &AuthProviderProps) -> Html {
 
     let user = use_selector(|store: &Store| store.user.clone());
 
+    let preferences = use_selector(|store: &Store| store.preferences.clone());
+
     use_async_with_options(
         async move {
-            match api.profile().await {
-                Ok(user) => Store::dispatch(Action::SetUser(Some(user))),
-                Err(err) => Store::dispatch(Action::SignReject(err.to_string())),
+            let user = match api.profile().await {
+                Ok(user) => user,
+                Err(ApiError::Network(err)) => {
+                    match offline::saved_profile() {
+                        Some((user, preferences)) => {
+                            Store::dispatch(Action::SetPreferences(Some(preferences)));
+
+                            Store::dispatch(Action::SetUser(Some(user)));
+                        }
+                        None => Store::dispatch(Action::SignReject(err)),
+                    }
+
+                    return Ok(());
+                }
+                Err(err) => {
+                    Store::dispatch(Action::SignReject(err.to_string()));
+
+                    return Ok(());
+                }
+            };
+
+            let preferences = match api.preferences().await {
+                Ok(preferences) => preferences,
+                Err(err) => {
+                    Store::dispatch(Action::AlertError(err.to_string()));
+
+                    Preferences::default()
+                }
             };
 
+            offline::save_profile(&user, &preferences);
+
+            Store::dispatch(Action::SetPreferences(Some(preferences)));
+
+            Store::dispatch(Action::SetUser(Some(user)));
+
             Ok(()) as Result<(), ()>
         },
         UseAsyncOptions::enable_auto(),
     );
 
-    match (token.as_ref(), user.as_ref()) {
-        (Some(_), Some(_)) => html!(<>{ for props.children.iter() }</>),
-        (Some(_), None) => html!(<SpinnerLarge />),
+    match (token.as_ref(), user.as_ref(), preferences.as_ref()) {
+        (Some(_), Some(_), Some(_)) => html!(<>{ for props.children.iter() }</>),
+        (Some(_), _, _) => html!(<SpinnerLarge />),
         _ => html!(<Redirect<Route> to={Route::SignIn} />),
     }
 }
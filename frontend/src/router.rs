@@ -2,8 +2,19 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 
 use crate::{
-    components::header::Header,
-    pages::{sign_in::SignIn, sign_up::SignUp, todos::Todos},
+    api::types::DefaultView,
+    components::{header::Header, shortcuts::Shortcuts},
+    pages::{
+        board::Kanban,
+        calendar::{Calendar, CalendarView},
+        import::Import,
+        settings::Settings,
+        sign_in::SignIn,
+        sign_up::SignUp,
+        stats::Stats,
+        todos::Todos,
+    },
+    preferences,
     providers::{auth::AuthProvider, guest::GuestProvider},
 };
 
@@ -36,6 +47,7 @@ pub fn switch(route: Route) -> Html {
         Route::PrivateRoot | Route::Private => html!(
             <AuthProvider>
                 <Header />
+                <Shortcuts />
                 <Switch<PrivateRoute> render={switch_private} />
             </AuthProvider>
         ),
@@ -48,11 +60,25 @@ pub fn switch(route: Route) -> Html {
 #[derive(Debug, Clone, PartialEq, Routable)]
 pub enum PrivateRoute {
     #[at("/")]
+    Home,
+    #[at("/active")]
     Active,
     #[at("/all")]
     All,
     #[at("/completed")]
     Completed,
+    #[at("/board")]
+    Board,
+    #[at("/calendar")]
+    CalendarMonth,
+    #[at("/calendar/week")]
+    CalendarWeek,
+    #[at("/import")]
+    Import,
+    #[at("/stats")]
+    Stats,
+    #[at("/settings")]
+    Settings,
     #[not_found]
     #[at("/404")]
     NotFound,
@@ -60,9 +86,26 @@ pub enum PrivateRoute {
 
 pub fn switch_private(route: PrivateRoute) -> Html {
     match route {
+        PrivateRoute::Home => {
+            let to = match preferences::current().default_view {
+                DefaultView::Active => PrivateRoute::Active,
+                DefaultView::All => PrivateRoute::All,
+                DefaultView::Completed => PrivateRoute::Completed,
+                DefaultView::Board => PrivateRoute::Board,
+                DefaultView::Calendar => PrivateRoute::CalendarMonth,
+            };
+
+            html!(<Redirect<PrivateRoute> to={ to } />)
+        }
         PrivateRoute::Active => html!(<Todos is_completed={ Some(false) } />),
         PrivateRoute::All => html!(<Todos is_completed={ None } />),
         PrivateRoute::Completed => html!(<Todos is_completed={ Some(true) } />),
-        PrivateRoute::NotFound => html!(<Redirect<PrivateRoute> to={PrivateRoute::Active} />),
+        PrivateRoute::Board => html!(<Kanban />),
+        PrivateRoute::CalendarMonth => html!(<Calendar view={ CalendarView::Month } />),
+        PrivateRoute::CalendarWeek => html!(<Calendar view={ CalendarView::Week } />),
+        PrivateRoute::Import => html!(<Import />),
+        PrivateRoute::Stats => html!(<Stats />),
+        PrivateRoute::Settings => html!(<Settings />),
+        PrivateRoute::NotFound => html!(<Redirect<PrivateRoute> to={PrivateRoute::Home} />),
     }
 }
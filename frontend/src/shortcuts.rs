@@ -0,0 +1,71 @@
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, KeyboardEvent};
+
+use crate::router::PrivateRoute;
+
+/// Keys and the translation key of what they do, for the help overlay.
+pub const BINDINGS: &[(&str, &str)] = &[
+    ("Ctrl K", "shortcuts.palette"),
+    ("?", "shortcuts.help"),
+    ("n", "shortcuts.new"),
+    ("/", "shortcuts.search"),
+    ("j / k", "shortcuts.select"),
+    ("x", "shortcuts.toggle"),
+    ("e", "shortcuts.rename"),
+    ("#", "shortcuts.delete"),
+    ("g a", "shortcuts.go_active"),
+    ("g l", "shortcuts.go_all"),
+    ("g c", "shortcuts.go_completed"),
+    ("g b", "shortcuts.go_board"),
+    ("g m", "shortcuts.go_calendar"),
+    ("Esc", "shortcuts.escape"),
+];
+
+/// Views the command palette offers, with the translation key of their name.
+pub fn views() -> Vec<(&'static str, PrivateRoute)> {
+    vec![
+        ("nav.active", PrivateRoute::Active),
+        ("nav.all", PrivateRoute::All),
+        ("nav.completed", PrivateRoute::Completed),
+        ("nav.board", PrivateRoute::Board),
+        ("nav.calendar", PrivateRoute::CalendarMonth),
+        ("nav.import", PrivateRoute::Import),
+        ("nav.stats", PrivateRoute::Stats),
+        ("nav.settings", PrivateRoute::Settings),
+    ]
+}
+
+/// View for the key following `g`.
+pub fn go_to(key: &str) -> Option<PrivateRoute> {
+    match key {
+        "a" => Some(PrivateRoute::Active),
+        "l" => Some(PrivateRoute::All),
+        "c" => Some(PrivateRoute::Completed),
+        "b" => Some(PrivateRoute::Board),
+        "m" => Some(PrivateRoute::CalendarMonth),
+        _ => None,
+    }
+}
+
+/// Whether `e` opens the command palette, Ctrl+K or Cmd+K on macOS.
+pub fn is_palette(e: &KeyboardEvent) -> bool {
+    (e.ctrl_key() || e.meta_key()) && e.key().eq_ignore_ascii_case("k")
+}
+
+/// The key of a shortcut, `None` while typing into a field or with Ctrl, Alt
+/// or Cmd held. Shift is allowed for keys like `?` and `#`.
+pub fn key(e: &KeyboardEvent) -> Option<String> {
+    if e.ctrl_key() || e.alt_key() || e.meta_key() || e.default_prevented() {
+        return None;
+    }
+
+    let is_typing = e
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .is_some_and(|element| {
+            element.is_content_editable()
+                || matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        });
+
+    (!is_typing).then(|| e.key())
+}
@@ -1,17 +1,32 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{DateTime, TimeZone, Utc};
 use gloo_storage::{LocalStorage, Storage};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use yew::Callback;
 use yewdux::prelude::*;
 
+use crate::{
+    api::types::{Preferences, TimeEntry, Todo},
+    offline, preferences,
+};
+
 #[derive(Debug, Clone, PartialEq, Store)]
 pub struct Store {
     pub alerts: VecDeque<Alert>,
     pub token: Option<Token>,
     pub user: Option<User>,
+    pub preferences: Option<Preferences>,
+    pub timer: Option<TimeEntry>,
+    pub sync_status: SyncStatus,
+    /// Changes made offline that the server hasn't seen yet.
+    pub pending_mutations: usize,
+    /// Todos as last loaded, with changes not yet confirmed by the server.
+    pub todos: HashMap<Uuid, Todo>,
+    /// Todos deleted within the undo grace period, hidden until it ends.
+    pub deleting: HashSet<Uuid>,
 }
 
 impl Default for Store {
@@ -22,6 +37,16 @@ impl Default for Store {
                 .ok()
                 .map(|token| token.with_claims()),
             user: None,
+            preferences: None,
+            timer: None,
+            sync_status: if offline::is_online() {
+                SyncStatus::Online
+            } else {
+                SyncStatus::Offline
+            },
+            pending_mutations: 0,
+            todos: HashMap::new(),
+            deleting: HashSet::new(),
         }
     }
 }
@@ -30,6 +55,8 @@ impl Default for Store {
 pub struct Alert {
     pub r#type: AlertType,
     pub text: String,
+    /// Shows an "Undo" button, the alert stays for the undo grace period.
+    pub undo: Option<Callback<()>>,
 }
 
 impl Alert {
@@ -37,6 +64,14 @@ impl Alert {
         Self {
             r#type,
             text: text.to_string(),
+            undo: None,
+        }
+    }
+
+    pub fn with_undo(self, undo: Callback<()>) -> Self {
+        Self {
+            undo: Some(undo),
+            ..self
         }
     }
 
@@ -60,6 +95,13 @@ pub enum AlertType {
     Success,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncStatus {
+    Online,
+    Offline,
+    Syncing,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Token {
     pub access: String,
@@ -100,7 +142,7 @@ impl Claims {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct User {
     pub id: Uuid,
     pub username: String,
@@ -114,11 +156,21 @@ pub enum Action {
     SignReject(String),
     SetToken(Option<Token>),
     SetUser(Option<User>),
+    SetPreferences(Option<Preferences>),
+    SetTimer(Option<TimeEntry>),
+    SetSyncStatus(SyncStatus),
+    SetPendingMutations(usize),
+    SetTodos(Vec<Todo>),
+    SetTodo(Todo),
+    HideTodo(Uuid),
+    ShowTodo(Uuid),
+    RemoveTodo(Uuid),
     Alert(Alert),
     AlertSuccess(String),
     AlertWarning(String),
     AlertError(String),
     PopAlert,
+    RemoveAlert(Alert),
 }
 
 impl Store {
@@ -136,11 +188,27 @@ impl Store {
                     Action::SignReject(text) => Store::sign_reject(store, text),
                     Action::SetToken(token) => Store::set_token(store, token),
                     Action::SetUser(user) => Store::set_user(store, user),
+                    Action::SetPreferences(preferences) => {
+                        Store::set_preferences(store, preferences)
+                    }
+                    Action::SetTimer(timer) => Store::set_timer(store, timer),
+                    Action::SetSyncStatus(status) => store.sync_status = status,
+                    Action::SetPendingMutations(count) => store.pending_mutations = count,
+                    Action::SetTodos(todos) => Store::set_todos(store, todos),
+                    Action::SetTodo(todo) => Store::set_todos(store, vec![todo]),
+                    Action::HideTodo(id) => {
+                        store.deleting.insert(id);
+                    }
+                    Action::ShowTodo(id) => {
+                        store.deleting.remove(&id);
+                    }
+                    Action::RemoveTodo(id) => Store::remove_todo(store, id),
                     Action::Alert(alert) => Store::alert(store, alert),
                     Action::AlertSuccess(text) => Store::alert(store, Alert::new_success(&text)),
                     Action::AlertWarning(text) => Store::alert(store, Alert::new_warning(&text)),
                     Action::AlertError(text) => Store::alert(store, Alert::new_error(&text)),
                     Action::PopAlert => Store::pop_alert(store),
+                    Action::RemoveAlert(alert) => Store::remove_alert(store, alert),
                 };
             });
         });
@@ -168,6 +236,18 @@ impl Store {
         store.token = None;
 
         store.user = None;
+
+        Store::set_preferences(store, None);
+
+        store.timer = None;
+
+        store.pending_mutations = 0;
+
+        store.todos.clear();
+
+        store.deleting.clear();
+
+        offline::clear();
     }
 
     fn sign_reject(store: &mut Store, text: String) {
@@ -177,6 +257,18 @@ impl Store {
 
         store.user = None;
 
+        Store::set_preferences(store, None);
+
+        store.timer = None;
+
+        store.pending_mutations = 0;
+
+        store.todos.clear();
+
+        store.deleting.clear();
+
+        offline::clear();
+
         store.alerts.push_back(Alert::new_error(&text));
     }
 
@@ -201,6 +293,28 @@ impl Store {
         store.user = user;
     }
 
+    fn set_preferences(store: &mut Store, preferences: Option<Preferences>) {
+        preferences::apply(preferences.as_ref().unwrap_or(&Preferences::default()));
+
+        store.preferences = preferences;
+    }
+
+    fn set_timer(store: &mut Store, timer: Option<TimeEntry>) {
+        store.timer = timer;
+    }
+
+    fn set_todos(store: &mut Store, todos: Vec<Todo>) {
+        store
+            .todos
+            .extend(todos.into_iter().map(|todo| (todo.id, todo)));
+    }
+
+    fn remove_todo(store: &mut Store, id: Uuid) {
+        store.todos.remove(&id);
+
+        store.deleting.remove(&id);
+    }
+
     fn alert(store: &mut Store, alert: Alert) {
         store.alerts.push_back(alert);
     }
@@ -208,4 +322,8 @@ impl Store {
     fn pop_alert(store: &mut Store) {
         store.alerts.pop_front();
     }
+
+    fn remove_alert(store: &mut Store, alert: Alert) {
+        store.alerts.retain(|other| *other != alert);
+    }
 }
@@ -0,0 +1,87 @@
+// Keeps the app shell cached so the app starts without a connection. API
+// requests are left to the app, it caches todos itself.
+const CACHE = "todos-shell-v1";
+
+// Caches the shell and the hashed bundle trunk links from it (wasm, js and
+// css), so the app starts offline before it was ever reloaded online.
+const precache = (cache) =>
+  fetch("/", { cache: "no-cache" }).then((response) => {
+    if (!response.ok) {
+      throw new Error(`Shell responded with ${response.status}`);
+    }
+
+    return response
+      .clone()
+      .text()
+      .then((html) => {
+        const assets = [
+          ...html.matchAll(/["']([^"':]+\.(?:wasm|js|css))["']/g),
+        ]
+          .map((match) => new URL(match[1], self.location.origin).href)
+          .filter((href) => href !== self.location.href);
+
+        return Promise.all([
+          cache.put("/", response),
+          cache.addAll([...new Set(assets)]),
+        ]);
+      });
+  });
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    caches
+      .open(CACHE)
+      .then(precache)
+      .then(() => self.skipWaiting())
+  );
+});
+
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) =>
+        Promise.all(
+          keys.filter((key) => key !== CACHE).map((key) => caches.delete(key))
+        )
+      )
+      .then(() => self.clients.claim())
+  );
+});
+
+self.addEventListener("fetch", (event) => {
+  const url = new URL(event.request.url);
+
+  if (
+    event.request.method !== "GET" ||
+    url.origin !== self.location.origin ||
+    url.pathname.startsWith("/api")
+  ) {
+    return;
+  }
+
+  // Network first so deploys show up right away, the cache when offline.
+  event.respondWith(
+    fetch(event.request)
+      .then((response) => {
+        if (response.ok) {
+          const copy = response.clone();
+
+          caches.open(CACHE).then((cache) => cache.put(event.request, copy));
+        }
+
+        return response;
+      })
+      .catch(() =>
+        caches
+          .match(event.request)
+          .then(
+            (response) =>
+              response ||
+              (event.request.mode === "navigate"
+                ? caches.match("/")
+                : Response.error())
+          )
+      )
+  );
+});
@@ -5,4 +5,9 @@ module.exports = {
     extend: {},
   },
   plugins: [require("daisyui")],
+  daisyui: {
+    // The first theme is the default, `darkTheme` follows `prefers-color-scheme`.
+    themes: ["light", "dark"],
+    darkTheme: "dark",
+  },
 }